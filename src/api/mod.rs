@@ -1,89 +1,410 @@
-use std::str::FromStr;
-
-use anyhow::anyhow;
+use log::warn;
 
 use crate::{
+    errors::SupplierError,
+    frb_generated::StreamSink,
     models::*,
     suppliers::{
-        get_supplier, AllContentSuppliers, AllMangaPagesLoaders, ContentSupplier, MangaPagesLoader,
+        self, AllContentSuppliers, AllMangaPagesLoaders, ContentSupplier, MangaPagesLoader,
+        get_manga_pages_loader, get_supplier,
+    },
+    utils::{
+        cache::{self, CacheOperation},
+        cancel, cookies, dns_over_https, downloader, har, hls, hls_proxy, proxy,
+        request_policy::{self, RequestPolicy},
+        sources_sink::SourcesSink,
     },
 };
 
 #[flutter_rust_bridge::frb(sync)]
-pub fn get_channels(supplier: String) -> anyhow::Result<Vec<String>> {
+pub fn get_channels(supplier: String) -> Result<Vec<String>, SupplierError> {
     let sup = get_supplier(&supplier)?;
-    Ok(AllContentSuppliers::get_channels(&sup))
+    Ok(AllContentSuppliers::get_channels(sup))
 }
 
 #[flutter_rust_bridge::frb(sync)]
-pub fn get_default_channels(supplier: String) -> anyhow::Result<Vec<String>> {
+pub fn get_default_channels(supplier: String) -> Result<Vec<String>, SupplierError> {
     let sup = get_supplier(&supplier)?;
-    Ok(AllContentSuppliers::get_default_channels(&sup))
+    Ok(AllContentSuppliers::get_default_channels(sup))
 }
 
 #[flutter_rust_bridge::frb(sync)]
-pub fn get_supported_types(supplier: String) -> anyhow::Result<Vec<ContentType>> {
+pub fn get_supported_types(supplier: String) -> Result<Vec<ContentType>, SupplierError> {
     let sup = get_supplier(&supplier)?;
-    Ok(AllContentSuppliers::get_supported_types(&sup))
+    Ok(AllContentSuppliers::get_supported_types(sup))
 }
 
 #[flutter_rust_bridge::frb(sync)]
-pub fn get_supported_languages(supplier: String) -> anyhow::Result<Vec<String>> {
+pub fn get_supported_languages(supplier: String) -> Result<Vec<String>, SupplierError> {
     let sup = get_supplier(&supplier)?;
-    Ok(AllContentSuppliers::get_supported_languages(&sup))
+    Ok(AllContentSuppliers::get_supported_languages(sup))
+}
+
+/// Everything host app needs to know about supplier in one call
+#[flutter_rust_bridge::frb(sync)]
+pub fn get_supplier_info(supplier: String) -> Result<SupplierInfo, SupplierError> {
+    suppliers::get_supplier_info(&supplier)
 }
 
 pub async fn search(
     supplier: String,
-    query: String, 
+    query: String,
     page: u16,
-) -> anyhow::Result<Vec<ContentInfo>> {
+    cancel_token: Option<CancelToken>,
+) -> Result<Vec<ContentInfo>, SupplierError> {
     let sup = get_supplier(&supplier)?;
-    AllContentSuppliers::search(&sup, &query, page).await
+    let call = cache::scoped(
+        CacheOperation::Search,
+        AllContentSuppliers::search(sup, &query, page),
+    );
+    cancel::run(cancel_token, call).await
+}
+
+/// Search in several suppliers concurrently, results are streamed per supplier and de-duplicated by title
+pub async fn search_all(
+    query: String,
+    filters: SearchFilters,
+    sink: StreamSink<SearchAllEvent>,
+    cancel_token: Option<CancelToken>,
+) -> Result<(), SupplierError> {
+    let call = cache::scoped(CacheOperation::Search, async {
+        suppliers::search_all(&query, &filters, |event| {
+            if let Err(err) = sink.add(event) {
+                warn!("fail to push search event: {err:?}");
+            }
+        })
+        .await;
+        Ok(())
+    });
+    cancel::run(cancel_token, call).await
 }
 
 pub async fn load_channel(
     supplier: String,
     channel: String,
     page: u16,
-) -> anyhow::Result<Vec<ContentInfo>> {
+    cancel_token: Option<CancelToken>,
+) -> Result<Vec<ContentInfo>, SupplierError> {
     let sup = get_supplier(&supplier)?;
-    AllContentSuppliers::load_channel(&sup, &channel, page).await
+    let call = cache::scoped(
+        CacheOperation::Channel,
+        AllContentSuppliers::load_channel(sup, &channel, page),
+    );
+    cancel::run(cancel_token, call).await
 }
 
 pub async fn get_content_details(
     supplier: String,
     id: String,
-) -> anyhow::Result<Option<ContentDetails>> {
+    cancel_token: Option<CancelToken>,
+) -> Result<Option<ContentDetails>, SupplierError> {
     let sup = get_supplier(&supplier)?;
-    AllContentSuppliers::get_content_details(&sup, &id).await
+    let call = cache::scoped(
+        CacheOperation::ContentDetails,
+        AllContentSuppliers::get_content_details(sup, &id),
+    );
+    cancel::run(cancel_token, call).await
 }
 
 pub async fn load_media_items(
     supplier: String,
     id: String,
     params: Vec<String>,
-) -> anyhow::Result<Vec<ContentMediaItem>> {
+    cancel_token: Option<CancelToken>,
+) -> Result<Vec<ContentMediaItem>, SupplierError> {
     let sup = get_supplier(&supplier)?;
-    AllContentSuppliers::load_media_items(&sup, &id, params).await
+    let call = cache::scoped(
+        CacheOperation::MediaItems,
+        AllContentSuppliers::load_media_items(sup, &id, params),
+    );
+    cancel::run(cancel_token, call).await
 }
 
 pub async fn load_media_item_sources(
     supplier: String,
     id: String,
     params: Vec<String>,
-) -> anyhow::Result<Vec<ContentMediaItemSource>> {
+    cancel_token: Option<CancelToken>,
+) -> Result<Vec<ContentMediaItemSource>, SupplierError> {
     let sup = get_supplier(&supplier)?;
-    AllContentSuppliers::load_media_item_sources(&sup, &id, params).await
+    let call = cache::scoped(
+        CacheOperation::MediaItemSources,
+        AllContentSuppliers::load_media_item_sources(sup, &id, params),
+    );
+    cancel::run(cancel_token, call).await
+}
+
+/// Same as `load_media_item_sources`, but sources are pushed as soon as each extractor resolves them.
+/// Stream ends with `Completed` or `Error` event
+pub async fn load_media_item_sources_stream(
+    supplier: String,
+    id: String,
+    params: Vec<String>,
+    sink: StreamSink<SourcesEvent>,
+    cancel_token: Option<CancelToken>,
+) -> Result<(), SupplierError> {
+    let sources_sink = SourcesSink::new(move |event| {
+        if let Err(err) = sink.add(event) {
+            warn!("fail to push sources event: {err:?}");
+        }
+    });
+
+    let result = async {
+        let sup = get_supplier(&supplier)?;
+        let call = cache::scoped(
+            CacheOperation::MediaItemSources,
+            AllContentSuppliers::stream_media_item_sources(sup, &id, params, &sources_sink),
+        );
+        cancel::run(cancel_token, call).await
+    }
+    .await;
+
+    // stream always ends with terminal event, call errors are reported through it
+    match result {
+        Ok(()) => sources_sink.complete(),
+        Err(err) => sources_sink.abort(err),
+    }
+    Ok(())
 }
 
 pub async fn load_manga_pages(
     supplier: String,
     id: String,
     params: Vec<String>,
-) -> anyhow::Result<Vec<String>> {
-    let loader = AllMangaPagesLoaders::from_str(&supplier).map_err(|err| anyhow!(err))?;
-    AllMangaPagesLoaders::load_pages(&loader, &id, params).await
+    cancel_token: Option<CancelToken>,
+) -> Result<Vec<String>, SupplierError> {
+    let loader = get_manga_pages_loader(&supplier)?;
+    let call = cache::scoped(
+        CacheOperation::MangaPages,
+        AllMangaPagesLoaders::load_pages(&loader, &id, params),
+    );
+    cancel::run(cancel_token, call).await
+}
+
+#[flutter_rust_bridge::frb(sync)]
+pub fn create_cancel_token() -> CancelToken {
+    cancel::create_token()
+}
+
+/// Abort every call started with given token
+#[flutter_rust_bridge::frb(sync)]
+pub fn trigger_cancel_token(token: CancelToken) {
+    cancel::cancel(token)
+}
+
+/// Forget token once screen that owns it is gone
+#[flutter_rust_bridge::frb(sync)]
+pub fn release_cancel_token(token: CancelToken) {
+    cancel::release(token)
+}
+
+#[flutter_rust_bridge::frb(sync)]
+pub fn set_cache_dir(path: String) -> Result<(), SupplierError> {
+    cache::set_cache_dir(&path)
+}
+
+/// Always revalidate cached responses with the site (pull to refresh, debugging)
+#[flutter_rust_bridge::frb(sync)]
+pub fn set_cache_bypass(bypass: bool) {
+    cache::set_bypass(bypass)
+}
+
+#[flutter_rust_bridge::frb(sync)]
+pub fn clear_cache() -> Result<(), SupplierError> {
+    cache::clear()
+}
+
+/// Persist shared cookie jar to file, loads cookies saved there before
+#[flutter_rust_bridge::frb(sync)]
+pub fn set_cookies_file(path: String) -> Result<(), SupplierError> {
+    cookies::set_cookies_file(&path)
+}
+
+/// Hand cookies to all supplier requests, e.g. challenge clearance solved in WebView
+#[flutter_rust_bridge::frb(sync)]
+pub fn import_cookies(cookies: Vec<HttpCookie>) -> Result<(), SupplierError> {
+    cookies::import(&cookies)
+}
+
+/// Cookies of domain (with subdomains), all cookies when domain is not set
+#[flutter_rust_bridge::frb(sync)]
+pub fn export_cookies(domain: Option<String>) -> Vec<HttpCookie> {
+    cookies::export(domain.as_deref())
+}
+
+/// Remove cookies of domain (with subdomains), all cookies when domain is not set
+#[flutter_rust_bridge::frb(sync)]
+pub fn clear_cookies(domain: Option<String>) {
+    cookies::clear(domain.as_deref())
+}
+
+/// Override rate limit and retry settings for all requests to given host
+#[flutter_rust_bridge::frb(sync)]
+pub fn set_request_policy(host: String, policy: RequestPolicy) -> Result<(), SupplierError> {
+    policy.validate()?;
+    request_policy::set_host_policy(&host, policy);
+    Ok(())
+}
+
+#[flutter_rust_bridge::frb(sync)]
+pub fn reset_request_policy(host: String) {
+    request_policy::reset_host_policy(&host)
+}
+
+/// Write all requests and responses to HAR file (attach to bug reports), secrets are redacted
+#[flutter_rust_bridge::frb(sync)]
+pub fn start_http_recording(path: String) -> Result<(), SupplierError> {
+    har::start_recording(&path)
+}
+
+/// Serve all requests from previously recorded HAR file instead of network
+#[flutter_rust_bridge::frb(sync)]
+pub fn start_http_replay(path: String) -> Result<(), SupplierError> {
+    har::start_replay(&path)
+}
+
+/// Stop recording or replay, recorded requests are written to HAR file now
+#[flutter_rust_bridge::frb(sync)]
+pub fn stop_http_recording() -> Result<(), SupplierError> {
+    har::stop()
+}
+
+/// Register supplier described by json or toml definition, returns its name
+#[flutter_rust_bridge::frb(sync)]
+pub fn register_supplier_definition(definition: String) -> Result<String, SupplierError> {
+    suppliers::register_supplier_definition(&definition)
+}
+
+/// Register definitions from all `*.json` and `*.toml` files of directory, returns their names
+#[flutter_rust_bridge::frb(sync)]
+pub fn load_supplier_definitions(dir: String) -> Result<Vec<String>, SupplierError> {
+    suppliers::load_supplier_definitions(&dir)
+}
+
+/// Current values of supplier setting described in `SupplierInfo::settings`
+#[flutter_rust_bridge::frb(sync)]
+pub fn get_supplier_setting(supplier: String, key: String) -> Result<Vec<String>, SupplierError> {
+    suppliers::get_supplier_setting(&supplier, &key)
+}
+
+/// Change supplier setting, empty values restore default
+#[flutter_rust_bridge::frb(sync)]
+pub fn set_supplier_setting(
+    supplier: String,
+    key: String,
+    values: Vec<String>,
+) -> Result<(), SupplierError> {
+    suppliers::set_supplier_setting(&supplier, &key, values)
+}
+
+/// Restore defaults of all supplier settings
+#[flutter_rust_bridge::frb(sync)]
+pub fn reset_supplier_settings(supplier: String) -> Result<(), SupplierError> {
+    suppliers::reset_supplier_settings(&supplier)
+}
+
+/// Set or rotate secret (api key, token) of supplier, empty value removes it
+#[flutter_rust_bridge::frb(sync)]
+pub fn set_supplier_credential(
+    supplier: String,
+    credential: String,
+    value: String,
+) -> Result<(), SupplierError> {
+    suppliers::set_supplier_credential(&supplier, &credential, &value)
+}
+
+/// Remove all secrets of supplier
+#[flutter_rust_bridge::frb(sync)]
+pub fn clear_supplier_credentials(supplier: String) -> Result<(), SupplierError> {
+    suppliers::clear_supplier_credentials(&supplier)
+}
+
+/// Mirrors of supplier site in failover order
+#[flutter_rust_bridge::frb(sync)]
+pub fn get_supplier_mirrors(supplier: String) -> Result<Vec<String>, SupplierError> {
+    suppliers::get_supplier_mirrors(&supplier)
+}
+
+/// Replace mirrors of supplier site (e.g. from remote config), empty list restores defaults
+#[flutter_rust_bridge::frb(sync)]
+pub fn set_supplier_mirrors(supplier: String, mirrors: Vec<String>) -> Result<(), SupplierError> {
+    suppliers::set_supplier_mirrors(&supplier, &mirrors)
+}
+
+/// Load master playlists of video sources and add qualities and codecs to descriptions.
+/// With `split` each variant, alternative audio track and subtitle becomes own source,
+/// variants with separate audio tracks need running hls proxy
+pub async fn expand_hls_sources(
+    sources: Vec<ContentMediaItemSource>,
+    split: bool,
+    cancel_token: Option<CancelToken>,
+) -> Result<Vec<ContentMediaItemSource>, SupplierError> {
+    let call = async { Ok(hls::expand_sources(sources, split).await) };
+    cancel::run(cancel_token, call).await
+}
+
+/// Download video source for offline viewing, container extension is added to `output`.
+/// Calling again with same output after error or cancel continues interrupted download
+pub async fn download_source(
+    source: ContentMediaItemSource,
+    output: String,
+    options: DownloadOptions,
+    sink: StreamSink<DownloadEvent>,
+    cancel_token: Option<CancelToken>,
+) -> Result<(), SupplierError> {
+    let call = downloader::download(source, &output, &options, |event| {
+        if let Err(err) = sink.add(event) {
+            warn!("fail to push download event: {err:?}");
+        }
+    });
+    cancel::run(cancel_token, call).await?;
+    Ok(())
+}
+
+/// Start local proxy for sources with `hls_proxy` flag, returns its base url.
+/// `allow_lan` makes links reachable from cast targets in same network
+pub async fn start_hls_proxy(port: u16, allow_lan: bool) -> Result<String, SupplierError> {
+    hls_proxy::start(port, allow_lan).await
+}
+
+#[flutter_rust_bridge::frb(sync)]
+pub fn stop_hls_proxy() {
+    hls_proxy::stop()
+}
+
+/// Video or subtitle source with link to local proxy, which sends source headers upstream
+#[flutter_rust_bridge::frb(sync)]
+pub fn proxy_media_source(
+    source: ContentMediaItemSource,
+) -> Result<ContentMediaItemSource, SupplierError> {
+    hls_proxy::proxy_source(source)
+}
+
+/// Proxy of all supplier requests, `None` sends them directly
+#[flutter_rust_bridge::frb(sync)]
+pub fn set_proxy(config: Option<ProxyConfig>) -> Result<(), SupplierError> {
+    proxy::set_default(config.as_ref())
+}
+
+/// Per-supplier proxy override, e.g. to send only geo-blocked suppliers through proxy.
+/// Covers supplier site and its mirrors, players and cdns on other hosts use default proxy
+#[flutter_rust_bridge::frb(sync)]
+pub fn set_supplier_proxy(
+    supplier: String,
+    supplier_proxy: SupplierProxy,
+) -> Result<(), SupplierError> {
+    suppliers::set_supplier_proxy(&supplier, &supplier_proxy)
+}
+
+/// Resolvers chain, static host overrides and cache ttl used by all supplier requests
+#[flutter_rust_bridge::frb(sync)]
+pub fn set_dns_config(config: DnsConfig) -> Result<(), SupplierError> {
+    dns_over_https::set_config(&config)
+}
+
+/// Resolve host like supplier requests do, reports which resolver answered
+pub async fn dns_lookup(host: String) -> Result<DnsLookup, SupplierError> {
+    dns_over_https::lookup(&host).await
 }
 
 #[flutter_rust_bridge::frb(sync)] // Synchronous mode for simplicity of the demo
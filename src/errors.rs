@@ -0,0 +1,233 @@
+use std::fmt;
+
+pub type SupplierResult<T> = Result<T, SupplierError>;
+
+#[derive(Debug, Clone)]
+pub enum SupplierError {
+    /// site is unreachable (dns, connection reset, tls...)
+    Network {
+        message: String,
+    },
+    Timeout,
+    /// site answered with non success status code
+    HttpStatus {
+        status: u16,
+        url: String,
+    },
+    /// response or page layout doesn't match what supplier expects
+    Parse {
+        message: String,
+    },
+    NotFound {
+        message: String,
+    },
+    /// required field is missing on the page, supplier selectors need update
+    LayoutChanged {
+        /// fields and scopes from processor root to broken selector
+        path: String,
+        /// selector or `@attr` that matched nothing, empty when element was found without value
+        selector: String,
+    },
+    /// anti-bot protection blocked the request, host app opens `url` in WebView,
+    /// imports cookies once user passed the challenge and retries
+    Blocked {
+        /// `cloudflare`, `ddos-guard` or `captcha`
+        protection: String,
+        url: String,
+    },
+    Unsupported {
+        operation: String,
+    },
+    InvalidParams {
+        message: String,
+    },
+    /// supplier needs credential (api key, token) host app didn't set
+    NotConfigured {
+        supplier: String,
+        credential: String,
+    },
+    /// call was aborted by host app
+    Cancelled,
+    /// local file (cache dir, download output) can't be read or written
+    Storage {
+        message: String,
+    },
+}
+
+impl SupplierError {
+    pub fn parse(message: impl Into<String>) -> Self {
+        SupplierError::Parse {
+            message: message.into(),
+        }
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        SupplierError::NotFound {
+            message: message.into(),
+        }
+    }
+
+    pub fn unsupported(operation: impl Into<String>) -> Self {
+        SupplierError::Unsupported {
+            operation: operation.into(),
+        }
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        SupplierError::InvalidParams {
+            message: message.into(),
+        }
+    }
+
+    pub fn not_configured(supplier: impl Into<String>, credential: impl Into<String>) -> Self {
+        SupplierError::NotConfigured {
+            supplier: supplier.into(),
+            credential: credential.into(),
+        }
+    }
+
+    pub fn storage(message: impl Into<String>) -> Self {
+        SupplierError::Storage {
+            message: message.into(),
+        }
+    }
+
+    pub fn unknown_channel(channel: &str) -> Self {
+        SupplierError::InvalidParams {
+            message: format!("unknown channel: {channel}"),
+        }
+    }
+
+    /// failure that may go away on its own (site down, overloaded, flaky network)
+    pub fn is_transient(&self) -> bool {
+        match self {
+            SupplierError::Network { .. } | SupplierError::Timeout => true,
+            SupplierError::HttpStatus { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for SupplierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SupplierError::Network { message } => write!(f, "network error: {message}"),
+            SupplierError::Timeout => write!(f, "request timed out"),
+            SupplierError::HttpStatus { status, url } => {
+                write!(f, "unexpected status {status} for {url}")
+            }
+            SupplierError::Parse { message } => write!(f, "parse error: {message}"),
+            SupplierError::NotFound { message } => write!(f, "not found: {message}"),
+            SupplierError::LayoutChanged { path, selector } if selector.is_empty() => {
+                write!(f, "layout changed: empty value at `{path}`")
+            }
+            SupplierError::LayoutChanged { path, selector } => {
+                write!(
+                    f,
+                    "layout changed: `{selector}` matched nothing at `{path}`"
+                )
+            }
+            SupplierError::Blocked { protection, url } => {
+                write!(f, "blocked by {protection} protection: {url}")
+            }
+            SupplierError::Unsupported { operation } => {
+                write!(f, "unsupported operation: {operation}")
+            }
+            SupplierError::InvalidParams { message } => write!(f, "invalid params: {message}"),
+            SupplierError::NotConfigured {
+                supplier,
+                credential,
+            } => write!(f, "{supplier} is not configured: {credential} is not set"),
+            SupplierError::Cancelled => write!(f, "cancelled"),
+            SupplierError::Storage { message } => write!(f, "storage error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SupplierError {}
+
+impl From<reqwest::Error> for SupplierError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            return SupplierError::Timeout;
+        }
+
+        if let Some(status) = err.status() {
+            return SupplierError::HttpStatus {
+                status: status.as_u16(),
+                url: err.url().map(|u| u.to_string()).unwrap_or_default(),
+            };
+        }
+
+        if err.is_decode() {
+            return SupplierError::parse(err.to_string());
+        }
+
+        if err.is_builder() {
+            return SupplierError::invalid_params(err.to_string());
+        }
+
+        SupplierError::Network {
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<serde_json::Error> for SupplierError {
+    fn from(err: serde_json::Error) -> Self {
+        SupplierError::parse(err.to_string())
+    }
+}
+
+impl From<std::num::ParseIntError> for SupplierError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        SupplierError::invalid_params(err.to_string())
+    }
+}
+
+// helpers (playerjs, extractors, crypto) still report anyhow errors,
+// keep the typed error when one is wrapped and treat the rest as parse failures
+impl From<anyhow::Error> for SupplierError {
+    fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<SupplierError>() {
+            Ok(err) => return err,
+            Err(err) => err,
+        };
+
+        let err = match err.downcast::<reqwest::Error>() {
+            Ok(err) => return err.into(),
+            Err(err) => err,
+        };
+
+        let err = match err.downcast::<serde_json::Error>() {
+            Ok(err) => return err.into(),
+            Err(err) => err,
+        };
+
+        SupplierError::parse(format!("{err:#}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_keep_typed_error_wrapped_in_anyhow() {
+        let err: SupplierError = anyhow::Error::new(SupplierError::Timeout).into();
+        assert!(matches!(err, SupplierError::Timeout));
+    }
+
+    #[test]
+    fn should_map_json_error_to_parse() {
+        let json_err = serde_json::from_str::<u32>("{").unwrap_err();
+        let err: SupplierError = anyhow::Error::new(json_err).into();
+        assert!(matches!(err, SupplierError::Parse { .. }));
+    }
+
+    #[test]
+    fn should_map_plain_anyhow_to_parse() {
+        let err: SupplierError = anyhow::anyhow!("player not found").into();
+        assert!(matches!(err, SupplierError::Parse { message } if message == "player not found"));
+    }
+}
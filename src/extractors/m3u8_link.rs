@@ -8,10 +8,7 @@ pub async fn extract(
     title: String,
     hls_proxy: bool,
 ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-    let iframe_res = utils::create_client()
-        .get(url)
-        .header("Referer", referer)
-        .send()
+    let iframe_res = utils::send(utils::create_client().get(url).header("Referer", referer))
         .await?
         .text()
         .await?;
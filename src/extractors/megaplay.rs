@@ -30,16 +30,14 @@ pub async fn extract(
     referer: &str,
     title: String,
     hls_proxy: bool,
+    subtitle_langs: &[String],
 ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
     let host = url
         .split("/")
         .nth(2)
         .ok_or_else(|| anyhow!("[megaplay] invalid url"))?;
 
-    let iframe_res = utils::create_client()
-        .get(url)
-        .header("Referer", referer)
-        .send()
+    let iframe_res = utils::send(utils::create_client().get(url).header("Referer", referer))
         .await?
         .text()
         .await?;
@@ -47,13 +45,14 @@ pub async fn extract(
     let data_id = if let Some(id) = find_data_id(&iframe_res) {
         id.to_string()
     } else if let Some(nested_url) = find_nested_iframe_src(&iframe_res) {
-        let nested_res = utils::create_client()
-            .get(nested_url)
-            .header("Referer", &format!("https://{host}/"))
-            .send()
-            .await?
-            .text()
-            .await?;
+        let nested_res = utils::send(
+            utils::create_client()
+                .get(nested_url)
+                .header("Referer", &format!("https://{host}/")),
+        )
+        .await?
+        .text()
+        .await?;
 
         match find_data_id(&nested_res) {
             Some(id) => id.to_string(),
@@ -65,13 +64,14 @@ pub async fn extract(
 
     let api_url = format!("https://{host}/stream/getSources?id={data_id}&id={data_id}");
 
-    let jwpconfig_str = utils::create_json_client()
-        .get(&api_url)
-        .header("Referer", referer)
-        .send()
-        .await?
-        .text()
-        .await?;
+    let jwpconfig_str = utils::send(
+        utils::create_json_client()
+            .get(&api_url)
+            .header("Referer", referer),
+    )
+    .await?
+    .text()
+    .await?;
 
     let jwpconfig: JWPConfig = serde_json::from_str(&jwpconfig_str)?;
 
@@ -82,6 +82,7 @@ pub async fn extract(
             format!("https://{host}/"),
         )])),
         hls_proxy,
+        subtitle_langs,
     ))
 }
 
@@ -94,7 +95,7 @@ mod tests {
         let url = "https://megaplay.buzz/stream/s-1/L3F1cTNLWThmTnV5MWxSU3RWNUVVdz09";
         let referer = "https://anikototv.to/watch/sakamoto-days-sfdxz/ep-5";
 
-        let res = extract(url, referer, "Test".to_string(), true).await;
+        let res = extract(url, referer, "Test".to_string(), true, &["en".into()]).await;
 
         println!("{res:#?}");
     }
@@ -104,7 +105,7 @@ mod tests {
         let url = "https://megaplay.buzz/stream/s-2/373559/sub?autostart=true";
         let referer = "https://anikototv.to/";
 
-        let res = extract(url, referer, "Test".to_string(), true).await;
+        let res = extract(url, referer, "Test".to_string(), true, &["en".into()]).await;
 
         println!("{res:#?}");
     }
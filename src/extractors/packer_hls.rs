@@ -18,13 +18,11 @@ pub async fn extract(
 
     // println!("host: {host}");
 
-    let iframe_res: String = utils::create_client()
-        .get(url)
-        .header("Referer", referer)
-        .send()
-        .await?
-        .text()
-        .await?;
+    let iframe_res: String =
+        utils::send(utils::create_client().get(url).header("Referer", referer))
+            .await?
+            .text()
+            .await?;
 
     // println!("{iframe_res}");
 
@@ -67,7 +65,7 @@ pub async fn extract(
                 link,
                 description: format!("{prefix} {idx}."),
                 headers: Some(HashMap::from([("Referer".to_string(), url.to_string())])),
-                hls_proxy: hls_proxy,
+                hls_proxy,
             }
         })
         .collect();
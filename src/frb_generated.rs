@@ -38,7 +38,7 @@ flutter_rust_bridge::frb_generated_boilerplate!(
     default_rust_auto_opaque = RustAutoOpaqueMoi,
 );
 pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_VERSION: &str = "2.12.0";
-pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_CONTENT_HASH: i32 = -1122641696;
+pub(crate) const FLUTTER_RUST_BRIDGE_CODEGEN_CONTENT_HASH: i32 = -85013147;
 
 // Section: executor
 
@@ -75,14 +75,14 @@ fn wire__crate__api__avalaible_suppliers_impl(
         },
     )
 }
-fn wire__crate__api__get_channels_impl(
+fn wire__crate__api__clear_cache_impl(
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
     data_len_: i32,
 ) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_channels",
+            debug_name: "clear_cache",
             port: None,
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
         },
@@ -96,28 +96,24 @@ fn wire__crate__api__get_channels_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_supplier = <String>::sse_decode(&mut deserializer);
             deserializer.end();
-            transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
-                (move || {
-                    let output_ok = crate::api::get_channels(api_supplier)?;
-                    Ok(output_ok)
-                })(),
-            )
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::clear_cache()?;
+                Ok(output_ok)
+            })())
         },
     )
 }
-fn wire__crate__api__get_content_details_impl(
-    port_: flutter_rust_bridge::for_generated::MessagePort,
+fn wire__crate__api__clear_cookies_impl(
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
     data_len_: i32,
-) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_content_details",
-            port: Some(port_),
-            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+            debug_name: "clear_cookies",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
         },
         move || {
             let message = unsafe {
@@ -129,30 +125,25 @@ fn wire__crate__api__get_content_details_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_supplier = <String>::sse_decode(&mut deserializer);
-            let api_id = <String>::sse_decode(&mut deserializer);
+            let api_domain = <Option<String>>::sse_decode(&mut deserializer);
             deserializer.end();
-            move |context| async move {
-                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
-                    (move || async move {
-                        let output_ok =
-                            crate::api::get_content_details(api_supplier, api_id).await?;
-                        Ok(output_ok)
-                    })()
-                    .await,
-                )
-            }
+            transform_result_sse::<_, ()>((move || {
+                let output_ok = Result::<_, ()>::Ok({
+                    crate::api::clear_cookies(api_domain);
+                })?;
+                Ok(output_ok)
+            })())
         },
     )
 }
-fn wire__crate__api__get_default_channels_impl(
+fn wire__crate__api__clear_supplier_credentials_impl(
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
     data_len_: i32,
 ) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_default_channels",
+            debug_name: "clear_supplier_credentials",
             port: None,
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
         },
@@ -168,23 +159,21 @@ fn wire__crate__api__get_default_channels_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             let api_supplier = <String>::sse_decode(&mut deserializer);
             deserializer.end();
-            transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
-                (move || {
-                    let output_ok = crate::api::get_default_channels(api_supplier)?;
-                    Ok(output_ok)
-                })(),
-            )
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::clear_supplier_credentials(api_supplier)?;
+                Ok(output_ok)
+            })())
         },
     )
 }
-fn wire__crate__api__get_supported_languages_impl(
+fn wire__crate__api__create_cancel_token_impl(
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
     data_len_: i32,
 ) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_supported_languages",
+            debug_name: "create_cancel_token",
             port: None,
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
         },
@@ -198,27 +187,25 @@ fn wire__crate__api__get_supported_languages_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_supplier = <String>::sse_decode(&mut deserializer);
             deserializer.end();
-            transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
-                (move || {
-                    let output_ok = crate::api::get_supported_languages(api_supplier)?;
-                    Ok(output_ok)
-                })(),
-            )
+            transform_result_sse::<_, ()>((move || {
+                let output_ok = Result::<_, ()>::Ok(crate::api::create_cancel_token())?;
+                Ok(output_ok)
+            })())
         },
     )
 }
-fn wire__crate__api__get_supported_types_impl(
+fn wire__crate__api__dns_lookup_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
     data_len_: i32,
-) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "get_supported_types",
-            port: None,
-            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+            debug_name: "dns_lookup",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
         move || {
             let message = unsafe {
@@ -230,26 +217,29 @@ fn wire__crate__api__get_supported_types_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_host = <String>::sse_decode(&mut deserializer);
             deserializer.end();
-            transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
-                (move || {
-                    let output_ok = crate::api::get_supported_types(api_supplier)?;
-                    Ok(output_ok)
-                })(),
-            )
+            move |context| async move {
+                transform_result_sse::<_, crate::errors::SupplierError>(
+                    (move || async move {
+                        let output_ok = crate::api::dns_lookup(api_host).await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
         },
     )
 }
-fn wire__crate__api__init_app_impl(
+fn wire__crate__api__download_source_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
     data_len_: i32,
 ) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "init_app",
+            debug_name: "download_source",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -263,19 +253,36 @@ fn wire__crate__api__init_app_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_source = <crate::models::ContentMediaItemSource>::sse_decode(&mut deserializer);
+            let api_output = <String>::sse_decode(&mut deserializer);
+            let api_options = <crate::models::DownloadOptions>::sse_decode(&mut deserializer);
+            let api_sink = <StreamSink<
+                crate::models::DownloadEvent,
+                flutter_rust_bridge::for_generated::SseCodec,
+            >>::sse_decode(&mut deserializer);
+            let api_cancel_token =
+                <Option<crate::models::CancelToken>>::sse_decode(&mut deserializer);
             deserializer.end();
-            move |context| {
-                transform_result_sse::<_, ()>((move || {
-                    let output_ok = Result::<_, ()>::Ok({
-                        crate::api::init_app();
-                    })?;
-                    Ok(output_ok)
-                })())
+            move |context| async move {
+                transform_result_sse::<_, crate::errors::SupplierError>(
+                    (move || async move {
+                        let output_ok = crate::api::download_source(
+                            api_source,
+                            api_output,
+                            api_options,
+                            api_sink,
+                            api_cancel_token,
+                        )
+                        .await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
             }
         },
     )
 }
-fn wire__crate__api__load_channel_impl(
+fn wire__crate__api__expand_hls_sources_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -283,7 +290,7 @@ fn wire__crate__api__load_channel_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "load_channel",
+            debug_name: "expand_hls_sources",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -297,15 +304,21 @@ fn wire__crate__api__load_channel_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_supplier = <String>::sse_decode(&mut deserializer);
-            let api_channel = <String>::sse_decode(&mut deserializer);
-            let api_page = <u16>::sse_decode(&mut deserializer);
+            let api_sources =
+                <Vec<crate::models::ContentMediaItemSource>>::sse_decode(&mut deserializer);
+            let api_split = <bool>::sse_decode(&mut deserializer);
+            let api_cancel_token =
+                <Option<crate::models::CancelToken>>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| async move {
-                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
+                transform_result_sse::<_, crate::errors::SupplierError>(
                     (move || async move {
-                        let output_ok =
-                            crate::api::load_channel(api_supplier, api_channel, api_page).await?;
+                        let output_ok = crate::api::expand_hls_sources(
+                            api_sources,
+                            api_split,
+                            api_cancel_token,
+                        )
+                        .await?;
                         Ok(output_ok)
                     })()
                     .await,
@@ -314,17 +327,16 @@ fn wire__crate__api__load_channel_impl(
         },
     )
 }
-fn wire__crate__api__load_manga_pages_impl(
-    port_: flutter_rust_bridge::for_generated::MessagePort,
+fn wire__crate__api__export_cookies_impl(
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
     data_len_: i32,
-) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "load_manga_pages",
-            port: Some(port_),
-            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+            debug_name: "export_cookies",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
         },
         move || {
             let message = unsafe {
@@ -336,24 +348,46 @@ fn wire__crate__api__load_manga_pages_impl(
             };
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
-            let api_supplier = <String>::sse_decode(&mut deserializer);
-            let api_id = <String>::sse_decode(&mut deserializer);
-            let api_params = <Vec<String>>::sse_decode(&mut deserializer);
+            let api_domain = <Option<String>>::sse_decode(&mut deserializer);
             deserializer.end();
-            move |context| async move {
-                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
-                    (move || async move {
-                        let output_ok =
-                            crate::api::load_manga_pages(api_supplier, api_id, api_params).await?;
-                        Ok(output_ok)
-                    })()
-                    .await,
+            transform_result_sse::<_, ()>((move || {
+                let output_ok = Result::<_, ()>::Ok(crate::api::export_cookies(api_domain))?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__get_channels_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_channels",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
                 )
-            }
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::get_channels(api_supplier)?;
+                Ok(output_ok)
+            })())
         },
     )
 }
-fn wire__crate__api__load_media_item_sources_impl(
+fn wire__crate__api__get_content_details_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
@@ -361,7 +395,7 @@ fn wire__crate__api__load_media_item_sources_impl(
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "load_media_item_sources",
+            debug_name: "get_content_details",
             port: Some(port_),
             mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
         },
@@ -377,13 +411,14 @@ fn wire__crate__api__load_media_item_sources_impl(
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             let api_supplier = <String>::sse_decode(&mut deserializer);
             let api_id = <String>::sse_decode(&mut deserializer);
-            let api_params = <Vec<String>>::sse_decode(&mut deserializer);
+            let api_cancel_token =
+                <Option<crate::models::CancelToken>>::sse_decode(&mut deserializer);
             deserializer.end();
             move |context| async move {
-                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
+                transform_result_sse::<_, crate::errors::SupplierError>(
                     (move || async move {
                         let output_ok =
-                            crate::api::load_media_item_sources(api_supplier, api_id, api_params)
+                            crate::api::get_content_details(api_supplier, api_id, api_cancel_token)
                                 .await?;
                         Ok(output_ok)
                     })()
@@ -393,17 +428,16 @@ fn wire__crate__api__load_media_item_sources_impl(
         },
     )
 }
-fn wire__crate__api__load_media_items_impl(
-    port_: flutter_rust_bridge::for_generated::MessagePort,
+fn wire__crate__api__get_default_channels_impl(
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
     data_len_: i32,
-) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "load_media_items",
-            port: Some(port_),
-            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+            debug_name: "get_default_channels",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
         },
         move || {
             let message = unsafe {
@@ -416,33 +450,24 @@ fn wire__crate__api__load_media_items_impl(
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             let api_supplier = <String>::sse_decode(&mut deserializer);
-            let api_id = <String>::sse_decode(&mut deserializer);
-            let api_params = <Vec<String>>::sse_decode(&mut deserializer);
             deserializer.end();
-            move |context| async move {
-                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
-                    (move || async move {
-                        let output_ok =
-                            crate::api::load_media_items(api_supplier, api_id, api_params).await?;
-                        Ok(output_ok)
-                    })()
-                    .await,
-                )
-            }
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::get_default_channels(api_supplier)?;
+                Ok(output_ok)
+            })())
         },
     )
 }
-fn wire__crate__api__search_impl(
-    port_: flutter_rust_bridge::for_generated::MessagePort,
+fn wire__crate__api__get_supplier_info_impl(
     ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
     rust_vec_len_: i32,
     data_len_: i32,
-) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
-            debug_name: "search",
-            port: Some(port_),
-            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+            debug_name: "get_supplier_info",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
         },
         move || {
             let message = unsafe {
@@ -455,43 +480,1262 @@ fn wire__crate__api__search_impl(
             let mut deserializer =
                 flutter_rust_bridge::for_generated::SseDeserializer::new(message);
             let api_supplier = <String>::sse_decode(&mut deserializer);
-            let api_query = <String>::sse_decode(&mut deserializer);
-            let api_page = <u16>::sse_decode(&mut deserializer);
             deserializer.end();
-            move |context| async move {
-                transform_result_sse::<_, flutter_rust_bridge::for_generated::anyhow::Error>(
-                    (move || async move {
-                        let output_ok =
-                            crate::api::search(api_supplier, api_query, api_page).await?;
-                        Ok(output_ok)
-                    })()
-                    .await,
-                )
-            }
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::get_supplier_info(api_supplier)?;
+                Ok(output_ok)
+            })())
         },
     )
 }
-
-// Section: dart2rust
-
-impl SseDecode for flutter_rust_bridge::for_generated::anyhow::Error {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <String>::sse_decode(deserializer);
-        return flutter_rust_bridge::for_generated::anyhow::anyhow!("{}", inner);
-    }
+fn wire__crate__api__get_supplier_mirrors_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_supplier_mirrors",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::get_supplier_mirrors(api_supplier)?;
+                Ok(output_ok)
+            })())
+        },
+    )
 }
-
-impl SseDecode for std::collections::HashMap<String, String> {
-    // Codec=Sse (Serialization based), see doc to use other codecs
-    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut inner = <Vec<(String, String)>>::sse_decode(deserializer);
-        return inner.into_iter().collect();
-    }
+fn wire__crate__api__get_supplier_setting_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_supplier_setting",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_key = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::get_supplier_setting(api_supplier, api_key)?;
+                Ok(output_ok)
+            })())
+        },
+    )
 }
-
-impl SseDecode for String {
-    // Codec=Sse (Serialization based), see doc to use other codecs
+fn wire__crate__api__get_supported_languages_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_supported_languages",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::get_supported_languages(api_supplier)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__get_supported_types_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "get_supported_types",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::get_supported_types(api_supplier)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__import_cookies_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "import_cookies",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_cookies = <Vec<crate::models::HttpCookie>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::import_cookies(api_cookies)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__init_app_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::SseCodec, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "init_app",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            move |context| {
+                transform_result_sse::<_, ()>((move || {
+                    let output_ok = Result::<_, ()>::Ok({
+                        crate::api::init_app();
+                    })?;
+                    Ok(output_ok)
+                })())
+            }
+        },
+    )
+}
+fn wire__crate__api__load_channel_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "load_channel",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_channel = <String>::sse_decode(&mut deserializer);
+            let api_page = <u16>::sse_decode(&mut deserializer);
+            let api_cancel_token =
+                <Option<crate::models::CancelToken>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| async move {
+                transform_result_sse::<_, crate::errors::SupplierError>(
+                    (move || async move {
+                        let output_ok = crate::api::load_channel(
+                            api_supplier,
+                            api_channel,
+                            api_page,
+                            api_cancel_token,
+                        )
+                        .await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
+        },
+    )
+}
+fn wire__crate__api__load_manga_pages_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "load_manga_pages",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_id = <String>::sse_decode(&mut deserializer);
+            let api_params = <Vec<String>>::sse_decode(&mut deserializer);
+            let api_cancel_token =
+                <Option<crate::models::CancelToken>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| async move {
+                transform_result_sse::<_, crate::errors::SupplierError>(
+                    (move || async move {
+                        let output_ok = crate::api::load_manga_pages(
+                            api_supplier,
+                            api_id,
+                            api_params,
+                            api_cancel_token,
+                        )
+                        .await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
+        },
+    )
+}
+fn wire__crate__api__load_media_item_sources_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "load_media_item_sources",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_id = <String>::sse_decode(&mut deserializer);
+            let api_params = <Vec<String>>::sse_decode(&mut deserializer);
+            let api_cancel_token =
+                <Option<crate::models::CancelToken>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| async move {
+                transform_result_sse::<_, crate::errors::SupplierError>(
+                    (move || async move {
+                        let output_ok = crate::api::load_media_item_sources(
+                            api_supplier,
+                            api_id,
+                            api_params,
+                            api_cancel_token,
+                        )
+                        .await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
+        },
+    )
+}
+fn wire__crate__api__load_media_item_sources_stream_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "load_media_item_sources_stream",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_id = <String>::sse_decode(&mut deserializer);
+            let api_params = <Vec<String>>::sse_decode(&mut deserializer);
+            let api_sink = <StreamSink<
+                crate::models::SourcesEvent,
+                flutter_rust_bridge::for_generated::SseCodec,
+            >>::sse_decode(&mut deserializer);
+            let api_cancel_token =
+                <Option<crate::models::CancelToken>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| async move {
+                transform_result_sse::<_, crate::errors::SupplierError>(
+                    (move || async move {
+                        let output_ok = crate::api::load_media_item_sources_stream(
+                            api_supplier,
+                            api_id,
+                            api_params,
+                            api_sink,
+                            api_cancel_token,
+                        )
+                        .await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
+        },
+    )
+}
+fn wire__crate__api__load_media_items_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "load_media_items",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_id = <String>::sse_decode(&mut deserializer);
+            let api_params = <Vec<String>>::sse_decode(&mut deserializer);
+            let api_cancel_token =
+                <Option<crate::models::CancelToken>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| async move {
+                transform_result_sse::<_, crate::errors::SupplierError>(
+                    (move || async move {
+                        let output_ok = crate::api::load_media_items(
+                            api_supplier,
+                            api_id,
+                            api_params,
+                            api_cancel_token,
+                        )
+                        .await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
+        },
+    )
+}
+fn wire__crate__api__load_supplier_definitions_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "load_supplier_definitions",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_dir = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::load_supplier_definitions(api_dir)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__proxy_media_source_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "proxy_media_source",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_source = <crate::models::ContentMediaItemSource>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::proxy_media_source(api_source)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__register_supplier_definition_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "register_supplier_definition",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_definition = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::register_supplier_definition(api_definition)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__release_cancel_token_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "release_cancel_token",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_token = <crate::models::CancelToken>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, ()>((move || {
+                let output_ok = Result::<_, ()>::Ok({
+                    crate::api::release_cancel_token(api_token);
+                })?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__reset_request_policy_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "reset_request_policy",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_host = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, ()>((move || {
+                let output_ok = Result::<_, ()>::Ok({
+                    crate::api::reset_request_policy(api_host);
+                })?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__reset_supplier_settings_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "reset_supplier_settings",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::reset_supplier_settings(api_supplier)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__search_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "search",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_query = <String>::sse_decode(&mut deserializer);
+            let api_page = <u16>::sse_decode(&mut deserializer);
+            let api_cancel_token =
+                <Option<crate::models::CancelToken>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| async move {
+                transform_result_sse::<_, crate::errors::SupplierError>(
+                    (move || async move {
+                        let output_ok =
+                            crate::api::search(api_supplier, api_query, api_page, api_cancel_token)
+                                .await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
+        },
+    )
+}
+fn wire__crate__api__search_all_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "search_all",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_query = <String>::sse_decode(&mut deserializer);
+            let api_filters = <crate::models::SearchFilters>::sse_decode(&mut deserializer);
+            let api_sink = <StreamSink<
+                crate::models::SearchAllEvent,
+                flutter_rust_bridge::for_generated::SseCodec,
+            >>::sse_decode(&mut deserializer);
+            let api_cancel_token =
+                <Option<crate::models::CancelToken>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| async move {
+                transform_result_sse::<_, crate::errors::SupplierError>(
+                    (move || async move {
+                        let output_ok = crate::api::search_all(
+                            api_query,
+                            api_filters,
+                            api_sink,
+                            api_cancel_token,
+                        )
+                        .await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
+        },
+    )
+}
+fn wire__crate__api__set_cache_bypass_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_cache_bypass",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_bypass = <bool>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, ()>((move || {
+                let output_ok = Result::<_, ()>::Ok({
+                    crate::api::set_cache_bypass(api_bypass);
+                })?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__set_cache_dir_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_cache_dir",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_path = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::set_cache_dir(api_path)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__set_cookies_file_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_cookies_file",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_path = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::set_cookies_file(api_path)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__set_dns_config_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_dns_config",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_config = <crate::models::DnsConfig>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::set_dns_config(api_config)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__set_proxy_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_proxy",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_config = <Option<crate::models::ProxyConfig>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::set_proxy(api_config)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__set_request_policy_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_request_policy",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_host = <String>::sse_decode(&mut deserializer);
+            let api_policy =
+                <crate::utils::request_policy::RequestPolicy>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::set_request_policy(api_host, api_policy)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__set_supplier_credential_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_supplier_credential",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_credential = <String>::sse_decode(&mut deserializer);
+            let api_value = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok =
+                    crate::api::set_supplier_credential(api_supplier, api_credential, api_value)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__set_supplier_mirrors_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_supplier_mirrors",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_mirrors = <Vec<String>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::set_supplier_mirrors(api_supplier, api_mirrors)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__set_supplier_proxy_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_supplier_proxy",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_supplier_proxy = <crate::models::SupplierProxy>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::set_supplier_proxy(api_supplier, api_supplier_proxy)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__set_supplier_setting_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "set_supplier_setting",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_supplier = <String>::sse_decode(&mut deserializer);
+            let api_key = <String>::sse_decode(&mut deserializer);
+            let api_values = <Vec<String>>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok =
+                    crate::api::set_supplier_setting(api_supplier, api_key, api_values)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__start_hls_proxy_impl(
+    port_: flutter_rust_bridge::for_generated::MessagePort,
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_async::<flutter_rust_bridge::for_generated::SseCodec, _, _, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "start_hls_proxy",
+            port: Some(port_),
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Normal,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_port = <u16>::sse_decode(&mut deserializer);
+            let api_allow_lan = <bool>::sse_decode(&mut deserializer);
+            deserializer.end();
+            move |context| async move {
+                transform_result_sse::<_, crate::errors::SupplierError>(
+                    (move || async move {
+                        let output_ok =
+                            crate::api::start_hls_proxy(api_port, api_allow_lan).await?;
+                        Ok(output_ok)
+                    })()
+                    .await,
+                )
+            }
+        },
+    )
+}
+fn wire__crate__api__start_http_recording_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "start_http_recording",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_path = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::start_http_recording(api_path)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__start_http_replay_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "start_http_replay",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_path = <String>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::start_http_replay(api_path)?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__stop_hls_proxy_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "stop_hls_proxy",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            transform_result_sse::<_, ()>((move || {
+                let output_ok = Result::<_, ()>::Ok({
+                    crate::api::stop_hls_proxy();
+                })?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__stop_http_recording_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "stop_http_recording",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            deserializer.end();
+            transform_result_sse::<_, crate::errors::SupplierError>((move || {
+                let output_ok = crate::api::stop_http_recording()?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+fn wire__crate__api__trigger_cancel_token_impl(
+    ptr_: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len_: i32,
+    data_len_: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap_sync::<flutter_rust_bridge::for_generated::SseCodec, _>(
+        flutter_rust_bridge::for_generated::TaskInfo {
+            debug_name: "trigger_cancel_token",
+            port: None,
+            mode: flutter_rust_bridge::for_generated::FfiCallMode::Sync,
+        },
+        move || {
+            let message = unsafe {
+                flutter_rust_bridge::for_generated::Dart2RustMessageSse::from_wire(
+                    ptr_,
+                    rust_vec_len_,
+                    data_len_,
+                )
+            };
+            let mut deserializer =
+                flutter_rust_bridge::for_generated::SseDeserializer::new(message);
+            let api_token = <crate::models::CancelToken>::sse_decode(&mut deserializer);
+            deserializer.end();
+            transform_result_sse::<_, ()>((move || {
+                let output_ok = Result::<_, ()>::Ok({
+                    crate::api::trigger_cancel_token(api_token);
+                })?;
+                Ok(output_ok)
+            })())
+        },
+    )
+}
+
+// Section: dart2rust
+
+impl SseDecode for std::collections::HashMap<String, String> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <Vec<(String, String)>>::sse_decode(deserializer);
+        return inner.into_iter().collect();
+    }
+}
+
+impl SseDecode for std::collections::HashMap<String, Vec<String>> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <Vec<(String, Vec<String>)>>::sse_decode(deserializer);
+        return inner.into_iter().collect();
+    }
+}
+
+impl SseDecode
+    for StreamSink<crate::models::DownloadEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <String>::sse_decode(deserializer);
+        return StreamSink::deserialize(inner);
+    }
+}
+
+impl SseDecode
+    for StreamSink<crate::models::SearchAllEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <String>::sse_decode(deserializer);
+        return StreamSink::deserialize(inner);
+    }
+}
+
+impl SseDecode
+    for StreamSink<crate::models::SourcesEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <String>::sse_decode(deserializer);
+        return StreamSink::deserialize(inner);
+    }
+}
+
+impl SseDecode for String {
+    // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut inner = <Vec<u8>>::sse_decode(deserializer);
         return String::from_utf8(inner).unwrap();
@@ -505,6 +1749,14 @@ impl SseDecode for bool {
     }
 }
 
+impl SseDecode for crate::models::CancelToken {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_id = <u32>::sse_decode(deserializer);
+        return crate::models::CancelToken { id: var_id };
+    }
+}
+
 impl SseDecode for crate::models::ContentDetails {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -631,94 +1883,327 @@ impl SseDecode for crate::models::ContentType {
     }
 }
 
-impl SseDecode for i32 {
+impl SseDecode for crate::models::DnsConfig {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_resolvers = <Vec<crate::models::DnsResolver>>::sse_decode(deserializer);
+        let mut var_overrides =
+            <std::collections::HashMap<String, Vec<String>>>::sse_decode(deserializer);
+        let mut var_minTtlSecs = <Option<u32>>::sse_decode(deserializer);
+        let mut var_maxTtlSecs = <Option<u32>>::sse_decode(deserializer);
+        let mut var_negativeTtlSecs = <Option<u32>>::sse_decode(deserializer);
+        return crate::models::DnsConfig {
+            resolvers: var_resolvers,
+            overrides: var_overrides,
+            min_ttl_secs: var_minTtlSecs,
+            max_ttl_secs: var_maxTtlSecs,
+            negative_ttl_secs: var_negativeTtlSecs,
+        };
+    }
+}
+
+impl SseDecode for crate::models::DnsLookup {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_addresses = <Vec<String>>::sse_decode(deserializer);
+        let mut var_resolver = <String>::sse_decode(deserializer);
+        return crate::models::DnsLookup {
+            addresses: var_addresses,
+            resolver: var_resolver,
+        };
+    }
+}
+
+impl SseDecode for crate::models::DnsProtocol {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::models::DnsProtocol::Udp,
+            1 => crate::models::DnsProtocol::Https,
+            2 => crate::models::DnsProtocol::Tls,
+            _ => unreachable!("Invalid variant for DnsProtocol: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::models::DnsResolver {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                return crate::models::DnsResolver::System;
+            }
+            1 => {
+                let mut var_protocol = <crate::models::DnsProtocol>::sse_decode(deserializer);
+                return crate::models::DnsResolver::Cloudflare {
+                    protocol: var_protocol,
+                };
+            }
+            2 => {
+                let mut var_protocol = <crate::models::DnsProtocol>::sse_decode(deserializer);
+                return crate::models::DnsResolver::Google {
+                    protocol: var_protocol,
+                };
+            }
+            3 => {
+                let mut var_protocol = <crate::models::DnsProtocol>::sse_decode(deserializer);
+                return crate::models::DnsResolver::Quad9 {
+                    protocol: var_protocol,
+                };
+            }
+            4 => {
+                let mut var_url = <String>::sse_decode(deserializer);
+                let mut var_ips = <Vec<String>>::sse_decode(deserializer);
+                return crate::models::DnsResolver::CustomHttps {
+                    url: var_url,
+                    ips: var_ips,
+                };
+            }
+            5 => {
+                let mut var_serverName = <String>::sse_decode(deserializer);
+                let mut var_ips = <Vec<String>>::sse_decode(deserializer);
+                return crate::models::DnsResolver::CustomTls {
+                    server_name: var_serverName,
+                    ips: var_ips,
+                };
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::models::DownloadEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                let mut var_completedSegments = <u32>::sse_decode(deserializer);
+                let mut var_totalSegments = <u32>::sse_decode(deserializer);
+                let mut var_downloadedBytes = <u64>::sse_decode(deserializer);
+                let mut var_totalBytes = <Option<u64>>::sse_decode(deserializer);
+                return crate::models::DownloadEvent::Progress {
+                    completed_segments: var_completedSegments,
+                    total_segments: var_totalSegments,
+                    downloaded_bytes: var_downloadedBytes,
+                    total_bytes: var_totalBytes,
+                };
+            }
+            1 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                let mut var_size = <u64>::sse_decode(deserializer);
+                return crate::models::DownloadEvent::Completed {
+                    path: var_path,
+                    size: var_size,
+                };
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::models::DownloadOptions {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_maxHeight = <Option<u32>>::sse_decode(deserializer);
+        let mut var_concurrency = <u32>::sse_decode(deserializer);
+        return crate::models::DownloadOptions {
+            max_height: var_maxHeight,
+            concurrency: var_concurrency,
+        };
+    }
+}
+
+impl SseDecode for f64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_f64::<NativeEndian>().unwrap()
+    }
+}
+
+impl SseDecode for crate::models::HttpCookie {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_name = <String>::sse_decode(deserializer);
+        let mut var_value = <String>::sse_decode(deserializer);
+        let mut var_domain = <String>::sse_decode(deserializer);
+        let mut var_path = <Option<String>>::sse_decode(deserializer);
+        let mut var_expires = <Option<i64>>::sse_decode(deserializer);
+        let mut var_secure = <bool>::sse_decode(deserializer);
+        let mut var_httpOnly = <bool>::sse_decode(deserializer);
+        return crate::models::HttpCookie {
+            name: var_name,
+            value: var_value,
+            domain: var_domain,
+            path: var_path,
+            expires: var_expires,
+            secure: var_secure,
+            http_only: var_httpOnly,
+        };
+    }
+}
+
+impl SseDecode for i32 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_i32::<NativeEndian>().unwrap()
+    }
+}
+
+impl SseDecode for i64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        deserializer.cursor.read_i64::<NativeEndian>().unwrap()
+    }
+}
+
+impl SseDecode for Vec<String> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = Vec::with_capacity(len_ as usize);
+        for idx_ in 0..len_ {
+            ans_.push(<String>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::models::ContentInfo> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = Vec::with_capacity(len_ as usize);
+        for idx_ in 0..len_ {
+            ans_.push(<crate::models::ContentInfo>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::models::ContentMediaItem> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = Vec::with_capacity(len_ as usize);
+        for idx_ in 0..len_ {
+            ans_.push(<crate::models::ContentMediaItem>::sse_decode(deserializer));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::models::ContentMediaItemSource> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = Vec::with_capacity(len_ as usize);
+        for idx_ in 0..len_ {
+            ans_.push(<crate::models::ContentMediaItemSource>::sse_decode(
+                deserializer,
+            ));
+        }
+        return ans_;
+    }
+}
+
+impl SseDecode for Vec<crate::models::ContentType> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        deserializer.cursor.read_i32::<NativeEndian>().unwrap()
+        let mut len_ = <i32>::sse_decode(deserializer);
+        let mut ans_ = Vec::with_capacity(len_ as usize);
+        for idx_ in 0..len_ {
+            ans_.push(<crate::models::ContentType>::sse_decode(deserializer));
+        }
+        return ans_;
     }
 }
 
-impl SseDecode for Vec<String> {
+impl SseDecode for Vec<crate::models::DnsResolver> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut len_ = <i32>::sse_decode(deserializer);
         let mut ans_ = Vec::with_capacity(len_ as usize);
         for idx_ in 0..len_ {
-            ans_.push(<String>::sse_decode(deserializer));
+            ans_.push(<crate::models::DnsResolver>::sse_decode(deserializer));
         }
         return ans_;
     }
 }
 
-impl SseDecode for Vec<crate::models::ContentInfo> {
+impl SseDecode for Vec<crate::models::HttpCookie> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut len_ = <i32>::sse_decode(deserializer);
         let mut ans_ = Vec::with_capacity(len_ as usize);
         for idx_ in 0..len_ {
-            ans_.push(<crate::models::ContentInfo>::sse_decode(deserializer));
+            ans_.push(<crate::models::HttpCookie>::sse_decode(deserializer));
         }
         return ans_;
     }
 }
 
-impl SseDecode for Vec<crate::models::ContentMediaItem> {
+impl SseDecode for Vec<u8> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut len_ = <i32>::sse_decode(deserializer);
         let mut ans_ = Vec::with_capacity(len_ as usize);
         for idx_ in 0..len_ {
-            ans_.push(<crate::models::ContentMediaItem>::sse_decode(deserializer));
+            ans_.push(<u8>::sse_decode(deserializer));
         }
         return ans_;
     }
 }
 
-impl SseDecode for Vec<crate::models::ContentMediaItemSource> {
+impl SseDecode for Vec<(String, Vec<String>)> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut len_ = <i32>::sse_decode(deserializer);
         let mut ans_ = Vec::with_capacity(len_ as usize);
         for idx_ in 0..len_ {
-            ans_.push(<crate::models::ContentMediaItemSource>::sse_decode(
-                deserializer,
-            ));
+            ans_.push(<(String, Vec<String>)>::sse_decode(deserializer));
         }
         return ans_;
     }
 }
 
-impl SseDecode for Vec<crate::models::ContentType> {
+impl SseDecode for Vec<(String, String)> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut len_ = <i32>::sse_decode(deserializer);
         let mut ans_ = Vec::with_capacity(len_ as usize);
         for idx_ in 0..len_ {
-            ans_.push(<crate::models::ContentType>::sse_decode(deserializer));
+            ans_.push(<(String, String)>::sse_decode(deserializer));
         }
         return ans_;
     }
 }
 
-impl SseDecode for Vec<u8> {
+impl SseDecode for Vec<crate::models::SearchAllItem> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut len_ = <i32>::sse_decode(deserializer);
         let mut ans_ = Vec::with_capacity(len_ as usize);
         for idx_ in 0..len_ {
-            ans_.push(<u8>::sse_decode(deserializer));
+            ans_.push(<crate::models::SearchAllItem>::sse_decode(deserializer));
         }
         return ans_;
     }
 }
 
-impl SseDecode for Vec<(String, String)> {
+impl SseDecode for Vec<crate::models::SupplierSetting> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut len_ = <i32>::sse_decode(deserializer);
         let mut ans_ = Vec::with_capacity(len_ as usize);
         for idx_ in 0..len_ {
-            ans_.push(<(String, String)>::sse_decode(deserializer));
+            ans_.push(<crate::models::SupplierSetting>::sse_decode(deserializer));
         }
         return ans_;
     }
@@ -760,6 +2245,17 @@ impl SseDecode for Option<String> {
     }
 }
 
+impl SseDecode for Option<crate::models::CancelToken> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<crate::models::CancelToken>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
 impl SseDecode for Option<crate::models::ContentDetails> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -771,6 +2267,50 @@ impl SseDecode for Option<crate::models::ContentDetails> {
     }
 }
 
+impl SseDecode for Option<i64> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<i64>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<crate::models::ProxyConfig> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<crate::models::ProxyConfig>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<u32> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<u32>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for Option<u64> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<u64>::sse_decode(deserializer));
+        } else {
+            return None;
+        }
+    }
+}
+
 impl SseDecode for Option<Vec<String>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
@@ -795,32 +2335,374 @@ impl SseDecode for Option<Vec<crate::models::ContentMediaItem>> {
     }
 }
 
-impl SseDecode for Option<Vec<crate::models::ContentMediaItemSource>> {
+impl SseDecode for Option<Vec<crate::models::ContentMediaItemSource>> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        if (<bool>::sse_decode(deserializer)) {
+            return Some(<Vec<crate::models::ContentMediaItemSource>>::sse_decode(
+                deserializer,
+            ));
+        } else {
+            return None;
+        }
+    }
+}
+
+impl SseDecode for crate::models::ProxyConfig {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_kind = <crate::models::ProxyKind>::sse_decode(deserializer);
+        let mut var_host = <String>::sse_decode(deserializer);
+        let mut var_port = <u16>::sse_decode(deserializer);
+        let mut var_username = <Option<String>>::sse_decode(deserializer);
+        let mut var_password = <Option<String>>::sse_decode(deserializer);
+        return crate::models::ProxyConfig {
+            kind: var_kind,
+            host: var_host,
+            port: var_port,
+            username: var_username,
+            password: var_password,
+        };
+    }
+}
+
+impl SseDecode for crate::models::ProxyKind {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::models::ProxyKind::Http,
+            1 => crate::models::ProxyKind::Https,
+            2 => crate::models::ProxyKind::Socks5,
+            _ => unreachable!("Invalid variant for ProxyKind: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for (String, Vec<String>) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_field0 = <String>::sse_decode(deserializer);
+        let mut var_field1 = <Vec<String>>::sse_decode(deserializer);
+        return (var_field0, var_field1);
+    }
+}
+
+impl SseDecode for (String, String) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_field0 = <String>::sse_decode(deserializer);
+        let mut var_field1 = <String>::sse_decode(deserializer);
+        return (var_field0, var_field1);
+    }
+}
+
+impl SseDecode for crate::utils::request_policy::RequestPolicy {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_requestsPerSecond = <f64>::sse_decode(deserializer);
+        let mut var_burst = <u32>::sse_decode(deserializer);
+        let mut var_maxRetries = <u32>::sse_decode(deserializer);
+        let mut var_baseDelayMs = <u64>::sse_decode(deserializer);
+        let mut var_maxDelayMs = <u64>::sse_decode(deserializer);
+        return crate::utils::request_policy::RequestPolicy {
+            requests_per_second: var_requestsPerSecond,
+            burst: var_burst,
+            max_retries: var_maxRetries,
+            base_delay_ms: var_baseDelayMs,
+            max_delay_ms: var_maxDelayMs,
+        };
+    }
+}
+
+impl SseDecode for crate::models::SearchAllEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                let mut var_supplier = <String>::sse_decode(deserializer);
+                let mut var_items = <Vec<crate::models::SearchAllItem>>::sse_decode(deserializer);
+                return crate::models::SearchAllEvent::Results {
+                    supplier: var_supplier,
+                    items: var_items,
+                };
+            }
+            1 => {
+                let mut var_item = <crate::models::SearchAllItem>::sse_decode(deserializer);
+                let mut var_originalSupplier = <String>::sse_decode(deserializer);
+                let mut var_originalId = <String>::sse_decode(deserializer);
+                return crate::models::SearchAllEvent::Duplicate {
+                    item: var_item,
+                    original_supplier: var_originalSupplier,
+                    original_id: var_originalId,
+                };
+            }
+            2 => {
+                let mut var_supplier = <String>::sse_decode(deserializer);
+                let mut var_error = <String>::sse_decode(deserializer);
+                return crate::models::SearchAllEvent::Failed {
+                    supplier: var_supplier,
+                    error: var_error,
+                };
+            }
+            3 => {
+                return crate::models::SearchAllEvent::Completed;
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::models::SearchAllItem {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_supplier = <String>::sse_decode(deserializer);
+        let mut var_info = <crate::models::ContentInfo>::sse_decode(deserializer);
+        return crate::models::SearchAllItem {
+            supplier: var_supplier,
+            info: var_info,
+        };
+    }
+}
+
+impl SseDecode for crate::models::SearchFilters {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_suppliers = <Vec<String>>::sse_decode(deserializer);
+        let mut var_contentTypes = <Vec<crate::models::ContentType>>::sse_decode(deserializer);
+        let mut var_languages = <Vec<String>>::sse_decode(deserializer);
+        return crate::models::SearchFilters {
+            suppliers: var_suppliers,
+            content_types: var_contentTypes,
+            languages: var_languages,
+        };
+    }
+}
+
+impl SseDecode for crate::models::SettingType {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut inner = <i32>::sse_decode(deserializer);
+        return match inner {
+            0 => crate::models::SettingType::Bool,
+            1 => crate::models::SettingType::Choice,
+            2 => crate::models::SettingType::MultiChoice,
+            3 => crate::models::SettingType::Text,
+            _ => unreachable!("Invalid variant for SettingType: {}", inner),
+        };
+    }
+}
+
+impl SseDecode for crate::models::SourcesEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                let mut var_origin = <String>::sse_decode(deserializer);
+                let mut var_sources =
+                    <Vec<crate::models::ContentMediaItemSource>>::sse_decode(deserializer);
+                return crate::models::SourcesEvent::Sources {
+                    origin: var_origin,
+                    sources: var_sources,
+                };
+            }
+            1 => {
+                let mut var_failed = <Vec<String>>::sse_decode(deserializer);
+                return crate::models::SourcesEvent::Completed { failed: var_failed };
+            }
+            2 => {
+                let mut var_error = <crate::errors::SupplierError>::sse_decode(deserializer);
+                return crate::models::SourcesEvent::Error { error: var_error };
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::errors::SupplierError {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                let mut var_message = <String>::sse_decode(deserializer);
+                return crate::errors::SupplierError::Network {
+                    message: var_message,
+                };
+            }
+            1 => {
+                return crate::errors::SupplierError::Timeout;
+            }
+            2 => {
+                let mut var_status = <u16>::sse_decode(deserializer);
+                let mut var_url = <String>::sse_decode(deserializer);
+                return crate::errors::SupplierError::HttpStatus {
+                    status: var_status,
+                    url: var_url,
+                };
+            }
+            3 => {
+                let mut var_message = <String>::sse_decode(deserializer);
+                return crate::errors::SupplierError::Parse {
+                    message: var_message,
+                };
+            }
+            4 => {
+                let mut var_message = <String>::sse_decode(deserializer);
+                return crate::errors::SupplierError::NotFound {
+                    message: var_message,
+                };
+            }
+            5 => {
+                let mut var_path = <String>::sse_decode(deserializer);
+                let mut var_selector = <String>::sse_decode(deserializer);
+                return crate::errors::SupplierError::LayoutChanged {
+                    path: var_path,
+                    selector: var_selector,
+                };
+            }
+            6 => {
+                let mut var_protection = <String>::sse_decode(deserializer);
+                let mut var_url = <String>::sse_decode(deserializer);
+                return crate::errors::SupplierError::Blocked {
+                    protection: var_protection,
+                    url: var_url,
+                };
+            }
+            7 => {
+                let mut var_operation = <String>::sse_decode(deserializer);
+                return crate::errors::SupplierError::Unsupported {
+                    operation: var_operation,
+                };
+            }
+            8 => {
+                let mut var_message = <String>::sse_decode(deserializer);
+                return crate::errors::SupplierError::InvalidParams {
+                    message: var_message,
+                };
+            }
+            9 => {
+                let mut var_supplier = <String>::sse_decode(deserializer);
+                let mut var_credential = <String>::sse_decode(deserializer);
+                return crate::errors::SupplierError::NotConfigured {
+                    supplier: var_supplier,
+                    credential: var_credential,
+                };
+            }
+            10 => {
+                return crate::errors::SupplierError::Cancelled;
+            }
+            11 => {
+                let mut var_message = <String>::sse_decode(deserializer);
+                return crate::errors::SupplierError::Storage {
+                    message: var_message,
+                };
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::models::SupplierInfo {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_name = <String>::sse_decode(deserializer);
+        let mut var_displayName = <String>::sse_decode(deserializer);
+        let mut var_homeUrl = <String>::sse_decode(deserializer);
+        let mut var_iconUrl = <String>::sse_decode(deserializer);
+        let mut var_channels = <Vec<String>>::sse_decode(deserializer);
+        let mut var_defaultChannels = <Vec<String>>::sse_decode(deserializer);
+        let mut var_supportedTypes = <Vec<crate::models::ContentType>>::sse_decode(deserializer);
+        let mut var_supportedLanguages = <Vec<String>>::sse_decode(deserializer);
+        let mut var_searchPaging = <bool>::sse_decode(deserializer);
+        let mut var_channelPaging = <bool>::sse_decode(deserializer);
+        let mut var_mangaPages = <bool>::sse_decode(deserializer);
+        let mut var_hlsProxy = <bool>::sse_decode(deserializer);
+        let mut var_credentials = <Vec<String>>::sse_decode(deserializer);
+        let mut var_settings = <Vec<crate::models::SupplierSetting>>::sse_decode(deserializer);
+        return crate::models::SupplierInfo {
+            name: var_name,
+            display_name: var_displayName,
+            home_url: var_homeUrl,
+            icon_url: var_iconUrl,
+            channels: var_channels,
+            default_channels: var_defaultChannels,
+            supported_types: var_supportedTypes,
+            supported_languages: var_supportedLanguages,
+            search_paging: var_searchPaging,
+            channel_paging: var_channelPaging,
+            manga_pages: var_mangaPages,
+            hls_proxy: var_hlsProxy,
+            credentials: var_credentials,
+            settings: var_settings,
+        };
+    }
+}
+
+impl SseDecode for crate::models::SupplierProxy {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut tag_ = <i32>::sse_decode(deserializer);
+        match tag_ {
+            0 => {
+                return crate::models::SupplierProxy::Default;
+            }
+            1 => {
+                return crate::models::SupplierProxy::Direct;
+            }
+            2 => {
+                let mut var_config = <crate::models::ProxyConfig>::sse_decode(deserializer);
+                return crate::models::SupplierProxy::Proxy { config: var_config };
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseDecode for crate::models::SupplierSetting {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
+        let mut var_key = <String>::sse_decode(deserializer);
+        let mut var_settingType = <crate::models::SettingType>::sse_decode(deserializer);
+        let mut var_allowedValues = <Vec<String>>::sse_decode(deserializer);
+        let mut var_defaultValues = <Vec<String>>::sse_decode(deserializer);
+        return crate::models::SupplierSetting {
+            key: var_key,
+            setting_type: var_settingType,
+            allowed_values: var_allowedValues,
+            default_values: var_defaultValues,
+        };
+    }
+}
+
+impl SseDecode for u16 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        if (<bool>::sse_decode(deserializer)) {
-            return Some(<Vec<crate::models::ContentMediaItemSource>>::sse_decode(
-                deserializer,
-            ));
-        } else {
-            return None;
-        }
+        deserializer.cursor.read_u16::<NativeEndian>().unwrap()
     }
 }
 
-impl SseDecode for (String, String) {
+impl SseDecode for u32 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        let mut var_field0 = <String>::sse_decode(deserializer);
-        let mut var_field1 = <String>::sse_decode(deserializer);
-        return (var_field0, var_field1);
+        deserializer.cursor.read_u32::<NativeEndian>().unwrap()
     }
 }
 
-impl SseDecode for u16 {
+impl SseDecode for u64 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
-        deserializer.cursor.read_u16::<NativeEndian>().unwrap()
+        deserializer.cursor.read_u64::<NativeEndian>().unwrap()
     }
 }
 
@@ -845,208 +2727,585 @@ fn pde_ffi_dispatcher_primary_impl(
 ) {
     // Codec=Pde (Serialization + dispatch), see doc to use other codecs
     match func_id {
-        3 => wire__crate__api__get_content_details_impl(port, ptr, rust_vec_len, data_len),
-        7 => wire__crate__api__init_app_impl(port, ptr, rust_vec_len, data_len),
-        8 => wire__crate__api__load_channel_impl(port, ptr, rust_vec_len, data_len),
-        9 => wire__crate__api__load_manga_pages_impl(port, ptr, rust_vec_len, data_len),
-        10 => wire__crate__api__load_media_item_sources_impl(port, ptr, rust_vec_len, data_len),
-        11 => wire__crate__api__load_media_items_impl(port, ptr, rust_vec_len, data_len),
-        12 => wire__crate__api__search_impl(port, ptr, rust_vec_len, data_len),
+        6 => wire__crate__api__dns_lookup_impl(port, ptr, rust_vec_len, data_len),
+        7 => wire__crate__api__download_source_impl(port, ptr, rust_vec_len, data_len),
+        8 => wire__crate__api__expand_hls_sources_impl(port, ptr, rust_vec_len, data_len),
+        11 => wire__crate__api__get_content_details_impl(port, ptr, rust_vec_len, data_len),
+        19 => wire__crate__api__init_app_impl(port, ptr, rust_vec_len, data_len),
+        20 => wire__crate__api__load_channel_impl(port, ptr, rust_vec_len, data_len),
+        21 => wire__crate__api__load_manga_pages_impl(port, ptr, rust_vec_len, data_len),
+        22 => wire__crate__api__load_media_item_sources_impl(port, ptr, rust_vec_len, data_len),
+        23 => {
+            wire__crate__api__load_media_item_sources_stream_impl(port, ptr, rust_vec_len, data_len)
+        }
+        24 => wire__crate__api__load_media_items_impl(port, ptr, rust_vec_len, data_len),
+        31 => wire__crate__api__search_impl(port, ptr, rust_vec_len, data_len),
+        32 => wire__crate__api__search_all_impl(port, ptr, rust_vec_len, data_len),
+        43 => wire__crate__api__start_hls_proxy_impl(port, ptr, rust_vec_len, data_len),
+        _ => unreachable!(),
+    }
+}
+
+fn pde_ffi_dispatcher_sync_impl(
+    func_id: i32,
+    ptr: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
+    rust_vec_len: i32,
+    data_len: i32,
+) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
+    // Codec=Pde (Serialization + dispatch), see doc to use other codecs
+    match func_id {
+        1 => wire__crate__api__avalaible_suppliers_impl(ptr, rust_vec_len, data_len),
+        2 => wire__crate__api__clear_cache_impl(ptr, rust_vec_len, data_len),
+        3 => wire__crate__api__clear_cookies_impl(ptr, rust_vec_len, data_len),
+        4 => wire__crate__api__clear_supplier_credentials_impl(ptr, rust_vec_len, data_len),
+        5 => wire__crate__api__create_cancel_token_impl(ptr, rust_vec_len, data_len),
+        9 => wire__crate__api__export_cookies_impl(ptr, rust_vec_len, data_len),
+        10 => wire__crate__api__get_channels_impl(ptr, rust_vec_len, data_len),
+        12 => wire__crate__api__get_default_channels_impl(ptr, rust_vec_len, data_len),
+        13 => wire__crate__api__get_supplier_info_impl(ptr, rust_vec_len, data_len),
+        14 => wire__crate__api__get_supplier_mirrors_impl(ptr, rust_vec_len, data_len),
+        15 => wire__crate__api__get_supplier_setting_impl(ptr, rust_vec_len, data_len),
+        16 => wire__crate__api__get_supported_languages_impl(ptr, rust_vec_len, data_len),
+        17 => wire__crate__api__get_supported_types_impl(ptr, rust_vec_len, data_len),
+        18 => wire__crate__api__import_cookies_impl(ptr, rust_vec_len, data_len),
+        25 => wire__crate__api__load_supplier_definitions_impl(ptr, rust_vec_len, data_len),
+        26 => wire__crate__api__proxy_media_source_impl(ptr, rust_vec_len, data_len),
+        27 => wire__crate__api__register_supplier_definition_impl(ptr, rust_vec_len, data_len),
+        28 => wire__crate__api__release_cancel_token_impl(ptr, rust_vec_len, data_len),
+        29 => wire__crate__api__reset_request_policy_impl(ptr, rust_vec_len, data_len),
+        30 => wire__crate__api__reset_supplier_settings_impl(ptr, rust_vec_len, data_len),
+        33 => wire__crate__api__set_cache_bypass_impl(ptr, rust_vec_len, data_len),
+        34 => wire__crate__api__set_cache_dir_impl(ptr, rust_vec_len, data_len),
+        35 => wire__crate__api__set_cookies_file_impl(ptr, rust_vec_len, data_len),
+        36 => wire__crate__api__set_dns_config_impl(ptr, rust_vec_len, data_len),
+        37 => wire__crate__api__set_proxy_impl(ptr, rust_vec_len, data_len),
+        38 => wire__crate__api__set_request_policy_impl(ptr, rust_vec_len, data_len),
+        39 => wire__crate__api__set_supplier_credential_impl(ptr, rust_vec_len, data_len),
+        40 => wire__crate__api__set_supplier_mirrors_impl(ptr, rust_vec_len, data_len),
+        41 => wire__crate__api__set_supplier_proxy_impl(ptr, rust_vec_len, data_len),
+        42 => wire__crate__api__set_supplier_setting_impl(ptr, rust_vec_len, data_len),
+        44 => wire__crate__api__start_http_recording_impl(ptr, rust_vec_len, data_len),
+        45 => wire__crate__api__start_http_replay_impl(ptr, rust_vec_len, data_len),
+        46 => wire__crate__api__stop_hls_proxy_impl(ptr, rust_vec_len, data_len),
+        47 => wire__crate__api__stop_http_recording_impl(ptr, rust_vec_len, data_len),
+        48 => wire__crate__api__trigger_cancel_token_impl(ptr, rust_vec_len, data_len),
         _ => unreachable!(),
     }
 }
-
-fn pde_ffi_dispatcher_sync_impl(
-    func_id: i32,
-    ptr: flutter_rust_bridge::for_generated::PlatformGeneralizedUint8ListPtr,
-    rust_vec_len: i32,
-    data_len: i32,
-) -> flutter_rust_bridge::for_generated::WireSyncRust2DartSse {
-    // Codec=Pde (Serialization + dispatch), see doc to use other codecs
-    match func_id {
-        1 => wire__crate__api__avalaible_suppliers_impl(ptr, rust_vec_len, data_len),
-        2 => wire__crate__api__get_channels_impl(ptr, rust_vec_len, data_len),
-        4 => wire__crate__api__get_default_channels_impl(ptr, rust_vec_len, data_len),
-        5 => wire__crate__api__get_supported_languages_impl(ptr, rust_vec_len, data_len),
-        6 => wire__crate__api__get_supported_types_impl(ptr, rust_vec_len, data_len),
-        _ => unreachable!(),
+
+// Section: rust2dart
+
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::CancelToken {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [self.id.into_into_dart().into_dart()].into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::CancelToken {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::CancelToken> for crate::models::CancelToken {
+    fn into_into_dart(self) -> crate::models::CancelToken {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::ContentDetails {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.title.into_into_dart().into_dart(),
+            self.original_title.into_into_dart().into_dart(),
+            self.image.into_into_dart().into_dart(),
+            self.description.into_into_dart().into_dart(),
+            self.media_type.into_into_dart().into_dart(),
+            self.additional_info.into_into_dart().into_dart(),
+            self.similar.into_into_dart().into_dart(),
+            self.media_items.into_into_dart().into_dart(),
+            self.params.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::ContentDetails {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::ContentDetails>
+    for crate::models::ContentDetails
+{
+    fn into_into_dart(self) -> crate::models::ContentDetails {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::ContentInfo {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.id.into_into_dart().into_dart(),
+            self.title.into_into_dart().into_dart(),
+            self.secondary_title.into_into_dart().into_dart(),
+            self.image.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::ContentInfo {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::ContentInfo> for crate::models::ContentInfo {
+    fn into_into_dart(self) -> crate::models::ContentInfo {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::ContentMediaItem {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.title.into_into_dart().into_dart(),
+            self.section.into_into_dart().into_dart(),
+            self.image.into_into_dart().into_dart(),
+            self.sources.into_into_dart().into_dart(),
+            self.params.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::models::ContentMediaItem
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::ContentMediaItem>
+    for crate::models::ContentMediaItem
+{
+    fn into_into_dart(self) -> crate::models::ContentMediaItem {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::ContentMediaItemSource {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            crate::models::ContentMediaItemSource::Video {
+                link,
+                description,
+                headers,
+                hls_proxy,
+            } => [
+                0.into_dart(),
+                link.into_into_dart().into_dart(),
+                description.into_into_dart().into_dart(),
+                headers.into_into_dart().into_dart(),
+                hls_proxy.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::models::ContentMediaItemSource::Subtitle {
+                link,
+                description,
+                headers,
+            } => [
+                1.into_dart(),
+                link.into_into_dart().into_dart(),
+                description.into_into_dart().into_dart(),
+                headers.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::models::ContentMediaItemSource::Manga {
+                description,
+                headers,
+                pages,
+                params,
+            } => [
+                2.into_dart(),
+                description.into_into_dart().into_dart(),
+                headers.into_into_dart().into_dart(),
+                pages.into_into_dart().into_dart(),
+                params.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::models::ContentMediaItemSource
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::ContentMediaItemSource>
+    for crate::models::ContentMediaItemSource
+{
+    fn into_into_dart(self) -> crate::models::ContentMediaItemSource {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::ContentType {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Movie => 0.into_dart(),
+            Self::Anime => 1.into_dart(),
+            Self::Cartoon => 2.into_dart(),
+            Self::Series => 3.into_dart(),
+            Self::Manga => 4.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::ContentType {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::ContentType> for crate::models::ContentType {
+    fn into_into_dart(self) -> crate::models::ContentType {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::DnsLookup {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.addresses.into_into_dart().into_dart(),
+            self.resolver.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::DnsLookup {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::DnsLookup> for crate::models::DnsLookup {
+    fn into_into_dart(self) -> crate::models::DnsLookup {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::DownloadEvent {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            crate::models::DownloadEvent::Progress {
+                completed_segments,
+                total_segments,
+                downloaded_bytes,
+                total_bytes,
+            } => [
+                0.into_dart(),
+                completed_segments.into_into_dart().into_dart(),
+                total_segments.into_into_dart().into_dart(),
+                downloaded_bytes.into_into_dart().into_dart(),
+                total_bytes.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::models::DownloadEvent::Completed { path, size } => [
+                1.into_dart(),
+                path.into_into_dart().into_dart(),
+                size.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::DownloadEvent {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::DownloadEvent>
+    for crate::models::DownloadEvent
+{
+    fn into_into_dart(self) -> crate::models::DownloadEvent {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::HttpCookie {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        [
+            self.name.into_into_dart().into_dart(),
+            self.value.into_into_dart().into_dart(),
+            self.domain.into_into_dart().into_dart(),
+            self.path.into_into_dart().into_dart(),
+            self.expires.into_into_dart().into_dart(),
+            self.secure.into_into_dart().into_dart(),
+            self.http_only.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::HttpCookie {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::HttpCookie> for crate::models::HttpCookie {
+    fn into_into_dart(self) -> crate::models::HttpCookie {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::MediaType {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            Self::Video => 0.into_dart(),
+            Self::Manga => 1.into_dart(),
+            _ => unreachable!(),
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::MediaType {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::MediaType> for crate::models::MediaType {
+    fn into_into_dart(self) -> crate::models::MediaType {
+        self
+    }
+}
+// Codec=Dco (DartCObject based), see doc to use other codecs
+impl flutter_rust_bridge::IntoDart for crate::models::SearchAllEvent {
+    fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
+        match self {
+            crate::models::SearchAllEvent::Results { supplier, items } => [
+                0.into_dart(),
+                supplier.into_into_dart().into_dart(),
+                items.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::models::SearchAllEvent::Duplicate {
+                item,
+                original_supplier,
+                original_id,
+            } => [
+                1.into_dart(),
+                item.into_into_dart().into_dart(),
+                original_supplier.into_into_dart().into_dart(),
+                original_id.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::models::SearchAllEvent::Failed { supplier, error } => [
+                2.into_dart(),
+                supplier.into_into_dart().into_dart(),
+                error.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::models::SearchAllEvent::Completed => [3.into_dart()].into_dart(),
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::SearchAllEvent {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::SearchAllEvent>
+    for crate::models::SearchAllEvent
+{
+    fn into_into_dart(self) -> crate::models::SearchAllEvent {
+        self
     }
 }
-
-// Section: rust2dart
-
 // Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for crate::models::ContentDetails {
+impl flutter_rust_bridge::IntoDart for crate::models::SearchAllItem {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         [
-            self.title.into_into_dart().into_dart(),
-            self.original_title.into_into_dart().into_dart(),
-            self.image.into_into_dart().into_dart(),
-            self.description.into_into_dart().into_dart(),
-            self.media_type.into_into_dart().into_dart(),
-            self.additional_info.into_into_dart().into_dart(),
-            self.similar.into_into_dart().into_dart(),
-            self.media_items.into_into_dart().into_dart(),
-            self.params.into_into_dart().into_dart(),
+            self.supplier.into_into_dart().into_dart(),
+            self.info.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
 }
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::ContentDetails {}
-impl flutter_rust_bridge::IntoIntoDart<crate::models::ContentDetails>
-    for crate::models::ContentDetails
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::SearchAllItem {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::SearchAllItem>
+    for crate::models::SearchAllItem
 {
-    fn into_into_dart(self) -> crate::models::ContentDetails {
+    fn into_into_dart(self) -> crate::models::SearchAllItem {
         self
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for crate::models::ContentInfo {
+impl flutter_rust_bridge::IntoDart for crate::models::SettingType {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
-        [
-            self.id.into_into_dart().into_dart(),
-            self.title.into_into_dart().into_dart(),
-            self.secondary_title.into_into_dart().into_dart(),
-            self.image.into_into_dart().into_dart(),
-        ]
-        .into_dart()
+        match self {
+            Self::Bool => 0.into_dart(),
+            Self::Choice => 1.into_dart(),
+            Self::MultiChoice => 2.into_dart(),
+            Self::Text => 3.into_dart(),
+            _ => unreachable!(),
+        }
     }
 }
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::ContentInfo {}
-impl flutter_rust_bridge::IntoIntoDart<crate::models::ContentInfo> for crate::models::ContentInfo {
-    fn into_into_dart(self) -> crate::models::ContentInfo {
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::SettingType {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::SettingType> for crate::models::SettingType {
+    fn into_into_dart(self) -> crate::models::SettingType {
         self
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for crate::models::ContentMediaItem {
+impl flutter_rust_bridge::IntoDart for crate::models::SourcesEvent {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
-        [
-            self.title.into_into_dart().into_dart(),
-            self.section.into_into_dart().into_dart(),
-            self.image.into_into_dart().into_dart(),
-            self.sources.into_into_dart().into_dart(),
-            self.params.into_into_dart().into_dart(),
-        ]
-        .into_dart()
+        match self {
+            crate::models::SourcesEvent::Sources { origin, sources } => [
+                0.into_dart(),
+                origin.into_into_dart().into_dart(),
+                sources.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::models::SourcesEvent::Completed { failed } => {
+                [1.into_dart(), failed.into_into_dart().into_dart()].into_dart()
+            }
+            crate::models::SourcesEvent::Error { error } => {
+                [2.into_dart(), error.into_into_dart().into_dart()].into_dart()
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
     }
 }
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
-    for crate::models::ContentMediaItem
-{
-}
-impl flutter_rust_bridge::IntoIntoDart<crate::models::ContentMediaItem>
-    for crate::models::ContentMediaItem
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::SourcesEvent {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::SourcesEvent>
+    for crate::models::SourcesEvent
 {
-    fn into_into_dart(self) -> crate::models::ContentMediaItem {
+    fn into_into_dart(self) -> crate::models::SourcesEvent {
         self
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for crate::models::ContentMediaItemSource {
+impl flutter_rust_bridge::IntoDart for crate::errors::SupplierError {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
         match self {
-            crate::models::ContentMediaItemSource::Video {
-                link,
-                description,
-                headers,
-                hls_proxy,
-            } => [
-                0.into_dart(),
-                link.into_into_dart().into_dart(),
-                description.into_into_dart().into_dart(),
-                headers.into_into_dart().into_dart(),
-                hls_proxy.into_into_dart().into_dart(),
+            crate::errors::SupplierError::Network { message } => {
+                [0.into_dart(), message.into_into_dart().into_dart()].into_dart()
+            }
+            crate::errors::SupplierError::Timeout => [1.into_dart()].into_dart(),
+            crate::errors::SupplierError::HttpStatus { status, url } => [
+                2.into_dart(),
+                status.into_into_dart().into_dart(),
+                url.into_into_dart().into_dart(),
             ]
             .into_dart(),
-            crate::models::ContentMediaItemSource::Subtitle {
-                link,
-                description,
-                headers,
-            } => [
-                1.into_dart(),
-                link.into_into_dart().into_dart(),
-                description.into_into_dart().into_dart(),
-                headers.into_into_dart().into_dart(),
+            crate::errors::SupplierError::Parse { message } => {
+                [3.into_dart(), message.into_into_dart().into_dart()].into_dart()
+            }
+            crate::errors::SupplierError::NotFound { message } => {
+                [4.into_dart(), message.into_into_dart().into_dart()].into_dart()
+            }
+            crate::errors::SupplierError::LayoutChanged { path, selector } => [
+                5.into_dart(),
+                path.into_into_dart().into_dart(),
+                selector.into_into_dart().into_dart(),
             ]
             .into_dart(),
-            crate::models::ContentMediaItemSource::Manga {
-                description,
-                headers,
-                pages,
-                params,
+            crate::errors::SupplierError::Blocked { protection, url } => [
+                6.into_dart(),
+                protection.into_into_dart().into_dart(),
+                url.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::errors::SupplierError::Unsupported { operation } => {
+                [7.into_dart(), operation.into_into_dart().into_dart()].into_dart()
+            }
+            crate::errors::SupplierError::InvalidParams { message } => {
+                [8.into_dart(), message.into_into_dart().into_dart()].into_dart()
+            }
+            crate::errors::SupplierError::NotConfigured {
+                supplier,
+                credential,
             } => [
-                2.into_dart(),
-                description.into_into_dart().into_dart(),
-                headers.into_into_dart().into_dart(),
-                pages.into_into_dart().into_dart(),
-                params.into_into_dart().into_dart(),
+                9.into_dart(),
+                supplier.into_into_dart().into_dart(),
+                credential.into_into_dart().into_dart(),
             ]
             .into_dart(),
+            crate::errors::SupplierError::Cancelled => [10.into_dart()].into_dart(),
+            crate::errors::SupplierError::Storage { message } => {
+                [11.into_dart(), message.into_into_dart().into_dart()].into_dart()
+            }
             _ => {
                 unimplemented!("");
             }
         }
     }
 }
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
-    for crate::models::ContentMediaItemSource
-{
-}
-impl flutter_rust_bridge::IntoIntoDart<crate::models::ContentMediaItemSource>
-    for crate::models::ContentMediaItemSource
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::errors::SupplierError {}
+impl flutter_rust_bridge::IntoIntoDart<crate::errors::SupplierError>
+    for crate::errors::SupplierError
 {
-    fn into_into_dart(self) -> crate::models::ContentMediaItemSource {
+    fn into_into_dart(self) -> crate::errors::SupplierError {
         self
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for crate::models::ContentType {
+impl flutter_rust_bridge::IntoDart for crate::models::SupplierInfo {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
-        match self {
-            Self::Movie => 0.into_dart(),
-            Self::Anime => 1.into_dart(),
-            Self::Cartoon => 2.into_dart(),
-            Self::Series => 3.into_dart(),
-            Self::Manga => 4.into_dart(),
-            _ => unreachable!(),
-        }
+        [
+            self.name.into_into_dart().into_dart(),
+            self.display_name.into_into_dart().into_dart(),
+            self.home_url.into_into_dart().into_dart(),
+            self.icon_url.into_into_dart().into_dart(),
+            self.channels.into_into_dart().into_dart(),
+            self.default_channels.into_into_dart().into_dart(),
+            self.supported_types.into_into_dart().into_dart(),
+            self.supported_languages.into_into_dart().into_dart(),
+            self.search_paging.into_into_dart().into_dart(),
+            self.channel_paging.into_into_dart().into_dart(),
+            self.manga_pages.into_into_dart().into_dart(),
+            self.hls_proxy.into_into_dart().into_dart(),
+            self.credentials.into_into_dart().into_dart(),
+            self.settings.into_into_dart().into_dart(),
+        ]
+        .into_dart()
     }
 }
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::ContentType {}
-impl flutter_rust_bridge::IntoIntoDart<crate::models::ContentType> for crate::models::ContentType {
-    fn into_into_dart(self) -> crate::models::ContentType {
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::SupplierInfo {}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::SupplierInfo>
+    for crate::models::SupplierInfo
+{
+    fn into_into_dart(self) -> crate::models::SupplierInfo {
         self
     }
 }
 // Codec=Dco (DartCObject based), see doc to use other codecs
-impl flutter_rust_bridge::IntoDart for crate::models::MediaType {
+impl flutter_rust_bridge::IntoDart for crate::models::SupplierSetting {
     fn into_dart(self) -> flutter_rust_bridge::for_generated::DartAbi {
-        match self {
-            Self::Video => 0.into_dart(),
-            Self::Manga => 1.into_dart(),
-            _ => unreachable!(),
-        }
+        [
+            self.key.into_into_dart().into_dart(),
+            self.setting_type.into_into_dart().into_dart(),
+            self.allowed_values.into_into_dart().into_dart(),
+            self.default_values.into_into_dart().into_dart(),
+        ]
+        .into_dart()
     }
 }
-impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive for crate::models::MediaType {}
-impl flutter_rust_bridge::IntoIntoDart<crate::models::MediaType> for crate::models::MediaType {
-    fn into_into_dart(self) -> crate::models::MediaType {
+impl flutter_rust_bridge::for_generated::IntoDartExceptPrimitive
+    for crate::models::SupplierSetting
+{
+}
+impl flutter_rust_bridge::IntoIntoDart<crate::models::SupplierSetting>
+    for crate::models::SupplierSetting
+{
+    fn into_into_dart(self) -> crate::models::SupplierSetting {
         self
     }
 }
 
-impl SseEncode for flutter_rust_bridge::for_generated::anyhow::Error {
+impl SseEncode for std::collections::HashMap<String, String> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <String>::sse_encode(format!("{:?}", self), serializer);
+        <Vec<(String, String)>>::sse_encode(self.into_iter().collect(), serializer);
     }
 }
 
-impl SseEncode for std::collections::HashMap<String, String> {
+impl SseEncode for std::collections::HashMap<String, Vec<String>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <Vec<(String, String)>>::sse_encode(self.into_iter().collect(), serializer);
+        <Vec<(String, Vec<String>)>>::sse_encode(self.into_iter().collect(), serializer);
+    }
+}
+
+impl SseEncode
+    for StreamSink<crate::models::DownloadEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        unimplemented!("")
+    }
+}
+
+impl SseEncode
+    for StreamSink<crate::models::SearchAllEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        unimplemented!("")
+    }
+}
+
+impl SseEncode
+    for StreamSink<crate::models::SourcesEvent, flutter_rust_bridge::for_generated::SseCodec>
+{
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        unimplemented!("")
     }
 }
 
@@ -1064,6 +3323,13 @@ impl SseEncode for bool {
     }
 }
 
+impl SseEncode for crate::models::CancelToken {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <u32>::sse_encode(self.id, serializer);
+    }
+}
+
 impl SseEncode for crate::models::ContentDetails {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1151,22 +3417,150 @@ impl SseEncode for crate::models::ContentMediaItemSource {
     }
 }
 
-impl SseEncode for crate::models::ContentType {
+impl SseEncode for crate::models::ContentType {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::models::ContentType::Movie => 0,
+                crate::models::ContentType::Anime => 1,
+                crate::models::ContentType::Cartoon => 2,
+                crate::models::ContentType::Series => 3,
+                crate::models::ContentType::Manga => 4,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::models::DnsConfig {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <Vec<crate::models::DnsResolver>>::sse_encode(self.resolvers, serializer);
+        <std::collections::HashMap<String, Vec<String>>>::sse_encode(self.overrides, serializer);
+        <Option<u32>>::sse_encode(self.min_ttl_secs, serializer);
+        <Option<u32>>::sse_encode(self.max_ttl_secs, serializer);
+        <Option<u32>>::sse_encode(self.negative_ttl_secs, serializer);
+    }
+}
+
+impl SseEncode for crate::models::DnsLookup {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <Vec<String>>::sse_encode(self.addresses, serializer);
+        <String>::sse_encode(self.resolver, serializer);
+    }
+}
+
+impl SseEncode for crate::models::DnsProtocol {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::models::DnsProtocol::Udp => 0,
+                crate::models::DnsProtocol::Https => 1,
+                crate::models::DnsProtocol::Tls => 2,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::models::DnsResolver {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::models::DnsResolver::System => {
+                <i32>::sse_encode(0, serializer);
+            }
+            crate::models::DnsResolver::Cloudflare { protocol } => {
+                <i32>::sse_encode(1, serializer);
+                <crate::models::DnsProtocol>::sse_encode(protocol, serializer);
+            }
+            crate::models::DnsResolver::Google { protocol } => {
+                <i32>::sse_encode(2, serializer);
+                <crate::models::DnsProtocol>::sse_encode(protocol, serializer);
+            }
+            crate::models::DnsResolver::Quad9 { protocol } => {
+                <i32>::sse_encode(3, serializer);
+                <crate::models::DnsProtocol>::sse_encode(protocol, serializer);
+            }
+            crate::models::DnsResolver::CustomHttps { url, ips } => {
+                <i32>::sse_encode(4, serializer);
+                <String>::sse_encode(url, serializer);
+                <Vec<String>>::sse_encode(ips, serializer);
+            }
+            crate::models::DnsResolver::CustomTls { server_name, ips } => {
+                <i32>::sse_encode(5, serializer);
+                <String>::sse_encode(server_name, serializer);
+                <Vec<String>>::sse_encode(ips, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseEncode for crate::models::DownloadEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::models::DownloadEvent::Progress {
+                completed_segments,
+                total_segments,
+                downloaded_bytes,
+                total_bytes,
+            } => {
+                <i32>::sse_encode(0, serializer);
+                <u32>::sse_encode(completed_segments, serializer);
+                <u32>::sse_encode(total_segments, serializer);
+                <u64>::sse_encode(downloaded_bytes, serializer);
+                <Option<u64>>::sse_encode(total_bytes, serializer);
+            }
+            crate::models::DownloadEvent::Completed { path, size } => {
+                <i32>::sse_encode(1, serializer);
+                <String>::sse_encode(path, serializer);
+                <u64>::sse_encode(size, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseEncode for crate::models::DownloadOptions {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <Option<u32>>::sse_encode(self.max_height, serializer);
+        <u32>::sse_encode(self.concurrency, serializer);
+    }
+}
+
+impl SseEncode for f64 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
-        <i32>::sse_encode(
-            match self {
-                crate::models::ContentType::Movie => 0,
-                crate::models::ContentType::Anime => 1,
-                crate::models::ContentType::Cartoon => 2,
-                crate::models::ContentType::Series => 3,
-                crate::models::ContentType::Manga => 4,
-                _ => {
-                    unimplemented!("");
-                }
-            },
-            serializer,
-        );
+        serializer.cursor.write_f64::<NativeEndian>(self).unwrap();
+    }
+}
+
+impl SseEncode for crate::models::HttpCookie {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.name, serializer);
+        <String>::sse_encode(self.value, serializer);
+        <String>::sse_encode(self.domain, serializer);
+        <Option<String>>::sse_encode(self.path, serializer);
+        <Option<i64>>::sse_encode(self.expires, serializer);
+        <bool>::sse_encode(self.secure, serializer);
+        <bool>::sse_encode(self.http_only, serializer);
     }
 }
 
@@ -1177,6 +3571,13 @@ impl SseEncode for i32 {
     }
 }
 
+impl SseEncode for i64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_i64::<NativeEndian>(self).unwrap();
+    }
+}
+
 impl SseEncode for Vec<String> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1227,6 +3628,26 @@ impl SseEncode for Vec<crate::models::ContentType> {
     }
 }
 
+impl SseEncode for Vec<crate::models::DnsResolver> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::models::DnsResolver>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Vec<crate::models::HttpCookie> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::models::HttpCookie>::sse_encode(item, serializer);
+        }
+    }
+}
+
 impl SseEncode for Vec<u8> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1237,6 +3658,16 @@ impl SseEncode for Vec<u8> {
     }
 }
 
+impl SseEncode for Vec<(String, Vec<String>)> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <(String, Vec<String>)>::sse_encode(item, serializer);
+        }
+    }
+}
+
 impl SseEncode for Vec<(String, String)> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1247,6 +3678,26 @@ impl SseEncode for Vec<(String, String)> {
     }
 }
 
+impl SseEncode for Vec<crate::models::SearchAllItem> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::models::SearchAllItem>::sse_encode(item, serializer);
+        }
+    }
+}
+
+impl SseEncode for Vec<crate::models::SupplierSetting> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(self.len() as _, serializer);
+        for item in self {
+            <crate::models::SupplierSetting>::sse_encode(item, serializer);
+        }
+    }
+}
+
 impl SseEncode for crate::models::MediaType {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1283,6 +3734,16 @@ impl SseEncode for Option<String> {
     }
 }
 
+impl SseEncode for Option<crate::models::CancelToken> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <crate::models::CancelToken>::sse_encode(value, serializer);
+        }
+    }
+}
+
 impl SseEncode for Option<crate::models::ContentDetails> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1293,6 +3754,46 @@ impl SseEncode for Option<crate::models::ContentDetails> {
     }
 }
 
+impl SseEncode for Option<i64> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <i64>::sse_encode(value, serializer);
+        }
+    }
+}
+
+impl SseEncode for Option<crate::models::ProxyConfig> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <crate::models::ProxyConfig>::sse_encode(value, serializer);
+        }
+    }
+}
+
+impl SseEncode for Option<u32> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <u32>::sse_encode(value, serializer);
+        }
+    }
+}
+
+impl SseEncode for Option<u64> {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <bool>::sse_encode(self.is_some(), serializer);
+        if let Some(value) = self {
+            <u64>::sse_encode(value, serializer);
+        }
+    }
+}
+
 impl SseEncode for Option<Vec<String>> {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1323,6 +3824,42 @@ impl SseEncode for Option<Vec<crate::models::ContentMediaItemSource>> {
     }
 }
 
+impl SseEncode for crate::models::ProxyConfig {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <crate::models::ProxyKind>::sse_encode(self.kind, serializer);
+        <String>::sse_encode(self.host, serializer);
+        <u16>::sse_encode(self.port, serializer);
+        <Option<String>>::sse_encode(self.username, serializer);
+        <Option<String>>::sse_encode(self.password, serializer);
+    }
+}
+
+impl SseEncode for crate::models::ProxyKind {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::models::ProxyKind::Http => 0,
+                crate::models::ProxyKind::Https => 1,
+                crate::models::ProxyKind::Socks5 => 2,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for (String, Vec<String>) {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.0, serializer);
+        <Vec<String>>::sse_encode(self.1, serializer);
+    }
+}
+
 impl SseEncode for (String, String) {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1331,6 +3868,225 @@ impl SseEncode for (String, String) {
     }
 }
 
+impl SseEncode for crate::utils::request_policy::RequestPolicy {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <f64>::sse_encode(self.requests_per_second, serializer);
+        <u32>::sse_encode(self.burst, serializer);
+        <u32>::sse_encode(self.max_retries, serializer);
+        <u64>::sse_encode(self.base_delay_ms, serializer);
+        <u64>::sse_encode(self.max_delay_ms, serializer);
+    }
+}
+
+impl SseEncode for crate::models::SearchAllEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::models::SearchAllEvent::Results { supplier, items } => {
+                <i32>::sse_encode(0, serializer);
+                <String>::sse_encode(supplier, serializer);
+                <Vec<crate::models::SearchAllItem>>::sse_encode(items, serializer);
+            }
+            crate::models::SearchAllEvent::Duplicate {
+                item,
+                original_supplier,
+                original_id,
+            } => {
+                <i32>::sse_encode(1, serializer);
+                <crate::models::SearchAllItem>::sse_encode(item, serializer);
+                <String>::sse_encode(original_supplier, serializer);
+                <String>::sse_encode(original_id, serializer);
+            }
+            crate::models::SearchAllEvent::Failed { supplier, error } => {
+                <i32>::sse_encode(2, serializer);
+                <String>::sse_encode(supplier, serializer);
+                <String>::sse_encode(error, serializer);
+            }
+            crate::models::SearchAllEvent::Completed => {
+                <i32>::sse_encode(3, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseEncode for crate::models::SearchAllItem {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.supplier, serializer);
+        <crate::models::ContentInfo>::sse_encode(self.info, serializer);
+    }
+}
+
+impl SseEncode for crate::models::SearchFilters {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <Vec<String>>::sse_encode(self.suppliers, serializer);
+        <Vec<crate::models::ContentType>>::sse_encode(self.content_types, serializer);
+        <Vec<String>>::sse_encode(self.languages, serializer);
+    }
+}
+
+impl SseEncode for crate::models::SettingType {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <i32>::sse_encode(
+            match self {
+                crate::models::SettingType::Bool => 0,
+                crate::models::SettingType::Choice => 1,
+                crate::models::SettingType::MultiChoice => 2,
+                crate::models::SettingType::Text => 3,
+                _ => {
+                    unimplemented!("");
+                }
+            },
+            serializer,
+        );
+    }
+}
+
+impl SseEncode for crate::models::SourcesEvent {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::models::SourcesEvent::Sources { origin, sources } => {
+                <i32>::sse_encode(0, serializer);
+                <String>::sse_encode(origin, serializer);
+                <Vec<crate::models::ContentMediaItemSource>>::sse_encode(sources, serializer);
+            }
+            crate::models::SourcesEvent::Completed { failed } => {
+                <i32>::sse_encode(1, serializer);
+                <Vec<String>>::sse_encode(failed, serializer);
+            }
+            crate::models::SourcesEvent::Error { error } => {
+                <i32>::sse_encode(2, serializer);
+                <crate::errors::SupplierError>::sse_encode(error, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseEncode for crate::errors::SupplierError {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::errors::SupplierError::Network { message } => {
+                <i32>::sse_encode(0, serializer);
+                <String>::sse_encode(message, serializer);
+            }
+            crate::errors::SupplierError::Timeout => {
+                <i32>::sse_encode(1, serializer);
+            }
+            crate::errors::SupplierError::HttpStatus { status, url } => {
+                <i32>::sse_encode(2, serializer);
+                <u16>::sse_encode(status, serializer);
+                <String>::sse_encode(url, serializer);
+            }
+            crate::errors::SupplierError::Parse { message } => {
+                <i32>::sse_encode(3, serializer);
+                <String>::sse_encode(message, serializer);
+            }
+            crate::errors::SupplierError::NotFound { message } => {
+                <i32>::sse_encode(4, serializer);
+                <String>::sse_encode(message, serializer);
+            }
+            crate::errors::SupplierError::LayoutChanged { path, selector } => {
+                <i32>::sse_encode(5, serializer);
+                <String>::sse_encode(path, serializer);
+                <String>::sse_encode(selector, serializer);
+            }
+            crate::errors::SupplierError::Blocked { protection, url } => {
+                <i32>::sse_encode(6, serializer);
+                <String>::sse_encode(protection, serializer);
+                <String>::sse_encode(url, serializer);
+            }
+            crate::errors::SupplierError::Unsupported { operation } => {
+                <i32>::sse_encode(7, serializer);
+                <String>::sse_encode(operation, serializer);
+            }
+            crate::errors::SupplierError::InvalidParams { message } => {
+                <i32>::sse_encode(8, serializer);
+                <String>::sse_encode(message, serializer);
+            }
+            crate::errors::SupplierError::NotConfigured {
+                supplier,
+                credential,
+            } => {
+                <i32>::sse_encode(9, serializer);
+                <String>::sse_encode(supplier, serializer);
+                <String>::sse_encode(credential, serializer);
+            }
+            crate::errors::SupplierError::Cancelled => {
+                <i32>::sse_encode(10, serializer);
+            }
+            crate::errors::SupplierError::Storage { message } => {
+                <i32>::sse_encode(11, serializer);
+                <String>::sse_encode(message, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseEncode for crate::models::SupplierInfo {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.name, serializer);
+        <String>::sse_encode(self.display_name, serializer);
+        <String>::sse_encode(self.home_url, serializer);
+        <String>::sse_encode(self.icon_url, serializer);
+        <Vec<String>>::sse_encode(self.channels, serializer);
+        <Vec<String>>::sse_encode(self.default_channels, serializer);
+        <Vec<crate::models::ContentType>>::sse_encode(self.supported_types, serializer);
+        <Vec<String>>::sse_encode(self.supported_languages, serializer);
+        <bool>::sse_encode(self.search_paging, serializer);
+        <bool>::sse_encode(self.channel_paging, serializer);
+        <bool>::sse_encode(self.manga_pages, serializer);
+        <bool>::sse_encode(self.hls_proxy, serializer);
+        <Vec<String>>::sse_encode(self.credentials, serializer);
+        <Vec<crate::models::SupplierSetting>>::sse_encode(self.settings, serializer);
+    }
+}
+
+impl SseEncode for crate::models::SupplierProxy {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        match self {
+            crate::models::SupplierProxy::Default => {
+                <i32>::sse_encode(0, serializer);
+            }
+            crate::models::SupplierProxy::Direct => {
+                <i32>::sse_encode(1, serializer);
+            }
+            crate::models::SupplierProxy::Proxy { config } => {
+                <i32>::sse_encode(2, serializer);
+                <crate::models::ProxyConfig>::sse_encode(config, serializer);
+            }
+            _ => {
+                unimplemented!("");
+            }
+        }
+    }
+}
+
+impl SseEncode for crate::models::SupplierSetting {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        <String>::sse_encode(self.key, serializer);
+        <crate::models::SettingType>::sse_encode(self.setting_type, serializer);
+        <Vec<String>>::sse_encode(self.allowed_values, serializer);
+        <Vec<String>>::sse_encode(self.default_values, serializer);
+    }
+}
+
 impl SseEncode for u16 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1338,6 +4094,20 @@ impl SseEncode for u16 {
     }
 }
 
+impl SseEncode for u32 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_u32::<NativeEndian>(self).unwrap();
+    }
+}
+
+impl SseEncode for u64 {
+    // Codec=Sse (Serialization based), see doc to use other codecs
+    fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
+        serializer.cursor.write_u64::<NativeEndian>(self).unwrap();
+    }
+}
+
 impl SseEncode for u8 {
     // Codec=Sse (Serialization based), see doc to use other codecs
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
@@ -1,6 +1,7 @@
 pub mod api;
 mod suppliers;
 mod models;
+mod errors;
 mod utils;
 mod extractors;
 mod frb_generated;
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 
+use crate::errors::SupplierError;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ContentType {
     Movie,
     Anime,
@@ -65,4 +66,224 @@ pub enum ContentMediaItemSource {
         pages: Option<Vec<String>>,
         params: Vec<String>,
     },
-}
\ No newline at end of file
+}
+#[derive(Debug)]
+pub struct SupplierInfo {
+    pub name: String,
+    pub display_name: String,
+    pub home_url: String,
+    pub icon_url: String,
+    pub channels: Vec<String>,
+    pub default_channels: Vec<String>,
+    pub supported_types: Vec<ContentType>,
+    pub supported_languages: Vec<String>,
+    /// search accepts page > 1
+    pub search_paging: bool,
+    /// channels accept page > 1
+    pub channel_paging: bool,
+    /// `load_manga_pages` is supported
+    pub manga_pages: bool,
+    /// some sources must be played through local hls proxy
+    pub hls_proxy: bool,
+    /// secrets host app has to set with `set_supplier_credential`
+    pub credentials: Vec<String>,
+    /// schema of user preferences, read and written with `get_supplier_setting`/`set_supplier_setting`
+    pub settings: Vec<SupplierSetting>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingType {
+    /// single `true` or `false` value
+    Bool,
+    /// single value from `allowed_values`
+    Choice,
+    /// any subset of `allowed_values`
+    MultiChoice,
+    /// single free form value
+    Text,
+}
+
+/// User preference supplier honours, host app renders settings page from it
+#[derive(Debug, Clone)]
+pub struct SupplierSetting {
+    pub key: String,
+    pub setting_type: SettingType,
+    /// empty for `Bool` and `Text`
+    pub allowed_values: Vec<String>,
+    pub default_values: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DnsProtocol {
+    Udp,
+    /// DNS over HTTPS
+    Https,
+    /// DNS over TLS
+    Tls,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DnsResolver {
+    /// resolvers from os network settings
+    System,
+    Cloudflare {
+        protocol: DnsProtocol,
+    },
+    Google {
+        protocol: DnsProtocol,
+    },
+    Quad9 {
+        protocol: DnsProtocol,
+    },
+    /// DoH endpoint like `https://dns.example/dns-query`, `ips` skip system lookup of its host
+    CustomHttps {
+        url: String,
+        ips: Vec<String>,
+    },
+    /// DoT server on port 853
+    CustomTls {
+        server_name: String,
+        ips: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DnsConfig {
+    /// tried in order until one answers, empty restores system resolver with Cloudflare fallback
+    pub resolvers: Vec<DnsResolver>,
+    /// hostname to ip addresses, answered without asking any resolver
+    pub overrides: HashMap<String, Vec<String>>,
+    /// bounds of cached answers ttl, resolver defaults when not set
+    pub min_ttl_secs: Option<u32>,
+    pub max_ttl_secs: Option<u32>,
+    /// how long failed lookups are cached
+    pub negative_ttl_secs: Option<u32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DnsLookup {
+    pub addresses: Vec<String>,
+    /// resolver that answered (`override`, `system`, `cloudflare-https`...)
+    pub resolver: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyKind {
+    Http,
+    Https,
+    /// host names are resolved by proxy
+    Socks5,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyConfig {
+    pub kind: ProxyKind,
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+/// How requests to supplier site and its mirrors are sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SupplierProxy {
+    /// same as all other requests
+    Default,
+    /// bypass default proxy
+    Direct,
+    Proxy {
+        config: ProxyConfig,
+    },
+}
+
+/// Cookie of shared jar, same fields as WebView cookie managers expose
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpCookie {
+    pub name: String,
+    pub value: String,
+    /// leading dot matches subdomains too
+    pub domain: String,
+    /// `/` when not set
+    pub path: Option<String>,
+    /// unix seconds, session cookie when not set
+    pub expires: Option<i64>,
+    pub secure: bool,
+    pub http_only: bool,
+}
+
+/// Handle created by host app to abort in-flight calls
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CancelToken {
+    pub id: u32,
+}
+
+#[derive(Debug)]
+pub enum SourcesEvent {
+    /// batch resolved by one extractor or server
+    Sources {
+        origin: String,
+        sources: Vec<ContentMediaItemSource>,
+    },
+    /// last event of the stream, lists extractors or servers that failed
+    Completed { failed: Vec<String> },
+    /// last event of the stream when the whole call failed or was cancelled
+    Error { error: SupplierError },
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilters {
+    /// suppliers to query, all suppliers when empty
+    pub suppliers: Vec<String>,
+    /// skip suppliers without any of these content types
+    pub content_types: Vec<ContentType>,
+    /// skip suppliers without any of these languages
+    pub languages: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct SearchAllItem {
+    pub supplier: String,
+    pub info: ContentInfo,
+}
+
+#[derive(Debug)]
+pub enum SearchAllEvent {
+    /// titles not seen in earlier results
+    Results {
+        supplier: String,
+        items: Vec<SearchAllItem>,
+    },
+    /// same title already returned by other supplier
+    Duplicate {
+        item: SearchAllItem,
+        original_supplier: String,
+        original_id: String,
+    },
+    Failed {
+        supplier: String,
+        error: String,
+    },
+    Completed,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DownloadOptions {
+    /// highest variant height picked from master playlist, best quality when not set
+    pub max_height: Option<u32>,
+    /// segments downloaded at once, 0 uses default
+    pub concurrency: u32,
+}
+
+#[derive(Debug)]
+pub enum DownloadEvent {
+    /// sent after each segment or chunk of plain file, includes parts done by earlier attempts
+    Progress {
+        completed_segments: u32,
+        total_segments: u32,
+        downloaded_bytes: u64,
+        /// known for plain files only
+        total_bytes: Option<u64>,
+    },
+    /// last event of the stream, `path` is output with `ts`, `mp4`, `mkv` or `webm` extension added
+    Completed { path: String, size: u64 },
+}
@@ -1,28 +1,32 @@
-use anyhow::anyhow;
 use indexmap::IndexMap;
-use log::error;
 use scraper::Selector;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::{
     extractors::megaplay,
     models::{
         ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
-        MediaType,
+        MediaType, SupplierSetting,
     },
     suppliers::ContentSupplier,
     utils::{
         self,
         html::{self, DOMProcessor, text_value, text_value_map},
+        lang,
+        params::{self, LegacyParams, MediaParams},
+        settings,
+        sources_sink::SourcesSink,
     },
 };
 
+const NAME: &str = "Anikoto";
 const URL: &str = "https://anikototv.to";
 
 pub struct AnikotoContentSupplier {
     channels_map: IndexMap<&'static str, String>,
     processor_content_info_items: html::ItemsProcessor<ContentInfo>,
-    processor_content_details: html::ScopeProcessor<ContentDetails>,
+    processor_content_details: html::Required<Option<ContentDetails>>,
     selector_subs: scraper::Selector,
     selector_dubs: scraper::Selector,
     seasons_selector: scraper::Selector,
@@ -35,12 +39,12 @@ impl Default for AnikotoContentSupplier {
             processor_content_info_items: html::ItemsProcessor::new(
                 "#list-items > .item > .inner",
                 html::ContentInfoProcessor {
-                    id: html::attr_value_map(
+                    id: html::required(html::attr_value_map(
                         ".poster > a",
                         "href",
                         AnikotoContentSupplier::extract_id_from_url,
-                    ),
-                    title: text_value(".info a.name"),
+                    )),
+                    title: html::required(text_value(".info a.name")),
                     secondary_title: html::default_value(),
                     image: html::attr_value(".poster img", "src"),
                 }
@@ -50,9 +54,9 @@ impl Default for AnikotoContentSupplier {
                 "body",
                 html::ContentDetailsProcessor {
                     media_type: MediaType::Video,
-                    title: html::text_value_map("#w-info h1.title", |s| {
+                    title: html::required(html::text_value_map("#w-info h1.title", |s| {
                         utils::text::sanitize_text(&s)
-                    }),
+                    })),
                     original_title: html::optional_attr_value("#w-info h1.title", "data-jp"),
                     image: html::self_hosted_image(URL, "#w-info .poster img", "src"),
                     description: html::text_value("#w-info .synopsis .shorting .content"),
@@ -81,7 +85,8 @@ impl Default for AnikotoContentSupplier {
                     params: html::attr_value_map("[data-id]", "data-id", |s| vec![s]),
                 }
                 .boxed(),
-            ),
+            )
+            .required(),
             selector_subs: Selector::parse(".type[data-type='sub'] ul li").unwrap(),
             selector_dubs: Selector::parse(".type[data-type='dub'] ul li").unwrap(),
             seasons_selector: Selector::parse(".ep-range li").unwrap(),
@@ -106,7 +111,19 @@ impl ContentSupplier for AnikotoContentSupplier {
         vec!["en".into()]
     }
 
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    fn get_display_name(&self) -> String {
+        "Anikoto".into()
+    }
+
+    fn get_home_url(&self) -> String {
+        URL.into()
+    }
+
+    fn get_settings(&self) -> Vec<SupplierSetting> {
+        vec![subtitle_languages_setting()]
+    }
+
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         utils::scrap_page(
             utils::create_client()
                 .get(format!("{URL}/filter"))
@@ -116,10 +133,10 @@ impl ContentSupplier for AnikotoContentSupplier {
         .await
     }
 
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    async fn load_channel(&self, channel: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         let url = match self.channels_map.get(channel) {
             Some(url) => format!("{url}?page={page}"),
-            None => return Err(anyhow!("unknown channel")),
+            None => return Err(SupplierError::unknown_channel(channel)),
         };
 
         utils::scrap_page(
@@ -129,7 +146,7 @@ impl ContentSupplier for AnikotoContentSupplier {
         .await
     }
 
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>> {
         utils::scrap_page(
             utils::create_client().get(format!("{URL}/watch/{id}/ep-1")),
             &self.processor_content_details,
@@ -141,25 +158,21 @@ impl ContentSupplier for AnikotoContentSupplier {
         &self,
         id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
-        if params.is_empty() {
-            return Err(anyhow!("data id expected in params"));
-        }
-
-        let data_id = &params[0];
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
+        // data id is scraped straight from details page
+        let data_id = params::required(&params, 0, "data id")?;
 
         let list_url = format!("{URL}/ajax/episode/list/{data_id}?vrf=");
 
         // println!("{list_url}");
 
-        let list_response_str = utils::create_json_client()
-            .get(list_url)
-            .header("X-Requested-With", "XMLHttpRequest")
-            .header("Referer", format!("{URL}/watch/{id}"))
-            .send()
-            .await?
-            .text()
-            .await?;
+        let list_response_str = utils::fetch_text(
+            utils::create_json_client()
+                .get(list_url)
+                .header("X-Requested-With", "XMLHttpRequest")
+                .header("Referer", format!("{URL}/watch/{id}")),
+        )
+        .await?;
 
         // println!("{list_response_str}");
 
@@ -182,7 +195,9 @@ impl ContentSupplier for AnikotoContentSupplier {
                     section: None,
                     image: None,
                     sources: None,
-                    params: vec![data_id.to_string()],
+                    params: params::encode(&EpisodeParams {
+                        episode_id: data_id.to_owned(),
+                    }),
                 })
             })
             .collect();
@@ -194,26 +209,64 @@ impl ContentSupplier for AnikotoContentSupplier {
         &self,
         id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        if params.is_empty() {
-            return Err(anyhow!("episode id expected"));
-        }
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        let sink = SourcesSink::default();
+        self.stream_media_item_sources(id, params, &sink).await?;
+
+        Ok(sink.into_sources())
+    }
+
+    async fn stream_media_item_sources(
+        &self,
+        id: &str,
+        params: Vec<String>,
+        sink: &SourcesSink,
+    ) -> SupplierResult<()> {
+        let EpisodeParams { episode_id } = params::decode(&params)?;
 
         let url = format!("{URL}/watch/{id}/ep-1");
 
-        let episode_id = &params[0];
-        let servers = self.extract_servers(id, episode_id).await?;
+        let servers = self.extract_servers(id, &episode_id).await?;
 
         // dbg!(&servers);
 
-        let futures = servers
+        let url = url.as_str();
+        let tasks = servers
             .into_iter()
-            .map(|server| self.load_server_sources(&url, server));
+            .map(|server| (server.title(), self.load_server_sources(url, server)));
 
-        let results = futures::future::join_all(futures).await;
-        let sources: Vec<_> = results.into_iter().flatten().collect();
+        sink.fan_out(tasks).await;
 
-        Ok(sources)
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+struct EpisodeParams {
+    episode_id: String,
+}
+
+impl MediaParams for EpisodeParams {
+    const KIND: &'static str = "anikoto_episode";
+    const VERSION: u32 = 2;
+
+    // v1 is bare [episode_id]
+    fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+        match legacy {
+            LegacyParams::Raw(raw) => Ok(Self {
+                episode_id: params::required(raw, 0, "episode id")?.to_owned(),
+            }),
+            LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(format!(
+                "anikoto episode params version {version} is not supported"
+            ))),
+        }
+    }
+
+    fn validate(&self) -> SupplierResult<()> {
+        if self.episode_id.is_empty() {
+            return Err(SupplierError::invalid_params("episode id expected"));
+        }
+        Ok(())
     }
 }
 
@@ -229,21 +282,26 @@ struct AnikotoServer {
     dub: bool,
 }
 
+impl AnikotoServer {
+    fn title(&self) -> String {
+        format!("[{}] {}", if self.dub { "dub" } else { "sub" }, self.name)
+    }
+}
+
 impl AnikotoContentSupplier {
     async fn extract_servers(
         &self,
         id: &str,
         episode_id: &str,
-    ) -> anyhow::Result<Vec<AnikotoServer>> {
-        let servers_response: ListResponse = utils::create_client()
-            .get(format!("{URL}/ajax/server/list"))
-            .query(&[("servers", episode_id)])
-            .header("X-Requested-With", "XMLHttpRequest")
-            .header("Referer", format!("{URL}/watch/{id}"))
-            .send()
-            .await?
-            .json()
-            .await?;
+    ) -> SupplierResult<Vec<AnikotoServer>> {
+        let servers_response: ListResponse = utils::fetch_json(
+            utils::create_client()
+                .get(format!("{URL}/ajax/server/list"))
+                .query(&[("servers", episode_id)])
+                .header("X-Requested-With", "XMLHttpRequest")
+                .header("Referer", format!("{URL}/watch/{id}")),
+        )
+        .await?;
 
         // dbg!(servers_response);
 
@@ -273,7 +331,7 @@ impl AnikotoContentSupplier {
         Some(AnikotoServer {
             id: data_id.to_owned(),
             name: utils::text::sanitize_text(&title).to_lowercase(),
-            dub: dub,
+            dub,
         })
     }
 
@@ -281,44 +339,21 @@ impl AnikotoContentSupplier {
         &self,
         referer: &str,
         server: AnikotoServer,
-    ) -> Vec<ContentMediaItemSource> {
-        let server_id = server.id.as_str();
-        let server_name = server.name.as_str();
-
-        let title = format!(
-            "[{}] {}",
-            if server.dub { "dub" } else { "sub" },
-            server_name
-        );
-
-        let link = match self.load_server_source_link(referer, server_id).await {
-            Ok(link) => link,
-            Err(err) => {
-                error!(
-                    "[anikoto] fail to load source link (server_id: {server_id}, server_name: {server_name}): {err}"
-                );
-                return vec![];
-            }
-        };
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        let title = server.title();
+        let link = self.load_server_source_link(referer, &server.id).await?;
 
-        let res = match server.name.as_str() {
+        match server.name.as_str() {
             "hd-1" | "hd-2" | "vidstream-1" | "vidstream-2" | "vidcloud-1" | "vidcloud-2"
-            | "megaplay-1" | "megaplay-2" => megaplay::extract(&link, referer, title, false).await,
-            _ => return vec![],
-        };
-
-        match res {
-            Ok(sources) => sources,
-            Err(err) => {
-                error!(
-                    "[anikoto] fail to load source link (link: {link}, server_name: {server_name}): {err}"
-                );
-                vec![]
+            | "megaplay-1" | "megaplay-2" => {
+                let subtitle_langs = settings::values(NAME, &subtitle_languages_setting());
+                Ok(megaplay::extract(&link, referer, title, false, &subtitle_langs).await?)
             }
+            _ => Ok(vec![]),
         }
     }
 
-    async fn load_server_source_link(&self, url: &str, server_id: &str) -> anyhow::Result<String> {
+    async fn load_server_source_link(&self, url: &str, server_id: &str) -> SupplierResult<String> {
         #[derive(Deserialize, Debug)]
         struct SourcesResponse {
             result: SourcesResponseResult,
@@ -329,15 +364,14 @@ impl AnikotoContentSupplier {
             url: String,
         }
 
-        let sources_resposne: SourcesResponse = utils::create_client()
-            .get(format!("{URL}/ajax/server"))
-            .query(&[("get", server_id)])
-            .header("X-Requested-With", "XMLHttpRequest")
-            .header("Referer", url)
-            .send()
-            .await?
-            .json()
-            .await?;
+        let sources_resposne: SourcesResponse = utils::fetch_json(
+            utils::create_client()
+                .get(format!("{URL}/ajax/server"))
+                .query(&[("get", server_id)])
+                .header("X-Requested-With", "XMLHttpRequest")
+                .header("Referer", url),
+        )
+        .await?;
 
         Ok(sources_resposne.result.url)
     }
@@ -347,6 +381,10 @@ impl AnikotoContentSupplier {
     }
 }
 
+fn subtitle_languages_setting() -> SupplierSetting {
+    settings::multi_choice("subtitle_languages", &lang::LANGUAGES, &["en", "uk", "ja"])
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
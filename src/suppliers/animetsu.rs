@@ -2,19 +2,18 @@ use std::collections::HashMap;
 
 use serde::Deserialize;
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::{
     models::{
         ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
         MediaType,
     },
     suppliers::ContentSupplier,
-    utils,
+    utils::{self, sources_sink::SourcesSink},
 };
-use anyhow::anyhow;
 
 const URL: &str = "https://animetsu.live";
 const API_URL: &str = "https://animetsu.live/v2/api";
-const PROXY_URL: &str = "https://swiftstream.top/proxy";
 const PAGE_SIZE: u16 = 20;
 
 #[derive(Default)]
@@ -37,7 +36,24 @@ impl ContentSupplier for AnimetsuContentSupplier {
         vec!["en".to_string()]
     }
 
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    fn get_display_name(&self) -> String {
+        "Animetsu".into()
+    }
+
+    fn get_home_url(&self) -> String {
+        URL.into()
+    }
+
+    fn supports_search_paging(&self) -> bool {
+        false
+    }
+
+    fn needs_hls_proxy(&self) -> bool {
+        // streams with `need_proxy` are served through local hls proxy
+        true
+    }
+
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         if page > 1 {
             return Ok(vec![]);
         }
@@ -49,7 +65,7 @@ impl ContentSupplier for AnimetsuContentSupplier {
         .await
     }
 
-    async fn load_channel(&self, _: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    async fn load_channel(&self, _: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         self.fetch_anim_list(
             format!("{API_URL}/anime/search/"),
             &[
@@ -61,14 +77,13 @@ impl ContentSupplier for AnimetsuContentSupplier {
         .await
     }
 
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
-        let response_str = utils::create_json_client()
-            .get(format!("{API_URL}/anime/info/{id}"))
-            .header("Referer", URL)
-            .send()
-            .await?
-            .text()
-            .await?;
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>> {
+        let response_str = utils::fetch_text(
+            utils::create_json_client()
+                .get(format!("{API_URL}/anime/info/{id}"))
+                .header("Referer", URL),
+        )
+        .await?;
 
         let item: AnimeDetailsResponse = serde_json::from_str(&response_str)?;
 
@@ -99,10 +114,10 @@ impl ContentSupplier for AnimetsuContentSupplier {
         if let Some(score) = item.average_score {
             additional_info.push(format!("Score: {score}"));
         }
-        if let Some(genres) = item.genres {
-            if !genres.is_empty() {
-                additional_info.push(format!("Genres: {}", genres.join(", ")));
-            }
+        if let Some(genres) = item.genres
+            && !genres.is_empty()
+        {
+            additional_info.push(format!("Genres: {}", genres.join(", ")));
         }
 
         let similar = item
@@ -134,14 +149,13 @@ impl ContentSupplier for AnimetsuContentSupplier {
         &self,
         id: &str,
         _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
-        let response_str = utils::create_json_client()
-            .get(format!("{API_URL}/anime/eps/{id}"))
-            .header("Referer", URL)
-            .send()
-            .await?
-            .text()
-            .await?;
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
+        let response_str = utils::fetch_text(
+            utils::create_json_client()
+                .get(format!("{API_URL}/anime/eps/{id}"))
+                .header("Referer", URL),
+        )
+        .await?;
 
         let mut episodes: Vec<AnimeEpisode> = serde_json::from_str(&response_str)?;
 
@@ -176,32 +190,42 @@ impl ContentSupplier for AnimetsuContentSupplier {
         &self,
         id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        let sink = SourcesSink::default();
+        self.stream_media_item_sources(id, params, &sink).await?;
+
+        Ok(sink.into_sources())
+    }
+
+    async fn stream_media_item_sources(
+        &self,
+        id: &str,
+        params: Vec<String>,
+        sink: &SourcesSink,
+    ) -> SupplierResult<()> {
         let ep_num = params
             .first()
-            .ok_or_else(|| anyhow!("episode number expected in params"))?;
+            .ok_or_else(|| SupplierError::invalid_params("episode number expected in params"))?;
 
         let servers = ["pahe", "kite", "dio", "meg", "kiss"];
         let source_types = ["sub", "dub"];
 
-        let futures = servers.iter().flat_map(|server| {
-            source_types.iter().map(move |source_type| async move {
-                let response = self
-                    .fetch_episode_sources(id, ep_num, server, source_type)
-                    .await;
-                response.map(|r| (*source_type, r))
-            })
-        });
+        let mut tasks = vec![];
+        for server in servers {
+            for source_type in source_types {
+                let task = async move {
+                    let response = self
+                        .fetch_episode_sources(id, ep_num, server, source_type)
+                        .await?;
+                    Ok(Self::map_sources_response(response, source_type))
+                };
+                tasks.push((format!("{server}/{source_type}"), task));
+            }
+        }
 
-        let results = futures::future::join_all(futures).await;
+        sink.fan_out(tasks).await;
 
-        let sources = results
-            .into_iter()
-            .filter_map(|r| r.ok())
-            .flat_map(|(source_type, response)| Self::map_sources_response(response, source_type))
-            .collect();
-
-        Ok(sources)
+        Ok(())
     }
 }
 
@@ -210,15 +234,14 @@ impl AnimetsuContentSupplier {
         &self,
         url: String,
         query: &[(&str, String)],
-    ) -> anyhow::Result<Vec<ContentInfo>> {
-        let response_str = utils::create_json_client()
-            .get(url)
-            .query(query)
-            .header("Referer", URL)
-            .send()
-            .await?
-            .text()
-            .await?;
+    ) -> SupplierResult<Vec<ContentInfo>> {
+        let response_str = utils::fetch_text(
+            utils::create_json_client()
+                .get(url)
+                .query(query)
+                .header("Referer", URL),
+        )
+        .await?;
 
         let response: AnimeListResponse = serde_json::from_str(&response_str)?;
 
@@ -247,18 +270,13 @@ impl AnimetsuContentSupplier {
         ep_num: &str,
         server: &str,
         source_type: &str,
-    ) -> anyhow::Result<EpisodeSourcesResponse> {
+    ) -> SupplierResult<EpisodeSourcesResponse> {
         let url = format!(
             "{API_URL}/anime/oppai/{id}/{ep_num}?server={server}&source_type={source_type}"
         );
 
-        let response_str = utils::create_json_client()
-            .get(&url)
-            .header("Referer", URL)
-            .send()
-            .await?
-            .text()
-            .await?;
+        let response_str =
+            utils::fetch_text(utils::create_json_client().get(&url).header("Referer", URL)).await?;
 
         Ok(serde_json::from_str(&response_str)?)
     }
@@ -271,39 +289,28 @@ impl AnimetsuContentSupplier {
         let mut sources = vec![];
         let mut index = 1;
 
+        // streams marked with `need_proxy` check Referer
         for source in response.sources.unwrap_or_default() {
-            let link = if source.need_proxy {
-                format!("{PROXY_URL}{}", source.url)
-            } else {
-                source.url
-            };
-
             sources.push(ContentMediaItemSource::Video {
-                link,
+                link: source.url,
                 description: format!(
                     "[{server}] {index}. [{source_type}] {}",
                     source.quality.unwrap_or_default()
                 ),
-                headers: Some(HashMap::from([("Referer".to_string(), URL.to_string())])),
-                hls_proxy: false,
+                headers: Some(referer_headers()),
+                hls_proxy: source.need_proxy,
             });
             index += 1;
         }
 
         for sub in response.subs.unwrap_or_default() {
-            let link = if sub.need_proxy.unwrap_or(false) {
-                format!("{PROXY_URL}{}", sub.url)
-            } else {
-                sub.url
-            };
-
             sources.push(ContentMediaItemSource::Subtitle {
-                link,
+                link: sub.url,
                 description: format!(
                     "[{server}] {index}. [{source_type}] {}",
                     sub.lang.unwrap_or_default()
                 ),
-                headers: None,
+                headers: sub.need_proxy.unwrap_or(false).then(referer_headers),
             });
             index += 1;
         }
@@ -312,6 +319,10 @@ impl AnimetsuContentSupplier {
     }
 }
 
+fn referer_headers() -> HashMap<String, String> {
+    HashMap::from([("Referer".to_string(), URL.to_string())])
+}
+
 #[derive(Debug, Deserialize)]
 struct AnimeListResponse {
     results: Vec<AnimeListResultItem>,
@@ -1,11 +1,7 @@
-use anyhow::anyhow;
 use indexmap::IndexMap;
 
 use crate::{
-    models::{
-        ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
-        MediaType,
-    },
+    models::{ContentType, MediaType},
     utils::{
         html::{self, DOMProcessor},
         playerjs,
@@ -14,34 +10,39 @@ use crate::{
 
 use crate::utils::{self, datalife};
 
-use super::ContentSupplier;
+use super::datalife::{DataLifeConfig, DataLifeSite, DataLifeSupplier, Playlist, SearchPaging};
 
 const URL: &str = "https://animeua.club";
 
-pub struct AnimeUAContentSupplier {
-    channels_map: IndexMap<&'static str, String>,
-    processor_content_info_items: html::ItemsProcessor<ContentInfo>,
-    processor_content_details: html::ScopeProcessor<ContentDetails>,
-}
+pub type AnimeUAContentSupplier = DataLifeSupplier<AnimeUA>;
+
+#[derive(Default)]
+pub struct AnimeUA;
 
-impl Default for AnimeUAContentSupplier {
-    fn default() -> Self {
-        Self {
+impl DataLifeSite for AnimeUA {
+    fn config(&self) -> DataLifeConfig {
+        DataLifeConfig {
+            url: URL,
+            display_name: "AnimeUA",
+            mirrors: vec![],
+            supported_types: vec![ContentType::Anime],
+            supported_languages: vec!["uk"],
             channels_map: IndexMap::from([
                 ("Новинки", format!("{URL}/page/")),
                 ("ТОП 100", format!("{URL}/top.html")),
                 ("Повнометражки", format!("{URL}/film/page/")),
                 ("Аніме серіали", format!("{URL}/anime/page/")),
             ]),
-            processor_content_info_items: html::ItemsProcessor::new(
-                ".grid-item",
-                content_info_processor(),
+            search_paging: SearchPaging::FirstPage,
+            content_info_items: html::required_scope(
+                "#dle-content",
+                html::items_processor(".grid-item", content_info_processor()),
             ),
-            processor_content_details: html::ScopeProcessor::new(
+            content_details: html::ScopeProcessor::new(
                 "#dle-content",
                 html::ContentDetailsProcessor {
                     media_type: MediaType::Video,
-                    title: html::text_value(".page__subcol-main > h1"),
+                    title: html::required(html::text_value(".page__subcol-main > h1")),
                     original_title: html::optional_text_value(
                         ".page__subcol-main > .pmovie__original-title",
                     ),
@@ -68,7 +69,11 @@ impl Default for AnimeUAContentSupplier {
                     params: html::attr_value_map(".video-inside iframe", "data-src", |s| vec![s]),
                 }
                 .boxed(),
-            ),
+            )
+            .required()
+            .boxed(),
+            playlist: Some(Playlist::PlayerJS(playerjs::convert_strategy_dub_season_ep)),
+            login_hash: false,
         }
     }
 }
@@ -78,91 +83,19 @@ fn content_info_processor() -> Box<html::ContentInfoProcessor> {
         id: html::AttrValue::new("href")
             .map_optional(|s| datalife::extract_id_from_url(URL, s))
             .unwrap_or_default()
+            .required()
             .boxed(),
-        title: html::text_value(".poster__desc > .poster__title"),
+        title: html::required(html::text_value(".poster__desc > .poster__title")),
         secondary_title: html::default_value(),
         image: html::self_hosted_image(URL, ".poster__img img", "data-src"),
     }
     .into()
 }
 
-impl ContentSupplier for AnimeUAContentSupplier {
-    fn get_channels(&self) -> Vec<String> {
-        self.channels_map.keys().map(|&s| s.into()).collect()
-    }
-
-    fn get_default_channels(&self) -> Vec<String> {
-        vec![]
-    }
-
-    fn get_supported_types(&self) -> Vec<ContentType> {
-        vec![ContentType::Anime]
-    }
-
-    fn get_supported_languages(&self) -> Vec<String> {
-        vec!["uk".into()]
-    }
-
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        if page > 1 {
-            return Ok(vec![]);
-        }
-
-        utils::scrap_page(
-            datalife::search_request(URL, query),
-            &self.processor_content_info_items,
-        )
-        .await
-    }
-
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        let url = datalife::get_channel_url(&self.channels_map, channel, page)?;
-
-        utils::scrap_page(
-            utils::create_client().get(&url),
-            &self.processor_content_info_items,
-        )
-        .await
-    }
-
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
-        let url = datalife::format_id_from_url(URL, id);
-
-        utils::scrap_page(
-            utils::create_client().get(&url),
-            &self.processor_content_details,
-        )
-        .await
-    }
-
-    async fn load_media_items(
-        &self,
-        _id: &str,
-        params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
-        if !params.is_empty() {
-            playerjs::load_and_parse_playerjs(
-                utils::create_client().get(&params[0]),
-                playerjs::convert_strategy_dub_season_ep,
-            )
-            .await
-        } else {
-            Err(anyhow!("iframe url expected"))
-        }
-    }
-
-    async fn load_media_item_sources(
-        &self,
-        _id: &str,
-        _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        Err(anyhow!("unimplemented"))
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::suppliers::ContentSupplier;
 
     #[tokio::test]
     async fn should_load_channel() {
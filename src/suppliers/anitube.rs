@@ -1,50 +1,51 @@
 use indexmap::IndexMap;
-use regex::Regex;
 
 use crate::{
-    models::{
-        ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
-        MediaType,
-    },
+    models::{ContentType, MediaType},
     utils::{
         self, datalife,
         html::{self, DOMProcessor},
-        playerjs,
     },
 };
 
-use super::ContentSupplier;
-
-use anyhow::anyhow;
+use super::datalife::{DataLifeConfig, DataLifeSite, DataLifeSupplier, Playlist, SearchPaging};
 
 const URL: &str = "https://anitube.in.ua";
 
-pub struct AniTubeContentSupplier {
-    channels_map: IndexMap<&'static str, String>,
-    processor_content_info_items: html::ItemsProcessor<ContentInfo>,
-    processor_content_details: html::ScopeProcessor<ContentDetails>,
-    regexp_dle_hash: Regex,
-}
+pub type AniTubeContentSupplier = DataLifeSupplier<AniTube>;
+
+#[derive(Default)]
+pub struct AniTube;
 
-impl Default for AniTubeContentSupplier {
-    fn default() -> Self {
-        Self {
+impl DataLifeSite for AniTube {
+    fn config(&self) -> DataLifeConfig {
+        DataLifeConfig {
+            url: URL,
+            display_name: "AniTube",
+            mirrors: vec![],
+            supported_types: vec![ContentType::Anime],
+            supported_languages: vec!["uk"],
             channels_map: IndexMap::from([("Новинки", format!("{URL}/anime/page/"))]),
-            processor_content_info_items: html::ItemsProcessor::new(
-                "article.story",
-                html::ContentInfoProcessor {
-                    id: html::AttrValue::new("href")
-                        .map_optional(|s| datalife::extract_id_from_url(URL, s))
-                        .in_scope_flatten(".story_c > h2 > a")
-                        .unwrap_or_default()
-                        .boxed(),
-                    title: html::text_value(".story_c > h2 > a"),
-                    secondary_title: html::default_value(),
-                    image: html::self_hosted_image(URL, ".story_c_l img", "data-src"),
-                }
-                .boxed(),
+            search_paging: SearchPaging::Param("search_start"),
+            content_info_items: html::required_scope(
+                "#dle-content",
+                html::items_processor(
+                    "article.story",
+                    html::ContentInfoProcessor {
+                        id: html::AttrValue::new("href")
+                            .map_optional(|s| datalife::extract_id_from_url(URL, s))
+                            .in_scope_flatten(".story_c > h2 > a")
+                            .unwrap_or_default()
+                            .required()
+                            .boxed(),
+                        title: html::required(html::text_value(".story_c > h2 > a")),
+                        secondary_title: html::default_value(),
+                        image: html::self_hosted_image(URL, ".story_c_l img", "data-src"),
+                    }
+                    .boxed(),
+                ),
             ),
-            processor_content_details: html::ScopeProcessor::new(
+            content_details: html::ScopeProcessor::new(
                 "div.content",
                 html::ContentDetailsProcessor {
                     media_type: MediaType::Video,
@@ -52,6 +53,7 @@ impl Default for AniTubeContentSupplier {
                         .map(|s| utils::text::sanitize_text(&s))
                         .in_scope(".story_c > .rcol > h2")
                         .unwrap_or_default()
+                        .required()
                         .boxed(),
                     original_title: html::default_value(),
                     image: html::self_hosted_image(URL, ".story_c .story_post img", "src"),
@@ -102,131 +104,12 @@ impl Default for AniTubeContentSupplier {
                     params: html::default_value(),
                 }
                 .boxed(),
-            ),
-            regexp_dle_hash: Regex::new(r#"dle_login_hash\s+=\s+'(?<hash>[a-z0-9]+)'"#).unwrap(),
-        }
-    }
-}
-
-impl ContentSupplier for AniTubeContentSupplier {
-    fn get_channels(&self) -> Vec<String> {
-        self.channels_map.keys().map(|&s| s.into()).collect()
-    }
-
-    fn get_default_channels(&self) -> Vec<String> {
-        vec![]
-    }
-
-    fn get_supported_types(&self) -> Vec<ContentType> {
-        vec![ContentType::Anime]
-    }
-
-    fn get_supported_languages(&self) -> Vec<String> {
-        vec!["uk".into()]
-    }
-
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        utils::scrap_page(
-            datalife::search_request(URL, query).query(&[("search_start", page.to_string())]),
-            &self.processor_content_info_items,
-        )
-        .await
-    }
-
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        let url = datalife::get_channel_url(&self.channels_map, channel, page)?;
-
-        utils::scrap_page(
-            utils::create_client().get(&url),
-            &self.processor_content_info_items,
-        )
-        .await
-    }
-
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
-        let url = datalife::format_id_from_url(URL, id);
-
-        let html = utils::create_client()
-            .get(&url)
-            .send()
-            .await?
-            .text()
-            .await?;
-
-        let document = scraper::Html::parse_document(&html);
-        let root = document.root_element();
-
-        let mut maybe_details = self.processor_content_details.process(&root);
-
-        if let Some(&mut ref mut details) = maybe_details.as_mut() {
-            details.params = self.extract_params(&html).unwrap_or_default()
-        }
-
-        Ok(maybe_details)
-    }
-
-    async fn load_media_items(
-        &self,
-        id: &str,
-        params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
-        if params.len() != 1 {
-            return Err(anyhow!("user hash expected"));
-        }
-
-        let news_id = id
-            .split_once("-")
-            .map(|(l, _)| l)
-            .ok_or_else(|| anyhow!("unable to extract news_id"))?;
-
-        let playlist_req = utils::create_client()
-            .get(format!("{URL}/engine/ajax/playlists.php"))
-            .query(&[
-                ("xfield", "playlist"),
-                ("news_id", news_id),
-                ("user_hash", &params[0]),
-            ])
-            .header("Referer", URL);
-
-        datalife::load_ajax_playlist(playlist_req).await
-    }
-
-    async fn load_media_item_sources(
-        &self,
-        _id: &str,
-        params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        if !params.len().is_multiple_of(2) {
-            return Err(anyhow!("Wrong params size"));
-        }
-
-        let mut results = vec![];
-        for chunk in params.chunks(2) {
-            let description = &chunk[0];
-            let url = &chunk[1];
-
-            let mut sources = playerjs::load_and_parse_playerjs_sources(
-                utils::create_client().get(url),
-                description,
             )
-            .await
-            .unwrap_or_default();
-            results.append(&mut sources);
+            .required()
+            .boxed(),
+            playlist: Some(Playlist::Ajax),
+            login_hash: true,
         }
-
-        Ok(results)
-    }
-}
-
-impl AniTubeContentSupplier {
-    fn extract_params(&self, html: &str) -> Option<Vec<String>> {
-        let hash = self
-            .regexp_dle_hash
-            .captures(html)
-            .and_then(|c| c.name("hash"))
-            .map(|m| m.as_str())?;
-
-        Some(vec![hash.into()])
     }
 }
 
@@ -240,6 +123,8 @@ fn extract_image(src: String) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::suppliers::ContentSupplier;
+
     #[tokio::test]
     async fn should_load_channel() {
         let res = AniTubeContentSupplier::default()
@@ -1,5 +1,6 @@
-use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::{
     models::{
         ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
@@ -8,6 +9,7 @@ use crate::{
     utils::{
         self, create_client,
         html::{self, DOMProcessor},
+        params::{self, LegacyParams, MediaParams},
     },
 };
 
@@ -20,7 +22,7 @@ pub struct AnizoneContentSupplier {
     selector_tracks: scraper::Selector,
     processor_content_info_items: html::ItemsProcessor<ContentInfo>,
     processor_channel_info_items: html::ItemsProcessor<ContentInfo>,
-    processor_content_details: html::ScopeProcessor<ContentDetails>,
+    processor_content_details: html::Required<Option<ContentDetails>>,
 }
 
 impl Default for AnizoneContentSupplier {
@@ -31,10 +33,14 @@ impl Default for AnizoneContentSupplier {
             processor_content_info_items: html::ItemsProcessor::new(
                 "main > div > div > div.grid > div",
                 html::ContentInfoProcessor {
-                    id: html::attr_value_map("div.h-6.inline > a", "href", extract_id_from_url),
-                    title: html::text_value_map("div.h-6.inline > a", |s| {
+                    id: html::required(html::attr_value_map(
+                        "div.h-6.inline > a",
+                        "href",
+                        extract_id_from_url,
+                    )),
+                    title: html::required(html::text_value_map("div.h-6.inline > a", |s| {
                         utils::text::sanitize_text(&s)
-                    }),
+                    })),
                     secondary_title: html::items_processor(
                         "div.h-4 > span",
                         html::TextValue::new().boxed(),
@@ -48,8 +54,8 @@ impl Default for AnizoneContentSupplier {
             processor_channel_info_items: html::ItemsProcessor::new(
                 ".swiper-wrapper.flex .swiper-slide",
                 html::ContentInfoProcessor {
-                    id: html::attr_value_map("a", "href", extract_id_from_url),
-                    title: html::text_value(".line-clamp-2 > a"),
+                    id: html::required(html::attr_value_map("a", "href", extract_id_from_url)),
+                    title: html::required(html::text_value(".line-clamp-2 > a")),
                     secondary_title: html::default_value(),
                     image: html::attr_value("img", "src"),
                 }
@@ -59,7 +65,11 @@ impl Default for AnizoneContentSupplier {
                 "main",
                 html::ContentDetailsProcessor {
                     media_type: MediaType::Video,
-                    title: html::attr_value_map("[x-data]", "x-data", extract_title_from_xdata),
+                    title: html::required(html::attr_value_map(
+                        "[x-data]",
+                        "x-data",
+                        extract_title_from_xdata,
+                    )),
                     original_title: html::default_value(),
                     image: html::attr_value("div.mx-auto img", "src"),
                     description: html::text_value_map("div.text-slate-100 div", |s| {
@@ -73,7 +83,8 @@ impl Default for AnizoneContentSupplier {
                     params: html::default_value(),
                 }
                 .boxed(),
-            ),
+            )
+            .required(),
         }
     }
 }
@@ -95,7 +106,23 @@ impl ContentSupplier for AnizoneContentSupplier {
         vec!["en".to_string(), "ja".to_string()]
     }
 
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    fn get_display_name(&self) -> String {
+        "AniZone".into()
+    }
+
+    fn get_home_url(&self) -> String {
+        SITE_URL.into()
+    }
+
+    fn supports_search_paging(&self) -> bool {
+        false
+    }
+
+    fn supports_channel_paging(&self) -> bool {
+        false
+    }
+
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         if page > 1 {
             return Ok(vec![]);
         }
@@ -109,7 +136,7 @@ impl ContentSupplier for AnizoneContentSupplier {
         .await
     }
 
-    async fn load_channel(&self, _channel: &str, _page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    async fn load_channel(&self, _channel: &str, _page: u16) -> SupplierResult<Vec<ContentInfo>> {
         utils::scrap_page(
             utils::create_client().get(SITE_URL),
             &self.processor_channel_info_items,
@@ -117,7 +144,7 @@ impl ContentSupplier for AnizoneContentSupplier {
         .await
     }
 
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>> {
         utils::scrap_page(
             utils::create_client().get(format!("{SITE_URL}/anime/{id}")),
             &self.processor_content_details,
@@ -129,10 +156,10 @@ impl ContentSupplier for AnizoneContentSupplier {
         &self,
         id: &str,
         _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
         let url = format!("{SITE_URL}/anime/{id}/1");
 
-        let page_content = create_client().get(url).send().await?.text().await?;
+        let page_content = utils::fetch_text(create_client().get(url)).await?;
 
         let document = scraper::Html::parse_document(&page_content);
         let selector = scraper::Selector::parse("main div.order-2 div a").unwrap();
@@ -149,7 +176,9 @@ impl ContentSupplier for AnizoneContentSupplier {
                     section: None,
                     sources: None,
                     image: None,
-                    params: vec![ep_num.to_string()],
+                    params: params::encode(&EpisodeParams {
+                        ep_num: ep_num as u32,
+                    }),
                 }
             })
             .collect();
@@ -161,12 +190,8 @@ impl ContentSupplier for AnizoneContentSupplier {
         &self,
         id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        if params.len() != 1 {
-            return Err(anyhow!("expected ep_num in params"));
-        }
-
-        let ep_num = &params[0];
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        let EpisodeParams { ep_num } = params::decode(&params)?;
 
         let anime_page_res = self.load_anime_page(id, ep_num).await?;
         let mut results: Vec<ContentMediaItemSource> = vec![];
@@ -190,6 +215,35 @@ impl ContentSupplier for AnizoneContentSupplier {
     }
 }
 
+#[derive(Deserialize, Serialize)]
+struct EpisodeParams {
+    ep_num: u32,
+}
+
+impl MediaParams for EpisodeParams {
+    const KIND: &'static str = "anizone_episode";
+    const VERSION: u32 = 2;
+
+    // v1 is bare [ep_num]
+    fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+        match legacy {
+            LegacyParams::Raw(raw) => Ok(Self {
+                ep_num: params::required(raw, 0, "ep_num")?.parse()?,
+            }),
+            LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(format!(
+                "anizone episode params version {version} is not supported"
+            ))),
+        }
+    }
+
+    fn validate(&self) -> SupplierResult<()> {
+        if self.ep_num == 0 {
+            return Err(SupplierError::invalid_params("ep_num starts from 1"));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 struct Subtitle {
     src: String,
@@ -203,21 +257,25 @@ struct AnimePageResult {
 }
 
 impl AnizoneContentSupplier {
-    async fn load_anime_page(&self, id: &str, ep_num: &str) -> anyhow::Result<AnimePageResult> {
+    async fn load_anime_page(&self, id: &str, ep_num: u32) -> SupplierResult<AnimePageResult> {
         let url = format!("{SITE_URL}/anime/{id}/{ep_num}");
 
-        let page_content = create_client().get(url).send().await?.text().await?;
+        let page_content = utils::fetch_text(create_client().get(url)).await?;
 
         let document = scraper::Html::parse_document(&page_content);
 
         let player_el = document
             .select(&self.selector_player)
             .next()
-            .ok_or_else(|| anyhow!("player not found for anime {} ep_num {}", id, ep_num))?;
-
-        let hls_src = player_el
-            .attr("src")
-            .ok_or_else(|| anyhow!("player src not found for anime {} ep_num {}", id, ep_num))?;
+            .ok_or_else(|| {
+                SupplierError::parse(format!("player not found for anime {id} ep_num {ep_num}"))
+            })?;
+
+        let hls_src = player_el.attr("src").ok_or_else(|| {
+            SupplierError::parse(format!(
+                "player src not found for anime {id} ep_num {ep_num}"
+            ))
+        })?;
 
         let subtitles: Vec<_> = player_el
             .select(&self.selector_tracks)
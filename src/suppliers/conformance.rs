@@ -0,0 +1,658 @@
+//! Trait contract checks shared by all suppliers.
+//!
+//! Each supplier with recorded fixtures gets its own `conformance` and `golden_pages` tests
+//! (`recorded_tests!`), both run inside `golden::run`, so the suite works offline
+//! against recorded fixtures and against live sites with `REFRESH_FIXTURES=1`.
+
+use std::{future::Future, panic::AssertUnwindSafe};
+
+use futures::FutureExt;
+
+use crate::errors::SupplierResult;
+use crate::models::{ContentMediaItemSource, ContentType, MediaType};
+
+use super::{AllContentSuppliers, AllMangaPagesLoaders, ContentSupplier, MangaPagesLoader};
+
+/// Inputs covered by supplier fixtures
+pub struct Case {
+    /// channel from `get_channels` which first page is recorded
+    pub channel: &'static str,
+    /// query with recorded first page
+    pub query: &'static str,
+    /// id listed by channel or search which details are recorded
+    pub id: &'static str,
+}
+
+#[derive(Default)]
+struct Violations(Vec<String>);
+
+impl Violations {
+    fn check(&mut self, ok: bool, msg: impl Into<String>) {
+        if !ok {
+            self.0.push(msg.into());
+        }
+    }
+
+    /// Run call and turn panic into violation
+    async fn call<T>(
+        &mut self,
+        what: &str,
+        fut: impl Future<Output = SupplierResult<T>>,
+    ) -> Option<SupplierResult<T>> {
+        match AssertUnwindSafe(fut).catch_unwind().await {
+            Ok(res) => Some(res),
+            Err(_) => {
+                self.0
+                    .push(format!("{what}: panicked instead of returning error"));
+                None
+            }
+        }
+    }
+
+    /// Run call which has to succeed
+    async fn ok<T>(
+        &mut self,
+        what: &str,
+        fut: impl Future<Output = SupplierResult<T>>,
+    ) -> Option<T> {
+        match self.call(what, fut).await? {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.0.push(format!("{what}: {err}"));
+                None
+            }
+        }
+    }
+
+    fn finish(self, name: &str) {
+        if !self.0.is_empty() {
+            panic!(
+                "{name} violates supplier contract:\n  {}",
+                self.0.join("\n  ")
+            );
+        }
+    }
+}
+
+pub async fn check(supplier: AllContentSuppliers, case: Case) {
+    let mut v = Violations::default();
+    let name = supplier.get_display_name();
+
+    // metadata
+    let channels = supplier.get_channels();
+    v.check(!name.is_empty(), "display name is empty");
+    v.check(!channels.is_empty(), "no channels");
+    v.check(
+        channels.iter().any(|c| c == case.channel),
+        format!("channel {:?} is not listed by get_channels", case.channel),
+    );
+    for channel in supplier.get_default_channels() {
+        v.check(
+            channels.contains(&channel),
+            format!("default channel {channel:?} is not listed by get_channels"),
+        );
+    }
+    v.check(
+        !supplier.get_supported_types().is_empty(),
+        "no supported types",
+    );
+    v.check(
+        !supplier.get_supported_languages().is_empty(),
+        "no supported languages",
+    );
+    v.check(
+        supplier.get_home_url().starts_with("https://"),
+        "home url is not https",
+    );
+
+    // channels
+    let channel_items = v
+        .ok(
+            "load_channel page 1",
+            supplier.load_channel(case.channel, 1),
+        )
+        .await
+        .unwrap_or_default();
+    v.check(!channel_items.is_empty(), "channel page 1 is empty");
+
+    if let Some(res) = v
+        .call(
+            "load_channel unknown",
+            supplier.load_channel("__unknown__", 1),
+        )
+        .await
+    {
+        v.check(res.is_err(), "unknown channel is not an error");
+    }
+    v.call(
+        "load_channel page 0",
+        supplier.load_channel(case.channel, 0),
+    )
+    .await;
+
+    // search
+    let search_items = v
+        .ok("search page 1", supplier.search(case.query, 1))
+        .await
+        .unwrap_or_default();
+    v.check(!search_items.is_empty(), "search page 1 is empty");
+    v.call("search empty query", supplier.search("", 1)).await;
+
+    for item in channel_items.iter().chain(&search_items) {
+        v.check(
+            !item.id.is_empty() && !item.title.is_empty(),
+            format!("listed item without id or title: {item:?}"),
+        );
+    }
+
+    // details
+    v.check(
+        channel_items
+            .iter()
+            .chain(&search_items)
+            .any(|item| item.id == case.id),
+        format!("id {:?} is not listed by channel or search", case.id),
+    );
+
+    let is_manga = supplier.get_supported_types().contains(&ContentType::Manga);
+    let pages_loader = AllMangaPagesLoaders::try_from(&supplier).ok();
+    v.check(
+        is_manga == pages_loader.is_some(),
+        "manga suppliers and only them have to implement MangaPagesLoader",
+    );
+
+    v.call(
+        "get_content_details unknown",
+        supplier.get_content_details("__unknown__"),
+    )
+    .await;
+
+    let Some(details) = v
+        .ok("get_content_details", supplier.get_content_details(case.id))
+        .await
+    else {
+        return v.finish(&name);
+    };
+
+    let Some(details) = details else {
+        v.0.push(format!("id {:?} does not round-trip to details", case.id));
+        return v.finish(&name);
+    };
+
+    v.check(!details.title.is_empty(), "details title is empty");
+    v.check(
+        is_manga == matches!(details.media_type, MediaType::Manga),
+        format!(
+            "details media type {:?} does not match supported types",
+            details.media_type
+        ),
+    );
+
+    // media items
+    let media_items = match details.media_items {
+        Some(media_items) => media_items,
+        None => v
+            .ok(
+                "load_media_items",
+                supplier.load_media_items(case.id, details.params),
+            )
+            .await
+            .unwrap_or_default(),
+    };
+    v.check(!media_items.is_empty(), "no media items");
+
+    if let Some(pages_loader) = pages_loader {
+        let chapter_params = media_items
+            .iter()
+            .flat_map(|item| item.sources.iter().flatten())
+            .find_map(|source| match source {
+                ContentMediaItemSource::Manga { params, .. } => Some(params.clone()),
+                _ => None,
+            });
+
+        match chapter_params {
+            Some(params) => {
+                let pages = v
+                    .ok("load_pages", pages_loader.load_pages(case.id, params))
+                    .await
+                    .unwrap_or_default();
+                v.check(!pages.is_empty(), "chapter has no pages");
+            }
+            None => v.0.push("manga media items have no manga sources".into()),
+        }
+    }
+
+    v.finish(&name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::suppliers::{
+        animeua::AnimeUAContentSupplier, anitube::AniTubeContentSupplier, declarative,
+        mangadex::MangaDexContentSupplier, mangainua::MangaInUaContentSupplier,
+        tmdb::TMDBContentSupplier, uaflix::UAFlixSupplier, uakinoclub::UAKinoClubContentSupplier,
+        uaserial::UAserialContentSupplier, uaserials_pro::UASerialsProContentSupplier,
+        ufdub::UFDubContentSupplier, weebcentral::WeebCentralContentSupplier,
+    };
+    use crate::utils::{credentials, golden};
+
+    /// Supplier with pages saved in `fixtures/<dir>/`
+    struct Recorded {
+        dir: &'static str,
+        supplier: AllContentSuppliers,
+        case: Case,
+        fixtures: Vec<golden::Fixture>,
+    }
+
+    fn animeua() -> Recorded {
+        Recorded {
+            dir: "animeua",
+            supplier: AnimeUAContentSupplier::default().into(),
+            case: Case {
+                channel: "Новинки",
+                query: "Доктор Стоун",
+                id: "7633-dr-stone-4",
+            },
+            fixtures: vec![
+                golden::get("channel.html", "https://animeua.club/page/1"),
+                golden::post("search.html", "https://animeua.club/index.php"),
+                golden::get("details.html", "https://animeua.club/7633-dr-stone-4.html"),
+                golden::get("playlist.html", "https://ashdi.vip/serial/971?season=4"),
+            ],
+        }
+    }
+
+    fn anitube() -> Recorded {
+        Recorded {
+            dir: "anitube",
+            supplier: AniTubeContentSupplier::default().into(),
+            case: Case {
+                channel: "Новинки",
+                query: "dragon ball",
+                id: "31-zapisnik-smert",
+            },
+            fixtures: vec![
+                golden::get("channel.html", "https://anitube.in.ua/anime/page/1"),
+                golden::post(
+                    "search.html",
+                    "https://anitube.in.ua/index.php?search_start=1",
+                ),
+                golden::get(
+                    "details.html",
+                    "https://anitube.in.ua/31-zapisnik-smert.html",
+                ),
+                golden::get(
+                    "playlist.json",
+                    "https://anitube.in.ua/engine/ajax/playlists.php?xfield=playlist&news_id=31&user_hash=867ca5be02de10b799c164d7b7c31e6eece1bb10",
+                ),
+            ],
+        }
+    }
+
+    fn mangadex() -> Recorded {
+        let api = "https://api.mangadex.org";
+        let ratings = "contentRating%5B%5D=safe&contentRating%5B%5D=suggestive\
+            &contentRating%5B%5D=erotica&contentRating%5B%5D=pornographic";
+        let id = "0f7295a6-eaf5-470b-a003-b7789a9a0f4a";
+
+        Recorded {
+            dir: "mangadex",
+            supplier: MangaDexContentSupplier::default().into(),
+            case: Case {
+                channel: "Popular Titles",
+                query: "idaten deities",
+                id,
+            },
+            fixtures: vec![
+                golden::get(
+                    "channel.json",
+                    format!(
+                        "{api}/manga?order%5BfollowedCount%5D=desc&includes%5B%5D=cover_art&hasAvailableChapters=true&limit=20&offset=0&{ratings}"
+                    ),
+                ),
+                golden::get(
+                    "search.json",
+                    format!(
+                        "{api}/manga?title=idaten+deities&includes%5B%5D=cover_art&hasAvailableChapters=true&{ratings}&limit=20&offset=0"
+                    ),
+                ),
+                golden::get(
+                    "details.json",
+                    format!(
+                        "{api}/manga/{id}?includes%5B%5D=cover_art&includes%5B%5D=author&{ratings}"
+                    ),
+                ),
+                golden::get(
+                    "chapters.json",
+                    format!(
+                        "{api}/manga/{id}/feed?includes%5B%5D=scanlation_group&order%5Bvolume%5D=asc&order%5Bchapter%5D=asc&offset=0&limit=500&{ratings}"
+                    ),
+                ),
+                golden::get(
+                    "pages.json",
+                    format!("{api}/at-home/server/1fe13d15-982f-402b-8120-91f717f886b8"),
+                ),
+            ],
+        }
+    }
+
+    fn mangainua() -> Recorded {
+        Recorded {
+            dir: "mangainua",
+            supplier: MangaInUaContentSupplier::default().into(),
+            case: Case {
+                channel: "Бойовик",
+                query: "solo leveling",
+                id: "mangas/boyovik/14196-hunter-x-hunter",
+            },
+            fixtures: vec![
+                golden::get("channel.html", "https://manga.in.ua/mangas/boyovik/"),
+                golden::post("search.html", "https://manga.in.ua/index.php"),
+                golden::get(
+                    "details.html",
+                    "https://manga.in.ua/mangas/boyovik/14196-hunter-x-hunter.html",
+                ),
+                golden::post(
+                    "chapters.html",
+                    "https://manga.in.ua/engine/ajax/controller.php?mod=load_chapters",
+                ),
+                golden::get(
+                    "pages.html",
+                    "https://manga.in.ua/engine/ajax/controller.php?mod=load_chapters_image&news_id=14813&user_hash=772f84a2554710856146eb1863c483d705b01412&action=show",
+                ),
+            ],
+        }
+    }
+
+    fn tmdb() -> Recorded {
+        // requests are not sent without api key, recorded responses don't depend on it
+        credentials::set("TMDB", "api_key", "conformance");
+
+        let api = "https://api.themoviedb.org/3";
+        Recorded {
+            dir: "tmdb",
+            supplier: TMDBContentSupplier::default().into(),
+            case: Case {
+                channel: "Popular Movies",
+                query: "terminator",
+                id: "movie/218",
+            },
+            fixtures: vec![
+                golden::get(
+                    "channel.json",
+                    format!("{api}/movie/popular?page=1&language=en-US"),
+                ),
+                golden::get(
+                    "search.json",
+                    format!("{api}/search/multi?query=terminator&language=en-US"),
+                ),
+                golden::get(
+                    "details.json",
+                    format!(
+                        "{api}/movie/218?append_to_response=external_ids%2Ccredits%2Crecommendations&language=en-US"
+                    ),
+                ),
+            ],
+        }
+    }
+
+    fn uaflix() -> Recorded {
+        Recorded {
+            dir: "uaflix",
+            supplier: UAFlixSupplier::default().into(),
+            case: Case {
+                channel: "Аніме",
+                query: "naruto",
+                id: "serials/naruto-naruto",
+            },
+            fixtures: vec![
+                golden::get("channel.html", "https://uafix.net/anime/page/1"),
+                golden::get(
+                    "search.html",
+                    "https://uafix.net/search.html?do=search&subaction=search&story=naruto&search_start=1",
+                ),
+                golden::get("details.html", "https://uafix.net/serials/naruto-naruto/"),
+                golden::get("playlist.html", "https://ashdi.vip/serial/236"),
+            ],
+        }
+    }
+
+    fn uakinoclub_case() -> Case {
+        Case {
+            channel: "Новинки",
+            query: "білий лотос",
+            id: "seriesss/drama_series/24161-bilyy-lotos-3-sezon",
+        }
+    }
+
+    fn uakinoclub_fixtures() -> Vec<golden::Fixture> {
+        vec![
+            golden::get("channel.html", "https://uakino.best/page/1"),
+            golden::post("search.html", "https://uakino.best/index.php?from_page=1"),
+            golden::get(
+                "details.html",
+                "https://uakino.best/seriesss/drama_series/24161-bilyy-lotos-3-sezon.html",
+            ),
+            golden::get(
+                "playlist.json",
+                "https://uakino.best/engine/ajax/playlists.php?xfield=playlist&news_id=24161&time=0",
+            ),
+        ]
+    }
+
+    fn uakinoclub() -> Recorded {
+        Recorded {
+            dir: "uakinoclub",
+            supplier: UAKinoClubContentSupplier::default().into(),
+            case: uakinoclub_case(),
+            fixtures: uakinoclub_fixtures(),
+        }
+    }
+
+    // declarative definition has to match built in supplier page for page
+    fn uakinoclub_declarative() -> Recorded {
+        Recorded {
+            dir: "uakinoclub",
+            supplier: declarative::parse(include_str!(
+                "../../fixtures/declarative/uakinoclub.toml"
+            ))
+            .unwrap()
+            .into(),
+            case: uakinoclub_case(),
+            fixtures: uakinoclub_fixtures(),
+        }
+    }
+
+    fn uaserial() -> Recorded {
+        Recorded {
+            dir: "uaserial",
+            supplier: UAserialContentSupplier::default().into(),
+            case: Case {
+                channel: "Фільми",
+                query: "terminator",
+                id: "terminator-zero/season-1",
+            },
+            fixtures: vec![
+                golden::get("channel.html", "https://uaserial.biz/movie/1"),
+                golden::get(
+                    "search.html",
+                    "https://uaserial.biz/search?query=terminator",
+                ),
+                golden::get(
+                    "details.html",
+                    "https://uaserial.biz/terminator-zero/season-1",
+                ),
+                golden::get("playlist.html", "https://hdvbua.pro/embed/8873"),
+            ],
+        }
+    }
+
+    fn uaserials_pro() -> Recorded {
+        Recorded {
+            dir: "uaserials_pro",
+            supplier: UASerialsProContentSupplier::default().into(),
+            case: Case {
+                channel: "Серіали",
+                query: "гра в кальмара",
+                id: "10963-gra-v-kalmara",
+            },
+            fixtures: vec![
+                golden::get("channel.html", "https://uaserials.my/series/page/1"),
+                golden::post(
+                    "search.html",
+                    "https://uaserials.my/index.php?search_start=1",
+                ),
+                golden::get(
+                    "details.html",
+                    "https://uaserials.my/10963-gra-v-kalmara.html",
+                ),
+                golden::get("playlist.html", "https://hdvbua.pro/embed/10963"),
+            ],
+        }
+    }
+
+    fn ufdub() -> Recorded {
+        Recorded {
+            dir: "ufdub",
+            supplier: UFDubContentSupplier::default().into(),
+            case: Case {
+                channel: "Аніме",
+                query: "Магічна битва",
+                id: "anime/412-mahichna-bytva",
+            },
+            fixtures: vec![
+                golden::get("channel.html", "https://ufdub.com/anime/page/1"),
+                golden::post("search.html", "https://ufdub.com/index.php"),
+                golden::get(
+                    "details.html",
+                    "https://ufdub.com/anime/412-mahichna-bytva.html",
+                ),
+                golden::get("playlist.html", "https://ufdub.com/video/player.php?id=412"),
+            ],
+        }
+    }
+
+    fn weebcentral() -> Recorded {
+        Recorded {
+            dir: "weebcentral",
+            supplier: WeebCentralContentSupplier::default().into(),
+            case: Case {
+                channel: "Popular",
+                query: "fairy",
+                id: "01J76XY7E5E1C5Y9J0M2FCVQ8H/Fairy-Tail",
+            },
+            fixtures: vec![
+                golden::get(
+                    "channel.html",
+                    "https://weebcentral.com/search/data?sort=Popularity&order=Descending&official=Any&anime=Any&adult=Any&display_mode=Full+Display",
+                ),
+                golden::get(
+                    "search.html",
+                    "https://weebcentral.com/search/data?text=fairy&sort=Best+Match&order=Descending&official=Any&anime=Any&adult=Any&display_mode=Full+Display",
+                ),
+                golden::get(
+                    "details.html",
+                    "https://weebcentral.com/series/01J76XY7E5E1C5Y9J0M2FCVQ8H/Fairy-Tail",
+                ),
+                golden::get(
+                    "chapters.html",
+                    "https://weebcentral.com/series/01J76XY7E5E1C5Y9J0M2FCVQ8H/full-chapter-list",
+                ),
+                golden::get(
+                    "pages.html",
+                    "https://weebcentral.com/chapters/01J76XYY73BP96JM3WRJSTBVMW/images?is_prev=False&reading_style=long_strip",
+                ),
+            ],
+        }
+    }
+
+    /// Parsed pages of case are compared with snapshots next to fixtures
+    async fn snapshot(dir: &str, supplier: &AllContentSuppliers, case: &Case) {
+        let channel = supplier.load_channel(case.channel, 1).await.unwrap();
+        golden::assert_snapshot(dir, "channel", &channel);
+
+        let search = supplier.search(case.query, 1).await.unwrap();
+        golden::assert_snapshot(dir, "search", &search);
+
+        let details = supplier
+            .get_content_details(case.id)
+            .await
+            .unwrap()
+            .unwrap();
+        golden::assert_snapshot(dir, "details", &details);
+
+        let media_items = supplier
+            .load_media_items(case.id, details.params.clone())
+            .await
+            .unwrap();
+
+        let Ok(pages_loader) = AllMangaPagesLoaders::try_from(supplier) else {
+            golden::assert_snapshot(dir, "playlist", &media_items);
+            return;
+        };
+        golden::assert_snapshot(dir, "chapters", &media_items);
+
+        let chapter_params = media_items
+            .iter()
+            .flat_map(|item| item.sources.iter().flatten())
+            .find_map(|source| match source {
+                ContentMediaItemSource::Manga { params, .. } => Some(params.clone()),
+                _ => None,
+            })
+            .unwrap();
+        let pages = pages_loader
+            .load_pages(case.id, chapter_params)
+            .await
+            .unwrap();
+        golden::assert_snapshot(dir, "pages", &pages);
+    }
+
+    /// `golden_pages` and `conformance` tests in module of each recorded supplier
+    macro_rules! recorded_tests {
+        ($($name:ident),* $(,)?) => {
+            $(
+                mod $name {
+                    use super::*;
+
+                    #[tokio::test]
+                    async fn golden_pages() {
+                        let Recorded {
+                            dir,
+                            supplier,
+                            case,
+                            fixtures,
+                        } = super::$name();
+                        golden::run(dir, &fixtures, snapshot(dir, &supplier, &case)).await;
+                    }
+
+                    #[tokio::test]
+                    async fn conformance() {
+                        let Recorded {
+                            dir,
+                            supplier,
+                            case,
+                            fixtures,
+                        } = super::$name();
+                        golden::run(dir, &fixtures, check(supplier, case)).await;
+                    }
+                }
+            )*
+        };
+    }
+
+    recorded_tests!(
+        animeua,
+        anitube,
+        mangadex,
+        mangainua,
+        tmdb,
+        uaflix,
+        uakinoclub,
+        uakinoclub_declarative,
+        uaserial,
+        uaserials_pro,
+        ufdub,
+        weebcentral,
+    );
+}
@@ -0,0 +1,221 @@
+//! Shared `ContentSupplier` of DataLife Engine sites.
+//!
+//! Site describes its pages with [`DataLifeConfig`] and gets search, channels,
+//! details and playlists for free, sites with own player override media items.
+
+use indexmap::IndexMap;
+use regex::Regex;
+use std::sync::OnceLock;
+
+use super::ContentSupplier;
+use crate::errors::{SupplierError, SupplierResult};
+use crate::models::{
+    ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
+};
+use crate::utils::playerjs::PlayerJSFile;
+use crate::utils::{self, datalife, html, params, playerjs};
+
+/// How search results are paged
+pub enum SearchPaging {
+    /// query parameter with page number (`search_start`, `from_page`)
+    Param(&'static str),
+    /// site returns everything on first page
+    FirstPage,
+}
+
+/// Where media items come from
+pub enum Playlist {
+    /// first details param is playerjs iframe url
+    PlayerJS(fn(&Vec<PlayerJSFile>) -> Vec<ContentMediaItem>),
+    /// ajax playlist of news
+    Ajax,
+    /// playerjs iframe when details have one, ajax playlist otherwise
+    PlayerJSOrAjax(fn(&Vec<PlayerJSFile>) -> Vec<ContentMediaItem>),
+}
+
+pub struct DataLifeConfig {
+    pub url: &'static str,
+    pub display_name: &'static str,
+    /// other domains of site, tried in order when `url` is not available
+    pub mirrors: Vec<&'static str>,
+    pub supported_types: Vec<ContentType>,
+    pub supported_languages: Vec<&'static str>,
+    /// channel urls ending with `/page/` get page number appended
+    pub channels_map: IndexMap<&'static str, String>,
+    pub search_paging: SearchPaging,
+    pub content_info_items: Box<dyn html::DOMProcessor<Vec<ContentInfo>>>,
+    pub content_details: Box<dyn html::DOMProcessor<Option<ContentDetails>>>,
+    /// `None` when site overrides [`DataLifeSite::load_media_items`]
+    pub playlist: Option<Playlist>,
+    /// details params are `dle_login_hash` of page, ajax playlist checks it
+    pub login_hash: bool,
+}
+
+/// Site specific part of DataLife Engine supplier
+pub trait DataLifeSite: Default + Sync + Send + 'static {
+    fn config(&self) -> DataLifeConfig;
+
+    async fn load_media_items(
+        &self,
+        config: &DataLifeConfig,
+        id: &str,
+        params: Vec<String>,
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
+        let user_hash = config.login_hash.then(|| params.first()).flatten();
+
+        match &config.playlist {
+            Some(Playlist::PlayerJS(strategy)) => {
+                let iframe = params::required(&params, 0, "iframe url")?;
+                playerjs::load_and_parse_playerjs(utils::create_client().get(iframe), *strategy)
+                    .await
+            }
+            Some(Playlist::PlayerJSOrAjax(strategy)) if !params.is_empty() => {
+                let iframe = params::required(&params, 0, "iframe url")?;
+                playerjs::load_and_parse_playerjs(utils::create_client().get(iframe), *strategy)
+                    .await
+            }
+            Some(Playlist::Ajax | Playlist::PlayerJSOrAjax(_)) => {
+                let request =
+                    datalife::ajax_playlist_request(config.url, id, user_hash.map(|s| s.as_str()))?;
+                datalife::load_ajax_playlist(request).await
+            }
+            None => Err(SupplierError::unsupported("load_media_items")),
+        }
+    }
+
+    async fn load_media_item_sources(
+        &self,
+        config: &DataLifeConfig,
+        _id: &str,
+        params: Vec<String>,
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        match config.playlist {
+            Some(Playlist::Ajax | Playlist::PlayerJSOrAjax(_)) => {
+                datalife::load_playlist_sources(&params).await
+            }
+            _ => Err(SupplierError::unsupported("load_media_item_sources")),
+        }
+    }
+}
+
+pub struct DataLifeSupplier<S> {
+    pub site: S,
+    pub config: DataLifeConfig,
+}
+
+impl<S: DataLifeSite> Default for DataLifeSupplier<S> {
+    fn default() -> Self {
+        let site = S::default();
+        let config = site.config();
+        Self { site, config }
+    }
+}
+
+impl<S: DataLifeSite> ContentSupplier for DataLifeSupplier<S> {
+    fn get_channels(&self) -> Vec<String> {
+        self.config.channels_map.keys().map(|&s| s.into()).collect()
+    }
+
+    fn get_default_channels(&self) -> Vec<String> {
+        vec![]
+    }
+
+    fn get_supported_types(&self) -> Vec<ContentType> {
+        self.config.supported_types.clone()
+    }
+
+    fn get_supported_languages(&self) -> Vec<String> {
+        self.config
+            .supported_languages
+            .iter()
+            .map(|&s| s.into())
+            .collect()
+    }
+
+    fn get_display_name(&self) -> String {
+        self.config.display_name.into()
+    }
+
+    fn get_home_url(&self) -> String {
+        self.config.url.into()
+    }
+
+    fn get_mirrors(&self) -> Vec<String> {
+        self.config.mirrors.iter().map(|&s| s.into()).collect()
+    }
+
+    fn supports_search_paging(&self) -> bool {
+        matches!(self.config.search_paging, SearchPaging::Param(_))
+    }
+
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
+        let request = datalife::search_request(self.config.url, query);
+        let request = match self.config.search_paging {
+            SearchPaging::Param(param) => request.query(&[(param, page.to_string())]),
+            SearchPaging::FirstPage if page > 1 => return Ok(vec![]),
+            SearchPaging::FirstPage => request,
+        };
+
+        utils::scrap_page(request, &self.config.content_info_items).await
+    }
+
+    async fn load_channel(&self, channel: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
+        let url = datalife::get_channel_url(&self.config.channels_map, channel, page)?;
+
+        utils::scrap_page(
+            utils::create_client().get(&url),
+            &self.config.content_info_items,
+        )
+        .await
+    }
+
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>> {
+        let url = datalife::format_id_from_url(self.config.url, id);
+        let request = utils::create_client().get(&url);
+
+        if !self.config.login_hash {
+            return utils::scrap_page(request, &self.config.content_details).await;
+        }
+
+        let html = utils::fetch_text(request).await?;
+
+        let document = scraper::Html::parse_document(&html);
+        let root = document.root_element();
+
+        let mut maybe_details = html::process_checked(&self.config.content_details, &root)?;
+
+        if let Some(details) = maybe_details.as_mut() {
+            details.params = extract_login_hash(&html).into_iter().collect();
+        }
+
+        Ok(maybe_details)
+    }
+
+    async fn load_media_items(
+        &self,
+        id: &str,
+        params: Vec<String>,
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
+        self.site.load_media_items(&self.config, id, params).await
+    }
+
+    async fn load_media_item_sources(
+        &self,
+        id: &str,
+        params: Vec<String>,
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        self.site
+            .load_media_item_sources(&self.config, id, params)
+            .await
+    }
+}
+
+pub(crate) fn extract_login_hash(html: &str) -> Option<String> {
+    static DLE_HASH_REGEXP: OnceLock<Regex> = OnceLock::new();
+    let re = DLE_HASH_REGEXP
+        .get_or_init(|| Regex::new(r#"dle_login_hash\s+=\s+'(?<hash>[a-z0-9]+)'"#).unwrap());
+
+    re.captures(html)
+        .and_then(|c| c.name("hash"))
+        .map(|m| m.as_str().to_owned())
+}
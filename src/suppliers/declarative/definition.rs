@@ -0,0 +1,431 @@
+use std::sync::Arc;
+
+use regex::Regex;
+use scraper::Selector;
+use serde::Deserialize;
+
+use crate::errors::{SupplierError, SupplierResult};
+use crate::models::{ContentDetails, ContentInfo, ContentType, MediaType};
+use crate::utils::{
+    datalife,
+    html::{self, DOMProcessor, ItrDOMProcessor},
+    text,
+};
+
+/// Supplier described by selectors instead of rust code, loaded from json or toml
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SupplierDefinition {
+    /// registry key, has to differ from compiled suppliers
+    pub name: String,
+    pub display_name: String,
+    /// site root without trailing slash, available as `{url}` in other urls
+    pub url: String,
+    /// other domains of site, tried in order when `url` is not available
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+    pub supported_types: Vec<String>,
+    pub languages: Vec<String>,
+    /// channel urls ending with `/page/` get page number appended
+    pub channels: Vec<ChannelDef>,
+    #[serde(default)]
+    pub default_channels: Vec<String>,
+    pub search: SearchDef,
+    /// items of channel and search pages
+    pub list: ContentInfoDef,
+    pub details: DetailsDef,
+    pub media_items: MediaItemsDef,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelDef {
+    pub name: String,
+    pub url: String,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum SearchDef {
+    /// datalife `index.php` search form
+    Datalife {
+        /// query parameter with page number, search is not paged without it
+        #[serde(default)]
+        page_param: Option<String>,
+    },
+    /// plain get request, `{query}` and `{page}` are replaced in url
+    Get { url: String },
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ContentInfoDef {
+    pub scope: String,
+    pub id: ValueSpec,
+    pub title: ValueSpec,
+    #[serde(default)]
+    pub secondary_title: Option<ValueSpec>,
+    #[serde(default)]
+    pub image: Option<ValueSpec>,
+    /// regexes of ids to drop (news, franchises, ads...)
+    #[serde(default)]
+    pub exclude_ids: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DetailsDef {
+    /// page url, `{url}` and `{id}` are replaced
+    #[serde(default = "default_details_url")]
+    pub url: String,
+    pub scope: String,
+    pub title: ValueSpec,
+    #[serde(default)]
+    pub original_title: Option<ValueSpec>,
+    #[serde(default)]
+    pub image: Option<ValueSpec>,
+    #[serde(default)]
+    pub description: Option<ValueSpec>,
+    #[serde(default)]
+    pub additional_info: Vec<ListDef>,
+    #[serde(default)]
+    pub similar: Option<ContentInfoDef>,
+    #[serde(default)]
+    pub params: Vec<ValueSpec>,
+}
+
+fn default_details_url() -> String {
+    "{url}/{id}.html".into()
+}
+
+/// List of strings collected from all elements matching scope
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ListDef {
+    pub scope: String,
+    #[serde(default)]
+    pub item: Option<ValueSpec>,
+    /// regexes of items to drop
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MediaItemsDef {
+    /// first details param is playerjs iframe url
+    #[serde(default)]
+    pub playerjs: Option<PlayerjsLayout>,
+    /// datalife ajax playlist by news id, used when details have no params
+    #[serde(default)]
+    pub ajax_playlist: bool,
+    /// details params are `dle_login_hash` of page, ajax playlist checks it
+    #[serde(default)]
+    pub login_hash: bool,
+}
+
+/// Nesting of playerjs playlist folders
+#[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerjsLayout {
+    Dub,
+    DubSeasonEp,
+    SeasonDubEp,
+    SeasonEpDub,
+}
+
+/// Value is either a selector which text is taken or full description
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ValueSpec {
+    Selector(String),
+    Value(ValueDef),
+}
+
+#[derive(Deserialize, Debug, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ValueDef {
+    /// css selector relative to current element, current element when not set
+    #[serde(default)]
+    pub selector: Option<String>,
+    /// read attribute instead of text
+    #[serde(default)]
+    pub attr: Option<String>,
+    /// take only first text node instead of all text
+    #[serde(default)]
+    pub first_node: bool,
+    /// first alternative with non empty value
+    #[serde(default)]
+    pub any: Vec<ValueSpec>,
+    /// values joined with space
+    #[serde(default)]
+    pub join: Vec<ValueSpec>,
+    #[serde(default)]
+    pub map: Vec<Transform>,
+    /// empty value fails page with layout changed error
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Transform {
+    Trim,
+    Sanitize,
+    /// datalife page url to id
+    DatalifeId,
+    /// relative url to absolute
+    SelfHosted,
+    /// first capture group or whole match
+    Regex(String),
+}
+
+impl SupplierDefinition {
+    pub fn parse(source: &str) -> SupplierResult<Self> {
+        if source.trim_start().starts_with('{') {
+            serde_json::from_str(source).map_err(|err| invalid(err.to_string()))
+        } else {
+            toml::from_str(source).map_err(|err| invalid(err.to_string()))
+        }
+    }
+
+    pub fn supported_types(&self) -> SupplierResult<Vec<ContentType>> {
+        self.supported_types
+            .iter()
+            .map(|t| match t.to_lowercase().as_str() {
+                "movie" => Ok(ContentType::Movie),
+                "anime" => Ok(ContentType::Anime),
+                "cartoon" => Ok(ContentType::Cartoon),
+                "series" => Ok(ContentType::Series),
+                "manga" => Err(invalid("manga suppliers can't be declared")),
+                _ => Err(invalid(format!("unknown content type: {t}"))),
+            })
+            .collect()
+    }
+
+    pub fn expand_url(&self, template: &str) -> String {
+        template.replace("{url}", &self.url)
+    }
+
+    pub fn build_list(&self) -> SupplierResult<html::FilterProcessor<ContentInfo>> {
+        build_content_info_items(&self.list, &self.url)
+    }
+
+    pub fn build_details(&self) -> SupplierResult<html::Required<Option<ContentDetails>>> {
+        let details = &self.details;
+        let url = &self.url;
+
+        let additional_info = details
+            .additional_info
+            .iter()
+            .map(|list| build_list(list, url))
+            .collect::<SupplierResult<Vec<_>>>()?;
+
+        let similar: Box<dyn DOMProcessor<Vec<ContentInfo>>> = match &details.similar {
+            Some(similar) => build_content_info_items(similar, url)?.boxed(),
+            None => html::default_value(),
+        };
+
+        let params = details
+            .params
+            .iter()
+            .map(|p| build_value(p, url))
+            .collect::<SupplierResult<Vec<_>>>()?;
+
+        Ok(html::ScopeProcessor::new(
+            selector(&details.scope)?,
+            html::ContentDetailsProcessor {
+                media_type: MediaType::Video,
+                title: build_value(&details.title, url)?,
+                original_title: build_optional_value(details.original_title.as_ref(), url)?,
+                image: build_value_or_default(details.image.as_ref(), url)?,
+                description: build_value_or_default(details.description.as_ref(), url)?,
+                additional_info: html::merge(additional_info),
+                similar,
+                params: html::JoinProcessors::new(params)
+                    .filter(|p| !p.is_empty())
+                    .boxed(),
+            }
+            .boxed(),
+        )
+        .required())
+    }
+}
+
+fn build_content_info_items(
+    def: &ContentInfoDef,
+    url: &str,
+) -> SupplierResult<html::FilterProcessor<ContentInfo>> {
+    let exclude = regexes(&def.exclude_ids)?;
+
+    Ok(html::ItemsProcessor::new(
+        selector(&def.scope)?,
+        html::ContentInfoProcessor {
+            id: build_value(&def.id, url)?,
+            title: build_value(&def.title, url)?,
+            secondary_title: build_optional_value(def.secondary_title.as_ref(), url)?,
+            image: build_value_or_default(def.image.as_ref(), url)?,
+        }
+        .boxed(),
+    )
+    .filter(move |info| !exclude.iter().any(|re| re.is_match(&info.id))))
+}
+
+fn build_list(def: &ListDef, url: &str) -> SupplierResult<Box<dyn DOMProcessor<Vec<String>>>> {
+    let item = match &def.item {
+        Some(item) => build_value(item, url)?,
+        None => html::TextValue::new()
+            .all_nodes()
+            .map(|s| text::sanitize_text(&s))
+            .boxed(),
+    };
+    let exclude = regexes(&def.exclude)?;
+
+    Ok(html::ItemsProcessor::new(selector(&def.scope)?, item)
+        .filter(move |s| !s.is_empty() && !exclude.iter().any(|re| re.is_match(s)))
+        .boxed())
+}
+
+fn build_optional_value(
+    spec: Option<&ValueSpec>,
+    url: &str,
+) -> SupplierResult<Box<dyn DOMProcessor<Option<String>>>> {
+    match spec {
+        Some(spec) => Ok(build_value(spec, url)?
+            .map(|s| Some(s).filter(|s| !s.is_empty()))
+            .boxed()),
+        None => Ok(html::DefaultValue::new().boxed()),
+    }
+}
+
+fn build_value_or_default(
+    spec: Option<&ValueSpec>,
+    url: &str,
+) -> SupplierResult<Box<dyn DOMProcessor<String>>> {
+    match spec {
+        Some(spec) => build_value(spec, url),
+        None => Ok(html::DefaultValue::new().boxed()),
+    }
+}
+
+fn build_value(spec: &ValueSpec, url: &str) -> SupplierResult<Box<dyn DOMProcessor<String>>> {
+    let text;
+    let def = match spec {
+        ValueSpec::Selector(selector) => {
+            text = ValueDef {
+                selector: Some(selector.clone()),
+                ..Default::default()
+            };
+            &text
+        }
+        ValueSpec::Value(def) => def,
+    };
+
+    let mut processor: Box<dyn DOMProcessor<String>> = if !def.any.is_empty() {
+        let alternatives = def
+            .any
+            .iter()
+            .map(|spec| build_value(spec, url))
+            .collect::<SupplierResult<Vec<_>>>()?;
+        html::Any::new(|s: &String| !s.is_empty(), alternatives).boxed()
+    } else if !def.join.is_empty() {
+        let parts = def
+            .join
+            .iter()
+            .map(|spec| build_value(spec, url))
+            .collect::<SupplierResult<Vec<_>>>()?;
+        html::JoinProcessors::new(parts)
+            .map(|v| v.join(" ").trim().to_owned())
+            .boxed()
+    } else if let Some(attr) = &def.attr {
+        html::AttrValue::new(attr.clone())
+            .unwrap_or_default()
+            .boxed()
+    } else if def.first_node {
+        html::TextValue::new().boxed()
+    } else {
+        html::TextValue::new().all_nodes().boxed()
+    };
+
+    if let Some(scope) = &def.selector {
+        processor = html::ScopeProcessor::new(selector(scope)?, processor)
+            .unwrap_or_default()
+            .boxed();
+    }
+
+    if !def.map.is_empty() {
+        let transforms = def
+            .map
+            .iter()
+            .map(|t| CompiledTransform::new(t, url))
+            .collect::<SupplierResult<Vec<_>>>()?;
+
+        processor = processor
+            .map(move |value| {
+                if value.is_empty() {
+                    return value;
+                }
+                transforms.iter().fold(value, |value, t| t.apply(value))
+            })
+            .boxed();
+    }
+
+    if def.required {
+        processor = html::required(processor);
+    }
+
+    Ok(processor)
+}
+
+enum CompiledTransform {
+    Trim,
+    Sanitize,
+    DatalifeId(Arc<str>),
+    SelfHosted(Arc<str>),
+    Regex(Regex),
+}
+
+impl CompiledTransform {
+    fn new(transform: &Transform, url: &str) -> SupplierResult<Self> {
+        Ok(match transform {
+            Transform::Trim => Self::Trim,
+            Transform::Sanitize => Self::Sanitize,
+            Transform::DatalifeId => Self::DatalifeId(url.into()),
+            Transform::SelfHosted => Self::SelfHosted(url.into()),
+            Transform::Regex(pattern) => Self::Regex(regex(pattern)?),
+        })
+    }
+
+    fn apply(&self, value: String) -> String {
+        match self {
+            Self::Trim => value.trim().to_owned(),
+            Self::Sanitize => text::sanitize_text(&value),
+            Self::DatalifeId(url) => datalife::extract_id_from_url(url, value),
+            Self::SelfHosted(url) => html::self_hosted_url(url, &value),
+            Self::Regex(re) => re
+                .captures(&value)
+                .and_then(|c| c.get(1).or_else(|| c.get(0)))
+                .map(|m| m.as_str().to_owned())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// processors panic on invalid selectors, definitions come from outside so check them first
+fn selector(selector: &str) -> SupplierResult<&str> {
+    Selector::parse(selector)
+        .map(|_| selector)
+        .map_err(|err| invalid(format!("invalid selector {selector:?}: {err}")))
+}
+
+fn regex(pattern: &str) -> SupplierResult<Regex> {
+    Regex::new(pattern).map_err(|err| invalid(format!("invalid regex {pattern:?}: {err}")))
+}
+
+fn regexes(patterns: &[String]) -> SupplierResult<Vec<Regex>> {
+    patterns.iter().map(|p| regex(p)).collect()
+}
+
+fn invalid(message: impl Into<String>) -> SupplierError {
+    SupplierError::invalid_params(message)
+}
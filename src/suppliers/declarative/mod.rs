@@ -0,0 +1,352 @@
+mod definition;
+
+use indexmap::IndexMap;
+use std::path::Path;
+
+use super::ContentSupplier;
+use crate::errors::{SupplierError, SupplierResult};
+use crate::models::{
+    ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
+};
+use crate::utils::{self, datalife, html, mirrors, params, playerjs};
+
+pub use definition::SupplierDefinition;
+use definition::{PlayerjsLayout, SearchDef};
+
+/// Supplier built at runtime from [`SupplierDefinition`]
+pub struct DeclarativeContentSupplier {
+    // boxed to keep `AllContentSuppliers` small
+    definition: Box<SupplierDefinition>,
+    supported_types: Vec<ContentType>,
+    channels_map: IndexMap<String, String>,
+    processor_content_info_items: html::FilterProcessor<ContentInfo>,
+    processor_content_details: html::Required<Option<ContentDetails>>,
+}
+
+impl DeclarativeContentSupplier {
+    pub fn new(definition: SupplierDefinition) -> SupplierResult<Self> {
+        if definition.name.is_empty() {
+            return Err(SupplierError::invalid_params("supplier name is empty"));
+        }
+        for base in std::iter::once(&definition.url).chain(&definition.mirrors) {
+            mirrors::parse_base(base)?;
+        }
+        // urls of channels and details are templates appended to it
+        if definition.url.ends_with('/') {
+            return Err(SupplierError::invalid_params(format!(
+                "supplier url has to be without trailing slash: {}",
+                definition.url
+            )));
+        }
+
+        let media_items = &definition.media_items;
+        if media_items.login_hash && (!media_items.ajax_playlist || media_items.playerjs.is_some())
+        {
+            return Err(SupplierError::invalid_params(
+                "login hash is only passed to ajax playlist without playerjs",
+            ));
+        }
+
+        let channels_map: IndexMap<String, String> = definition
+            .channels
+            .iter()
+            .map(|c| (c.name.clone(), definition.expand_url(&c.url)))
+            .collect();
+
+        if let Some(channel) = definition
+            .default_channels
+            .iter()
+            .find(|&c| !channels_map.contains_key(c))
+        {
+            return Err(SupplierError::invalid_params(format!(
+                "default channel {channel} is not declared"
+            )));
+        }
+
+        Ok(Self {
+            supported_types: definition.supported_types()?,
+            processor_content_info_items: definition.build_list()?,
+            processor_content_details: definition.build_details()?,
+            channels_map,
+            definition: Box::new(definition),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    pub fn definition(&self) -> &SupplierDefinition {
+        &self.definition
+    }
+
+    fn details_url(&self, id: &str) -> String {
+        self.definition
+            .expand_url(&self.definition.details.url)
+            .replace("{id}", id)
+    }
+}
+
+/// Parse definition (json or toml) and build supplier from it
+pub fn parse(source: &str) -> SupplierResult<DeclarativeContentSupplier> {
+    DeclarativeContentSupplier::new(SupplierDefinition::parse(source)?)
+}
+
+/// Build suppliers from all `*.json` and `*.toml` files of directory, sorted by file name
+pub fn load_dir(dir: &str) -> SupplierResult<Vec<DeclarativeContentSupplier>> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|err| SupplierError::invalid_params(format!("definitions dir {dir}: {err}")))?;
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.extension()
+                .is_some_and(|ext| ext == "json" || ext == "toml")
+        })
+        .collect();
+    paths.sort();
+
+    paths.iter().map(|p| load_file(p)).collect()
+}
+
+fn load_file(path: &Path) -> SupplierResult<DeclarativeContentSupplier> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|err| SupplierError::invalid_params(format!("definition {path:?}: {err}")))?;
+
+    parse(&source).map_err(|err| SupplierError::invalid_params(format!("{path:?}: {err}")))
+}
+
+impl ContentSupplier for DeclarativeContentSupplier {
+    fn get_channels(&self) -> Vec<String> {
+        self.channels_map.keys().cloned().collect()
+    }
+
+    fn get_default_channels(&self) -> Vec<String> {
+        self.definition.default_channels.clone()
+    }
+
+    fn get_supported_types(&self) -> Vec<ContentType> {
+        self.supported_types.clone()
+    }
+
+    fn get_supported_languages(&self) -> Vec<String> {
+        self.definition.languages.clone()
+    }
+
+    fn get_display_name(&self) -> String {
+        self.definition.display_name.clone()
+    }
+
+    fn get_home_url(&self) -> String {
+        self.definition.url.clone()
+    }
+
+    fn get_mirrors(&self) -> Vec<String> {
+        self.definition.mirrors.clone()
+    }
+
+    fn supports_search_paging(&self) -> bool {
+        match &self.definition.search {
+            SearchDef::Datalife { page_param } => page_param.is_some(),
+            SearchDef::Get { url } => url.contains("{page}"),
+        }
+    }
+
+    fn supports_channel_paging(&self) -> bool {
+        self.channels_map
+            .values()
+            .any(|url| url.ends_with("/page/"))
+    }
+
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
+        let request = match &self.definition.search {
+            SearchDef::Datalife { page_param } => {
+                let request = datalife::search_request(&self.definition.url, query);
+                match page_param {
+                    Some(param) => request.query(&[(param, page.to_string())]),
+                    None => request,
+                }
+            }
+            SearchDef::Get { url } => {
+                let url = self
+                    .definition
+                    .expand_url(url)
+                    .replace("{query}", &urlencoding::encode(query))
+                    .replace("{page}", &page.to_string());
+                utils::create_client().get(url)
+            }
+        };
+
+        utils::scrap_page(request, &self.processor_content_info_items).await
+    }
+
+    async fn load_channel(&self, channel: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
+        let url = datalife::get_channel_url(&self.channels_map, channel, page)?;
+
+        utils::scrap_page(
+            utils::create_client().get(&url),
+            &self.processor_content_info_items,
+        )
+        .await
+    }
+
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>> {
+        let request = utils::create_client().get(self.details_url(id));
+
+        if !self.definition.media_items.login_hash {
+            return utils::scrap_page(request, &self.processor_content_details).await;
+        }
+
+        let html = utils::fetch_text(request).await?;
+        let document = scraper::Html::parse_document(&html);
+
+        let mut maybe_details =
+            html::process_checked(&self.processor_content_details, &document.root_element())?;
+
+        if let Some(details) = maybe_details.as_mut() {
+            details.params = super::datalife::extract_login_hash(&html)
+                .into_iter()
+                .collect();
+        }
+
+        Ok(maybe_details)
+    }
+
+    async fn load_media_items(
+        &self,
+        id: &str,
+        params: Vec<String>,
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
+        let media_items = &self.definition.media_items;
+
+        match media_items.playerjs {
+            Some(layout) if !params.is_empty() => {
+                let strategy = match layout {
+                    PlayerjsLayout::Dub => playerjs::convert_strategy_dub,
+                    PlayerjsLayout::DubSeasonEp => playerjs::convert_strategy_dub_season_ep,
+                    PlayerjsLayout::SeasonDubEp => playerjs::convert_strategy_season_dub_ep,
+                    PlayerjsLayout::SeasonEpDub => playerjs::convert_strategy_season_ep_dub,
+                };
+
+                playerjs::load_and_parse_playerjs(
+                    utils::create_client().get(params::required(&params, 0, "playlist url")?),
+                    strategy,
+                )
+                .await
+            }
+            _ if media_items.ajax_playlist => {
+                let user_hash = media_items.login_hash.then(|| params.first()).flatten();
+                let request = datalife::ajax_playlist_request(
+                    &self.definition.url,
+                    id,
+                    user_hash.map(|s| s.as_str()),
+                )?;
+                datalife::load_ajax_playlist(request).await
+            }
+            _ => Err(SupplierError::invalid_params("no player found in details")),
+        }
+    }
+
+    async fn load_media_item_sources(
+        &self,
+        _id: &str,
+        params: Vec<String>,
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        if !self.definition.media_items.ajax_playlist {
+            return Err(SupplierError::unsupported("load_media_item_sources"));
+        }
+
+        datalife::load_playlist_sources(&params).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::suppliers;
+
+    const UAKINOCLUB: &str = include_str!("../../../fixtures/declarative/uakinoclub.toml");
+
+    #[test]
+    fn should_register_json_definition() {
+        let definition: toml::Value = toml::from_str(UAKINOCLUB).unwrap();
+        let json = serde_json::to_string(&definition)
+            .unwrap()
+            .replace("UAKinoClubDeclarative", "UAKinoClubJson");
+
+        let name = suppliers::register_supplier_definition(&json).unwrap();
+        assert_eq!(name, "UAKinoClubJson");
+        assert!(suppliers::avalaible_suppliers().contains(&name));
+
+        let info = suppliers::get_supplier_info(&name).unwrap();
+        assert_eq!(info.display_name, "UAKino Club");
+        assert!(info.search_paging);
+        assert!(!info.manga_pages);
+    }
+
+    #[test]
+    fn should_reuse_supplier_for_unchanged_definition() {
+        let definition = UAKINOCLUB.replace("UAKinoClubDeclarative", "UAKinoClubReused");
+
+        let name = suppliers::register_supplier_definition(&definition).unwrap();
+        let registered = suppliers::get_supplier(&name).unwrap();
+
+        suppliers::register_supplier_definition(&definition).unwrap();
+        assert!(std::ptr::eq(
+            registered,
+            suppliers::get_supplier(&name).unwrap()
+        ));
+
+        let changed = definition.replace("UAKino Club", "UAKino Club 2");
+        suppliers::register_supplier_definition(&changed).unwrap();
+        assert!(!std::ptr::eq(
+            registered,
+            suppliers::get_supplier(&name).unwrap()
+        ));
+    }
+
+    #[test]
+    fn should_accept_mirrors_valid_for_mirror_registry() {
+        let definition = UAKINOCLUB
+            .replace("UAKinoClubDeclarative", "UAKinoClubMirrors")
+            .replace(
+                "url = \"https://uakino.best\"",
+                "url = \"https://uakino.best\"\nmirrors = [\"http://uakino.me\", \"https://uakino.club/\"]",
+            );
+
+        let name = suppliers::register_supplier_definition(&definition).unwrap();
+        let supplier = suppliers::get_supplier(&name).unwrap();
+        assert_eq!(
+            supplier.get_mirrors(),
+            vec!["http://uakino.me", "https://uakino.club/"]
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_definitions() {
+        let cases = [
+            ("UAKinoClubDeclarative", "UAKinoClub"),
+            ("#dle-content .movie-item", "#dle-content >>> .movie-item"),
+            ("^news", "^(news"),
+            ("\"movie\", \"cartoon\"", "\"manga\""),
+            ("page_param", "page_parameter"),
+            (
+                "url = \"https://uakino.best\"",
+                "url = \"https://uakino.best\"\nmirrors = [\"https://uakino.me/filmy\"]",
+            ),
+            (
+                "ajax_playlist = true",
+                "ajax_playlist = true\nlogin_hash = true",
+            ),
+        ];
+
+        for (from, to) in cases {
+            let definition = UAKINOCLUB.replace(from, to);
+            let res = suppliers::register_supplier_definition(&definition);
+            assert!(
+                matches!(res, Err(SupplierError::InvalidParams { .. })),
+                "{to:?} is accepted"
+            );
+        }
+    }
+}
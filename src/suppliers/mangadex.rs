@@ -1,20 +1,26 @@
 use std::collections::HashMap;
 
-use anyhow::anyhow;
 use indexmap::IndexMap;
 use reqwest::header::{self, HeaderMap};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::{
     models::{
         ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
-        MediaType,
+        MediaType, SupplierSetting,
+    },
+    utils::{
+        self,
+        params::{self, LegacyParams, MediaParams},
+        request_policy::{self, RequestPolicy},
+        settings,
     },
-    utils,
 };
 
 use super::{ContentSupplier, MangaPagesLoader};
 
+const NAME: &str = "MangaDex";
 const API_URL: &str = "https://api.mangadex.org";
 const COVERS_URL: &str = "https://uploads.mangadex.org/covers";
 const CHANNEL_PAGE_SIZE: usize = 20;
@@ -31,6 +37,16 @@ impl Default for MangaDexContentSupplier {
         headers.insert(header::ACCEPT, "application/json".parse().unwrap());
         headers.insert(header::CONTENT_TYPE, "application/json".parse().unwrap());
 
+        // https://api.mangadex.org/docs/2-limitations/ allows ~5 requests per second
+        request_policy::register_host_policy(
+            "api.mangadex.org",
+            RequestPolicy {
+                requests_per_second: 4.0,
+                burst: 5,
+                ..Default::default()
+            },
+        );
+
         Self {
             channels_map: IndexMap::from([
                 (
@@ -76,73 +92,72 @@ impl ContentSupplier for MangaDexContentSupplier {
         vec!["uk".into(), "en".into()]
     }
 
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        let offset = (page as usize - 1) * CHANNEL_PAGE_SIZE;
-
-        let res_json = self
-            .api_client
-            .get(format!("{API_URL}/manga"))
-            .query(&[
-                ("title", query),
-                ("includes[]", "cover_art"),
-                ("hasAvailableChapters", "true"),
-                ("contentRating[]", "safe"),
-                ("contentRating[]", "suggestive"),
-                ("contentRating[]", "erotica"),
-                ("contentRating[]", "pornographic"),
-            ])
-            .query(&[("limit", CHANNEL_PAGE_SIZE), ("offset", offset)])
-            .send()
-            .await?
-            .text()
-            .await?;
+    fn get_display_name(&self) -> String {
+        "MangaDex".into()
+    }
+
+    fn get_home_url(&self) -> String {
+        "https://mangadex.org".into()
+    }
+
+    fn get_settings(&self) -> Vec<SupplierSetting> {
+        vec![content_ratings_setting()]
+    }
+
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
+        let offset = page.saturating_sub(1) as usize * CHANNEL_PAGE_SIZE;
+
+        let res_json = utils::fetch_text(
+            self.api_client
+                .get(format!("{API_URL}/manga"))
+                .query(&[
+                    ("title", query),
+                    ("includes[]", "cover_art"),
+                    ("hasAvailableChapters", "true"),
+                ])
+                .query(&content_ratings_query())
+                .query(&[("limit", CHANNEL_PAGE_SIZE), ("offset", offset)]),
+        )
+        .await?;
 
         let search_res: MangaDexSearchResponse = serde_json::from_str(&res_json)?;
 
         Ok(search_res.into())
     }
 
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    async fn load_channel(&self, channel: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         let query = match self.channels_map.get(channel) {
             Some(query) => query,
-            None => return Err(anyhow!("Unknown channel")),
+            None => return Err(SupplierError::unknown_channel(channel)),
         };
 
-        let offset = (page as usize - 1) * CHANNEL_PAGE_SIZE;
-        let search_res: MangaDexSearchResponse = self
-            .api_client
-            .get(format!("{API_URL}/manga"))
-            .query(query)
-            .query(&[("limit", CHANNEL_PAGE_SIZE), ("offset", offset)])
-            .query(&[
-                ("contentRating[]", "safe"),
-                ("contentRating[]", "suggestive"),
-                ("contentRating[]", "erotica"),
-            ])
-            .send()
-            .await?
-            .json()
-            .await?;
+        let offset = page.saturating_sub(1) as usize * CHANNEL_PAGE_SIZE;
+        let search_res: MangaDexSearchResponse = utils::fetch_json(
+            self.api_client
+                .get(format!("{API_URL}/manga"))
+                .query(query)
+                .query(&[("limit", CHANNEL_PAGE_SIZE), ("offset", offset)])
+                .query(&content_ratings_query()),
+        )
+        .await?;
 
         Ok(search_res.into())
     }
 
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
-        let res: MangaDexSingeItemResponse = self
-            .api_client
-            .get(format!("{API_URL}/manga/{id}"))
-            .query(&[
-                ("includes[]", "cover_art"),
-                ("includes[]", "author"),
-                ("contentRating[]", "safe"),
-                ("contentRating[]", "suggestive"),
-                ("contentRating[]", "erotica"),
-                ("contentRating[]", "pornographic"),
-            ])
-            .send()
-            .await?
-            .json()
-            .await?;
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>> {
+        let res: MangaDexSingeItemResponse = utils::fetch_json(
+            self.api_client
+                .get(format!("{API_URL}/manga/{id}"))
+                .query(&[
+                    ("includes[]", "cover_art"),
+                    ("includes[]", "author"),
+                    ("contentRating[]", "safe"),
+                    ("contentRating[]", "suggestive"),
+                    ("contentRating[]", "erotica"),
+                    ("contentRating[]", "pornographic"),
+                ]),
+        )
+        .await?;
 
         Ok(res.data.and_then(|d| d.into()))
     }
@@ -151,7 +166,7 @@ impl ContentSupplier for MangaDexContentSupplier {
         &self,
         id: &str,
         _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
         let mut requests_left = 30usize;
         let mut last_offset = 0usize;
         let mut media_items: IndexMap<String, ContentMediaItem> = IndexMap::new();
@@ -168,14 +183,12 @@ impl ContentSupplier for MangaDexContentSupplier {
                 ("contentRating[]", "pornographic".to_string()),
             ];
 
-            let res_str = self
-                .api_client
-                .get(format!("{API_URL}/manga/{id}/feed"))
-                .query(&query)
-                .send()
-                .await?
-                .text()
-                .await?;
+            let res_str = utils::fetch_text(
+                self.api_client
+                    .get(format!("{API_URL}/manga/{id}/feed"))
+                    .query(&query),
+            )
+            .await?;
 
             let res: MangaDexChaptersResponse = serde_json::from_str(&res_str)?;
 
@@ -206,7 +219,9 @@ impl ContentSupplier for MangaDexContentSupplier {
                     description: format!("[{translation_lang}] {scanlation_group}"),
                     headers: None,
                     pages: None,
-                    params: vec![id.to_owned()],
+                    params: params::encode(&ChapterParams {
+                        chapter_id: id.to_owned(),
+                    }),
                 });
             });
 
@@ -225,18 +240,43 @@ impl ContentSupplier for MangaDexContentSupplier {
         &self,
         _id: &str,
         _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        Err(anyhow!("Unimplemented"))
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        Err(SupplierError::unsupported("load_media_item_sources"))
     }
 }
 
-impl MangaPagesLoader for MangaDexContentSupplier {
-    async fn load_pages(&self, _id: &str, params: Vec<String>) -> anyhow::Result<Vec<String>> {
-        if params.is_empty() {
-            return Err(anyhow!("Chapter id expected"));
+#[derive(Deserialize, Serialize)]
+struct ChapterParams {
+    chapter_id: String,
+}
+
+impl MediaParams for ChapterParams {
+    const KIND: &'static str = "mangadex_chapter";
+    const VERSION: u32 = 2;
+
+    // v1 is bare [chapter_id]
+    fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+        match legacy {
+            LegacyParams::Raw(raw) => Ok(Self {
+                chapter_id: params::required(raw, 0, "Chapter id")?.to_owned(),
+            }),
+            LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(format!(
+                "mangadex chapter params version {version} is not supported"
+            ))),
         }
+    }
 
-        let chapter_id = &params[0];
+    fn validate(&self) -> SupplierResult<()> {
+        if self.chapter_id.is_empty() {
+            return Err(SupplierError::invalid_params("Chapter id expected"));
+        }
+        Ok(())
+    }
+}
+
+impl MangaPagesLoader for MangaDexContentSupplier {
+    async fn load_pages(&self, _id: &str, params: Vec<String>) -> SupplierResult<Vec<String>> {
+        let ChapterParams { chapter_id } = params::decode(&params)?;
 
         #[derive(Deserialize)]
         struct Chapter {
@@ -251,13 +291,11 @@ impl MangaPagesLoader for MangaDexContentSupplier {
             chapter: Chapter,
         }
 
-        let chapter_server_res_str = self
-            .api_client
-            .get(format!("{API_URL}/at-home/server/{chapter_id}"))
-            .send()
-            .await?
-            .text()
-            .await?;
+        let chapter_server_res_str = utils::fetch_text(
+            self.api_client
+                .get(format!("{API_URL}/at-home/server/{chapter_id}")),
+        )
+        .await?;
 
         let chapter_server_res: ChapterServerRes = serde_json::from_str(&chapter_server_res_str)?;
 
@@ -357,6 +395,22 @@ impl From<MangaDexItem> for Option<ContentDetails> {
     }
 }
 
+fn content_ratings_setting() -> SupplierSetting {
+    settings::multi_choice(
+        "content_ratings",
+        &["safe", "suggestive", "erotica", "pornographic"],
+        &["safe", "suggestive", "erotica", "pornographic"],
+    )
+}
+
+// details and chapters are not filtered, so already opened titles keep working
+fn content_ratings_query() -> Vec<(&'static str, String)> {
+    settings::values(NAME, &content_ratings_setting())
+        .into_iter()
+        .map(|rating| ("contentRating[]", rating))
+        .collect()
+}
+
 fn lookup_title(attributes: &HashMap<String, serde_json::Value>) -> Option<String> {
     let title_obj = attributes.get("title")?.as_object()?;
 
@@ -493,50 +547,133 @@ fn lookup_scanlation_group(rels: &[MangaDexRelationship]) -> &str {
 
 #[cfg(test)]
 mod tests {
-
-    use std::vec;
+    use crate::utils::golden;
 
     use super::*;
+
+    const SUPPLIER: &str = "mangadex";
+
     #[tokio::test]
     async fn mangadex_should_load_channel() {
-        let res = MangaDexContentSupplier::default()
-            .load_channel("Popular Titles", 2)
-            .await;
-        println!("{res:#?}");
+        let res = golden::cassette(
+            SUPPLIER,
+            "channel",
+            MangaDexContentSupplier::default().load_channel("Popular Titles", 2),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[0].id, "a1c7c817-4e59-43b7-9365-09675a149a6f");
+        assert_eq!(res[0].title, "One Piece");
+        assert_eq!(
+            res[0].image,
+            "https://uploads.mangadex.org/covers/a1c7c817-4e59-43b7-9365-09675a149a6f/4a5e3bd8-3b4c-4e2a-9a0c-56d0a1c2e8f1.jpg.512.jpg"
+        );
+        // no english title, first one is used
+        assert_eq!(res[2].title, "Shingeki no Kyojin");
     }
 
     #[tokio::test]
     async fn mangadex_should_search() {
-        let res = MangaDexContentSupplier::default()
-            .search("idaten deities", 1)
-            .await;
-        println!("{res:#?}");
+        let res = golden::cassette(
+            SUPPLIER,
+            "search",
+            MangaDexContentSupplier::default().search("idaten deities", 1),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].id, "0f7295a6-eaf5-470b-a003-b7789a9a0f4a");
+        assert_eq!(res[0].title, "The Idaten Deities Know Only Peace");
     }
 
     #[tokio::test]
     async fn mangadex_should_get_content_details() {
-        let res = MangaDexContentSupplier::default()
-            .get_content_details("0f7295a6-eaf5-470b-a003-b7789a9a0f4a")
-            .await;
-        println!("{res:#?}");
+        let res = golden::cassette(
+            SUPPLIER,
+            "details",
+            MangaDexContentSupplier::default()
+                .get_content_details("0f7295a6-eaf5-470b-a003-b7789a9a0f4a"),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(res.title, "The Idaten Deities Know Only Peace");
+        assert_eq!(res.original_title.as_deref(), Some("平穏世代の韋駄天達"));
+        assert_eq!(
+            res.image,
+            "https://uploads.mangadex.org/covers/0f7295a6-eaf5-470b-a003-b7789a9a0f4a/6d5b6a4c-1b1e-4d8e-8c1f-2a3e4b5c6d7e.jpg.512.jpg"
+        );
+        assert!(res.description.starts_with("800 years ago"));
+        assert_eq!(
+            res.additional_info,
+            vec![
+                "Author: Amahara",
+                "Year: 2019",
+                "Status: ongoing",
+                "Genres: Action, Fantasy"
+            ]
+        );
     }
 
     #[tokio::test]
     async fn mangadex_should_load_media_items() {
-        let res = MangaDexContentSupplier::default()
-            .load_media_items("c1e284bc-0436-42fe-b571-fa35a94279ce", vec![])
-            .await;
-        println!("{res:#?}");
+        let res = golden::cassette(
+            SUPPLIER,
+            "media_items",
+            MangaDexContentSupplier::default()
+                .load_media_items("c1e284bc-0436-42fe-b571-fa35a94279ce", vec![]),
+        )
+        .await
+        .unwrap();
+
+        let titles: Vec<_> = res.iter().map(|item| item.title.as_str()).collect();
+        assert_eq!(titles, vec!["1", "2", "Oneshot"]);
+        assert_eq!(res[0].section.as_deref(), Some("1"));
+        assert_eq!(res[2].section.as_deref(), Some("No Volume"));
+
+        // translations of the same chapter are merged into one item
+        let descriptions: Vec<_> = res[0]
+            .sources
+            .iter()
+            .flatten()
+            .map(|source| match source {
+                ContentMediaItemSource::Manga { description, .. } => description.as_str(),
+                _ => panic!("manga source expected"),
+            })
+            .collect();
+        assert_eq!(descriptions, vec!["[en] Mangastic", "[uk] Unknown"]);
+
+        let ContentMediaItemSource::Manga { params, .. } = &res[0].sources.as_ref().unwrap()[0]
+        else {
+            unreachable!()
+        };
+        let ChapterParams { chapter_id } = params::decode(params).unwrap();
+        assert_eq!(chapter_id, "1fe13d15-982f-402b-8120-91f717f886b8");
     }
 
-    #[tokio::test()]
+    #[tokio::test]
     async fn mangadex_should_load_pages() {
-        let res = MangaDexContentSupplier::default()
-            .load_pages(
+        let res = golden::cassette(
+            SUPPLIER,
+            "pages",
+            MangaDexContentSupplier::default().load_pages(
                 "c1e284bc-0436-42fe-b571-fa35a94279ce",
                 vec!["1fe13d15-982f-402b-8120-91f717f886b8".into()],
-            )
-            .await;
-        println!("{res:#?}")
+            ),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            res,
+            vec![
+                "https://cmdxd98sb0x3yprd.mangadex.network/data/3c1a9e1b5f2d4c7e8a9b0c1d2e3f4a5b/1-8b1e6b0c2d3f4a5e.png",
+                "https://cmdxd98sb0x3yprd.mangadex.network/data/3c1a9e1b5f2d4c7e8a9b0c1d2e3f4a5b/2-9c2f7c1d3e4a5b6f.png",
+            ]
+        );
     }
 }
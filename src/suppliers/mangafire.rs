@@ -1,13 +1,16 @@
-use anyhow::anyhow;
 use reqwest::header::{self, HeaderMap};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::{
     models::{
         ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
         MediaType,
     },
-    utils,
+    utils::{
+        self,
+        params::{self, LegacyParams, MediaParams},
+    },
 };
 
 use super::{ContentSupplier, MangaPagesLoader};
@@ -52,47 +55,46 @@ impl ContentSupplier for MangaFireContentSupplier {
         vec!["en".into()]
     }
 
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        let res: MangaFireSearchResponse = self
-            .api_client
-            .get(format!("{API_URL}/titles"))
-            .query(&[
-                ("keyword", query),
-                ("content_rating[]", "safe"),
-                ("content_rating[]", "suggestive"),
-                ("order[relevance]", "desc"),
-            ])
-            .query(&[("page", page), ("limit", PAGE_LIMIT)])
-            .send()
-            .await?
-            .json()
-            .await?;
+    fn get_display_name(&self) -> String {
+        "MangaFire".into()
+    }
+
+    fn get_home_url(&self) -> String {
+        BASE_URL.into()
+    }
+
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
+        let res: MangaFireSearchResponse = utils::fetch_json(
+            self.api_client
+                .get(format!("{API_URL}/titles"))
+                .query(&[
+                    ("keyword", query),
+                    ("content_rating[]", "safe"),
+                    ("content_rating[]", "suggestive"),
+                    ("order[relevance]", "desc"),
+                ])
+                .query(&[("page", page), ("limit", PAGE_LIMIT)]),
+        )
+        .await?;
 
         Ok(res.into())
     }
 
-    async fn load_channel(&self, _: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        let res: MangaFireSearchResponse = self
-            .api_client
-            .get(format!("{API_URL}/top-titles"))
-            .query(&[("days", "30")])
-            .query(&[("page", page), ("limit", PAGE_LIMIT)])
-            .send()
-            .await?
-            .json()
-            .await?;
+    async fn load_channel(&self, _: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
+        let res: MangaFireSearchResponse = utils::fetch_json(
+            self.api_client
+                .get(format!("{API_URL}/top-titles"))
+                .query(&[("days", "30")])
+                .query(&[("page", page), ("limit", PAGE_LIMIT)]),
+        )
+        .await?;
 
         Ok(res.into())
     }
 
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
-        let res: MangaFireDetailsResponse = self
-            .api_client
-            .get(format!("{API_URL}/titles/{id}"))
-            .send()
-            .await?
-            .json()
-            .await?;
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>> {
+        let res: MangaFireDetailsResponse =
+            utils::fetch_json(self.api_client.get(format!("{API_URL}/titles/{id}"))).await?;
 
         Ok(res.data.map(|d| d.into()))
     }
@@ -101,21 +103,19 @@ impl ContentSupplier for MangaFireContentSupplier {
         &self,
         id: &str,
         _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
         let mut all_items: Vec<ContentMediaItem> = Vec::new();
         let mut page = 1u16;
         let max_pages = 20u16;
 
         loop {
-            let res: MangaFireChaptersResponse = self
-                .api_client
-                .get(format!("{API_URL}/titles/{id}/chapters"))
-                .query(&[("language", "en"), ("sort", "number"), ("order", "asc")])
-                .query(&[("page", page), ("limit", 100)])
-                .send()
-                .await?
-                .json()
-                .await?;
+            let res: MangaFireChaptersResponse = utils::fetch_json(
+                self.api_client
+                    .get(format!("{API_URL}/titles/{id}/chapters"))
+                    .query(&[("language", "en"), ("sort", "number"), ("order", "asc")])
+                    .query(&[("page", page), ("limit", 100)]),
+            )
+            .await?;
 
             for ch in res.items {
                 let title = if ch.name.is_empty() {
@@ -132,7 +132,9 @@ impl ContentSupplier for MangaFireContentSupplier {
                         description: "EN".to_string(),
                         headers: None,
                         pages: None,
-                        params: vec![ch.id.to_string()],
+                        params: params::encode(&ChapterParams {
+                            chapter_id: ch.id.to_string(),
+                        }),
                     }]),
                     params: vec![],
                 });
@@ -151,8 +153,8 @@ impl ContentSupplier for MangaFireContentSupplier {
         &self,
         _id: &str,
         _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        Err(anyhow!("Unimplemented"))
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        Err(SupplierError::unsupported("load_media_item_sources"))
     }
 }
 
@@ -261,10 +263,10 @@ impl From<MangaFireDetailItem> for ContentDetails {
             additional_info.push(format!("Genres: {}", all_tags.join(", ")));
         }
 
-        if let Some(langs) = &item.languages {
-            if !langs.is_empty() {
-                additional_info.push(format!("Languages: {}", langs.join(", ")));
-            }
+        if let Some(langs) = &item.languages
+            && !langs.is_empty()
+        {
+            additional_info.push(format!("Languages: {}", langs.join(", ")));
         }
 
         let params = item.languages.unwrap_or_default();
@@ -283,21 +285,44 @@ impl From<MangaFireDetailItem> for ContentDetails {
     }
 }
 
-impl MangaPagesLoader for MangaFireContentSupplier {
-    async fn load_pages(&self, _id: &str, params: Vec<String>) -> anyhow::Result<Vec<String>> {
-        if params.is_empty() {
-            return Err(anyhow!("Chapter id expected"));
+#[derive(Deserialize, Serialize)]
+struct ChapterParams {
+    chapter_id: String,
+}
+
+impl MediaParams for ChapterParams {
+    const KIND: &'static str = "mangafire_chapter";
+    const VERSION: u32 = 2;
+
+    // v1 is bare [chapter_id]
+    fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+        match legacy {
+            LegacyParams::Raw(raw) => Ok(Self {
+                chapter_id: params::required(raw, 0, "Chapter id")?.to_owned(),
+            }),
+            LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(format!(
+                "mangafire chapter params version {version} is not supported"
+            ))),
         }
+    }
 
-        let chapter_id = &params[0];
+    fn validate(&self) -> SupplierResult<()> {
+        if self.chapter_id.is_empty() {
+            return Err(SupplierError::invalid_params("Chapter id expected"));
+        }
+        Ok(())
+    }
+}
 
-        let res: MangaFirePagesResponse = self
-            .api_client
-            .get(format!("{API_URL}/chapters/{chapter_id}"))
-            .send()
-            .await?
-            .json()
-            .await?;
+impl MangaPagesLoader for MangaFireContentSupplier {
+    async fn load_pages(&self, _id: &str, params: Vec<String>) -> SupplierResult<Vec<String>> {
+        let ChapterParams { chapter_id } = params::decode(&params)?;
+
+        let res: MangaFirePagesResponse = utils::fetch_json(
+            self.api_client
+                .get(format!("{API_URL}/chapters/{chapter_id}")),
+        )
+        .await?;
 
         let pages = res.data.pages.into_iter().map(|p| p.url).collect();
 
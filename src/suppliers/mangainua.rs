@@ -1,36 +1,48 @@
 use std::collections::HashMap;
 
-use anyhow::{Ok, anyhow};
 use indexmap::IndexMap;
 use regex::Regex;
 use scraper::{Selector, selectable::Selectable};
+use serde::{Deserialize, Serialize};
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::{
-    models::{
-        ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
-        MediaType,
-    },
+    models::{ContentMediaItem, ContentMediaItemSource, ContentType, MediaType},
     utils::{
         self, datalife,
         html::{self, DOMProcessor},
+        params::{self, LegacyParams, MediaParams},
     },
 };
 
-use super::{ContentSupplier, MangaPagesLoader};
+use super::MangaPagesLoader;
+use super::datalife::{DataLifeConfig, DataLifeSite, DataLifeSupplier, SearchPaging};
 
 const URL: &str = "https://manga.in.ua";
 const USER_HASH: &str = "772f84a2554710856146eb1863c483d705b01412";
 
-pub struct MangaInUaContentSupplier {
-    channels_map: IndexMap<&'static str, String>,
-    processor_content_info_items: html::ItemsProcessor<ContentInfo>,
-    processor_content_details: html::ScopeProcessor<ContentDetails>,
+pub type MangaInUaContentSupplier = DataLifeSupplier<MangaInUa>;
+
+pub struct MangaInUa {
     re_chapters: Regex,
 }
 
-impl Default for MangaInUaContentSupplier {
+impl Default for MangaInUa {
     fn default() -> Self {
         Self {
+            re_chapters: Regex::new(r"chapters/([a-z0-9]+)").unwrap(),
+        }
+    }
+}
+
+impl DataLifeSite for MangaInUa {
+    fn config(&self) -> DataLifeConfig {
+        DataLifeConfig {
+            url: URL,
+            display_name: "Manga.in.ua",
+            mirrors: vec![],
+            supported_types: vec![ContentType::Manga],
+            supported_languages: vec!["uk"],
             channels_map: IndexMap::from([
                 ("Новинки", URL.to_string()),
                 ("Манґа", format!("{URL}/xfsearch/type/manga/")),
@@ -46,11 +58,12 @@ impl Default for MangaInUaContentSupplier {
                 ("Бойовик", format!("{URL}/mangas/boyovik/")),
                 ("Психологія", format!("{URL}/mangas/psihologia/")),
             ]),
-            processor_content_info_items: html::ItemsProcessor::new(".movie > article.item",     html::ContentInfoProcessor {
-                id: html::attr_value_map(".card__content > h3 > a", "href", |s| {
+            search_paging: SearchPaging::FirstPage,
+            content_info_items: html::required_scope(".movie", html::items_processor("article.item",     html::ContentInfoProcessor {
+                id: html::required(html::attr_value_map(".card__content > h3 > a", "href", |s| {
                     datalife::extract_id_from_url(URL, s)
-                }),
-                title: html::text_value(".card__content > h3 > a"),
+                })),
+                title: html::required(html::text_value(".card__content > h3 > a")),
                 secondary_title: html::items_processor(".card__category a", html::TextValue::new().boxed())
                     .map(|str| Some(str.join(", ")))
                     .boxed(),
@@ -68,12 +81,12 @@ impl Default for MangaInUaContentSupplier {
                 .unwrap_or_default()
                 .boxed(),
             }
-            .boxed()),
-            processor_content_details: html::ScopeProcessor::new(
+            .boxed())),
+            content_details: html::ScopeProcessor::new(
                 "#site-content",
                 html::ContentDetailsProcessor {
                     media_type: MediaType::Manga,
-                    title: html::text_value(".item__full-title span"),
+                    title: html::required(html::text_value(".item__full-title span")),
                     original_title: html::default_value(),
                     image: html::self_hosted_image(URL, ".item__full-sidebar--poster img", "src"),
                     description: html::text_value(".item__full-description"),
@@ -97,79 +110,21 @@ impl Default for MangaInUaContentSupplier {
                     params: html::default_value(),
                 }
                 .boxed(),
-            ),
-            re_chapters: Regex::new(r"chapters/([a-z0-9]+)").unwrap()
-        }
-    }
-}
-
-impl ContentSupplier for MangaInUaContentSupplier {
-    fn get_channels(&self) -> Vec<String> {
-        self.channels_map.keys().map(|&s| s.to_string()).collect()
-    }
-
-    fn get_default_channels(&self) -> Vec<String> {
-        vec![]
-    }
-
-    fn get_supported_types(&self) -> Vec<ContentType> {
-        vec![ContentType::Manga]
-    }
-
-    fn get_supported_languages(&self) -> Vec<String> {
-        vec!["uk".to_string()]
-    }
-
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        if page > 1 {
-            return Ok(vec![]);
+            )
+            .required()
+            .boxed(),
+            playlist: None,
+            login_hash: false,
         }
-
-        utils::scrap_page(
-            datalife::search_request(URL, query),
-            &self.processor_content_info_items,
-        )
-        .await
-    }
-
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        let url = datalife::get_channel_url(&self.channels_map, channel, page)?;
-
-        utils::scrap_page(
-            utils::create_client().get(&url),
-            &self.processor_content_info_items,
-        )
-        .await
-    }
-
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
-        let url = datalife::format_id_from_url(URL, id);
-
-        let html = utils::create_client()
-            .get(&url)
-            .send()
-            .await?
-            .text()
-            .await?;
-
-        let document = scraper::Html::parse_document(&html);
-        let root = document.root_element();
-
-        let maybe_details = self.processor_content_details.process(&root);
-
-        Ok(maybe_details)
     }
 
     async fn load_media_items(
         &self,
+        _config: &DataLifeConfig,
         id: &str,
         _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
-        let news_id = id
-            .rsplit_once("/")
-            .and_then(|(_, r)| r.split_once("-"))
-            .map(|(l, _)| l)
-            .ok_or_else(|| anyhow!("cant extract news_id for id: {}", id))?;
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
+        let news_id = datalife::extract_news_id(id)?;
 
         let client = utils::create_json_client();
 
@@ -180,19 +135,18 @@ impl ContentSupplier for MangaInUaContentSupplier {
         form_params.insert("news_category", "54");
         form_params.insert("this_link", "");
 
-        let chapters_list_html = client
-            .post(format!("{URL}/engine/ajax/controller.php"))
-            .query(&[("mod", "load_chapters")])
-            .form(&form_params)
-            .header(
-                "Content-Type",
-                "application/x-www-form-urlencoded; charset=UTF-8",
-            )
-            .header("x-requested-with", "XMLHttpRequest")
-            .send()
-            .await?
-            .text()
-            .await?;
+        let chapters_list_html = utils::fetch_text(
+            client
+                .post(format!("{URL}/engine/ajax/controller.php"))
+                .query(&[("mod", "load_chapters")])
+                .form(&form_params)
+                .header(
+                    "Content-Type",
+                    "application/x-www-form-urlencoded; charset=UTF-8",
+                )
+                .header("x-requested-with", "XMLHttpRequest"),
+        )
+        .await?;
 
         let fragment = scraper::Html::parse_fragment(&chapters_list_html);
 
@@ -215,14 +169,16 @@ impl ContentSupplier for MangaInUaContentSupplier {
 
                 Some(ContentMediaItem {
                     section: None,
-                    title: title,
+                    title,
                     image: None,
                     params: vec![],
                     sources: Some(vec![ContentMediaItemSource::Manga {
                         description: translator.to_string(),
                         headers: None,
                         pages: None,
-                        params: vec![chapter_id.to_string()],
+                        params: params::encode(&ChapterParams {
+                            chapter_id: chapter_id.to_owned(),
+                        }),
                     }]),
                 })
             })
@@ -230,39 +186,55 @@ impl ContentSupplier for MangaInUaContentSupplier {
 
         Ok(result)
     }
+}
 
-    async fn load_media_item_sources(
-        &self,
-        _id: &str,
-        _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        Err(anyhow!("unimplemented"))
-    }
+#[derive(Deserialize, Serialize)]
+struct ChapterParams {
+    chapter_id: String,
 }
 
-impl MangaPagesLoader for MangaInUaContentSupplier {
-    async fn load_pages(&self, _id: &str, params: Vec<String>) -> anyhow::Result<Vec<String>> {
-        if params.len() < 1 {
-            return Err(anyhow!("invalid params number"));
+impl MediaParams for ChapterParams {
+    const KIND: &'static str = "mangainua_chapter";
+    const VERSION: u32 = 2;
+
+    // v1 is bare [chapter_id]
+    fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+        match legacy {
+            LegacyParams::Raw(raw) => Ok(Self {
+                chapter_id: params::required(raw, 0, "chapter id")?.to_owned(),
+            }),
+            LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(format!(
+                "mangainua chapter params version {version} is not supported"
+            ))),
         }
+    }
 
-        let news_id = &params[0];
+    fn validate(&self) -> SupplierResult<()> {
+        if self.chapter_id.is_empty() {
+            return Err(SupplierError::invalid_params("chapter id expected"));
+        }
+        Ok(())
+    }
+}
+
+impl MangaPagesLoader for MangaInUaContentSupplier {
+    async fn load_pages(&self, _id: &str, params: Vec<String>) -> SupplierResult<Vec<String>> {
+        let ChapterParams { chapter_id } = params::decode(&params)?;
 
         let client = utils::create_json_client();
 
-        let pages_list = client
-            .get(format!("{URL}/engine/ajax/controller.php"))
-            .query(&[
-                ("mod", "load_chapters_image"),
-                ("news_id", news_id),
-                ("user_hash", USER_HASH),
-                ("action", "show"),
-            ])
-            .header("x-requested-with", "XMLHttpRequest")
-            .send()
-            .await?
-            .text()
-            .await?;
+        let pages_list = utils::fetch_text(
+            client
+                .get(format!("{URL}/engine/ajax/controller.php"))
+                .query(&[
+                    ("mod", "load_chapters_image"),
+                    ("news_id", chapter_id.as_str()),
+                    ("user_hash", USER_HASH),
+                    ("action", "show"),
+                ])
+                .header("x-requested-with", "XMLHttpRequest"),
+        )
+        .await?;
 
         let fragment = scraper::Html::parse_fragment(&pages_list);
 
@@ -282,6 +254,7 @@ impl MangaPagesLoader for MangaInUaContentSupplier {
 mod tests {
 
     use super::*;
+    use crate::suppliers::ContentSupplier;
 
     #[tokio::test]
     async fn mangainua_should_search() {
@@ -5,9 +5,14 @@ mod animetsu;
 mod animeua;
 mod anitube;
 mod anizone;
+#[cfg(test)]
+mod conformance;
+mod datalife;
+mod declarative;
 mod mangadex;
 mod mangafire;
 mod mangainua;
+mod search_all;
 mod tmdb;
 mod uaflix;
 mod uakinoclub;
@@ -21,6 +26,7 @@ use animetsu::AnimetsuContentSupplier;
 use animeua::AnimeUAContentSupplier;
 use anitube::AniTubeContentSupplier;
 use anizone::AnizoneContentSupplier;
+use declarative::DeclarativeContentSupplier;
 use mangadex::MangaDexContentSupplier;
 use mangafire::MangaFireContentSupplier;
 use mangainua::MangaInUaContentSupplier;
@@ -32,14 +38,24 @@ use uaserials_pro::UASerialsProContentSupplier;
 use ufdub::UFDubContentSupplier;
 use weebcentral::WeebCentralContentSupplier;
 
+pub use search_all::search_all;
+
 use enum_dispatch::enum_dispatch;
-use std::str::FromStr;
+use indexmap::IndexMap;
+use log::warn;
+use std::{
+    str::FromStr,
+    sync::{OnceLock, RwLock},
+};
 use strum::VariantNames;
 use strum_macros::{EnumIter, EnumString, VariantNames};
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::models::{
     ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
+    SupplierInfo, SupplierProxy, SupplierSetting,
 };
+use crate::utils::{credentials, mirrors, proxy, settings, sources_sink::SourcesSink};
 
 #[enum_dispatch]
 pub trait ContentSupplier {
@@ -47,19 +63,59 @@ pub trait ContentSupplier {
     fn get_default_channels(&self) -> Vec<String>;
     fn get_supported_types(&self) -> Vec<ContentType>;
     fn get_supported_languages(&self) -> Vec<String>;
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>>;
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>>;
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>>;
+    fn get_display_name(&self) -> String;
+    fn get_home_url(&self) -> String;
+    /// names of secrets (api keys, tokens) host app has to set before use
+    fn get_credentials(&self) -> Vec<String> {
+        vec![]
+    }
+    /// user preferences supplier honours
+    fn get_settings(&self) -> Vec<SupplierSetting> {
+        vec![]
+    }
+    /// other domains of site, tried in order when home url is not available
+    fn get_mirrors(&self) -> Vec<String> {
+        vec![]
+    }
+    fn get_icon_url(&self) -> String {
+        format!("{}/favicon.ico", self.get_home_url().trim_end_matches('/'))
+    }
+    /// search returns more than first page
+    fn supports_search_paging(&self) -> bool {
+        true
+    }
+    /// channels return more than first page
+    fn supports_channel_paging(&self) -> bool {
+        true
+    }
+    /// some of the sources have to be played through local hls proxy
+    fn needs_hls_proxy(&self) -> bool {
+        false
+    }
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>>;
+    async fn load_channel(&self, channel: &str, page: u16) -> SupplierResult<Vec<ContentInfo>>;
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>>;
     async fn load_media_items(
         &self,
         id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>>;
+    ) -> SupplierResult<Vec<ContentMediaItem>>;
     async fn load_media_item_sources(
         &self,
         id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>>;
+    ) -> SupplierResult<Vec<ContentMediaItemSource>>;
+    /// Push sources to sink as soon as each extractor or server resolves them
+    async fn stream_media_item_sources(
+        &self,
+        id: &str,
+        params: Vec<String>,
+        sink: &SourcesSink,
+    ) -> SupplierResult<()> {
+        let sources = self.load_media_item_sources(id, params).await?;
+        sink.add("default", sources);
+        Ok(())
+    }
 }
 
 #[enum_dispatch(ContentSupplier)]
@@ -96,34 +152,359 @@ pub enum AllContentSuppliers {
     MangaInUaContentSupplier,
     #[strum(serialize = "WeebCentral")]
     WeebCentralContentSupplier,
+    /// registered at runtime, see `register_supplier`
+    #[strum(disabled)]
+    DeclarativeContentSupplier,
 }
 
 #[enum_dispatch]
 pub trait MangaPagesLoader {
-    async fn load_pages(&self, id: &str, params: Vec<String>) -> anyhow::Result<Vec<String>>;
+    async fn load_pages(&self, id: &str, params: Vec<String>) -> SupplierResult<Vec<String>>;
 }
 
 #[enum_dispatch(MangaPagesLoader)]
-#[derive(EnumString)]
 #[allow(clippy::enum_variant_names)]
-pub enum AllMangaPagesLoaders {
-    #[strum(serialize = "MangaDex")]
-    MangaDexContentSupplier,
-    #[strum(serialize = "MangaInUa")]
-    MangaInUaContentSupplier,
-    #[strum(serialize = "WeebCentral")]
-    WeebCentralContentSupplier,
-    #[strum(serialize = "MangaFire")]
-    MangaFireContentSupplier,
+pub enum AllMangaPagesLoaders<'a> {
+    MangaDexContentSupplier(&'a MangaDexContentSupplier),
+    MangaInUaContentSupplier(&'a MangaInUaContentSupplier),
+    WeebCentralContentSupplier(&'a WeebCentralContentSupplier),
+    MangaFireContentSupplier(&'a MangaFireContentSupplier),
+}
+
+impl<T: MangaPagesLoader> MangaPagesLoader for &T {
+    async fn load_pages(&self, id: &str, params: Vec<String>) -> SupplierResult<Vec<String>> {
+        (**self).load_pages(id, params).await
+    }
+}
+
+impl<'a> TryFrom<&'a AllContentSuppliers> for AllMangaPagesLoaders<'a> {
+    type Error = SupplierError;
+
+    fn try_from(supplier: &'a AllContentSuppliers) -> Result<Self, Self::Error> {
+        match supplier {
+            AllContentSuppliers::MangaDexContentSupplier(s) => Ok(Self::MangaDexContentSupplier(s)),
+            AllContentSuppliers::MangaInUaContentSupplier(s) => {
+                Ok(Self::MangaInUaContentSupplier(s))
+            }
+            AllContentSuppliers::WeebCentralContentSupplier(s) => {
+                Ok(Self::WeebCentralContentSupplier(s))
+            }
+            AllContentSuppliers::MangaFireContentSupplier(s) => {
+                Ok(Self::MangaFireContentSupplier(s))
+            }
+            _ => Err(SupplierError::unsupported("load_pages")),
+        }
+    }
+}
+
+// suppliers are expensive to build (selectors, processors, http clients),
+// so each one is created on first use and shared for the process lifetime
+fn registry() -> &'static IndexMap<&'static str, OnceLock<AllContentSuppliers>> {
+    static REGISTRY: OnceLock<IndexMap<&'static str, OnceLock<AllContentSuppliers>>> =
+        OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        AllContentSuppliers::VARIANTS
+            .iter()
+            .map(|&name| (name, OnceLock::new()))
+            .collect()
+    })
+}
+
+// suppliers from definitions are leaked: callers hold `&'static` references,
+// so replaced definitions stay alive, unchanged definitions reuse registered supplier
+fn runtime_registry() -> &'static RwLock<IndexMap<String, &'static AllContentSuppliers>> {
+    static RUNTIME_REGISTRY: OnceLock<RwLock<IndexMap<String, &'static AllContentSuppliers>>> =
+        OnceLock::new();
+    RUNTIME_REGISTRY.get_or_init(Default::default)
 }
 
 pub fn avalaible_suppliers() -> Vec<String> {
-    AllContentSuppliers::VARIANTS
-        .iter()
+    registry()
+        .keys()
         .map(|&s| s.to_owned())
+        .chain(runtime_registry().read().unwrap().keys().cloned())
         .collect()
 }
 
-pub fn get_supplier(name: &str) -> Result<AllContentSuppliers, anyhow::Error> {
-    AllContentSuppliers::from_str(name).map_err(|err| err.into())
+pub fn get_supplier(name: &str) -> SupplierResult<&'static AllContentSuppliers> {
+    if let Some(cell) = registry().get(name) {
+        return Ok(cell.get_or_init(|| {
+            let sup = AllContentSuppliers::from_str(name)
+                .expect("registry contains only known suppliers");
+            register_mirrors(&sup);
+            sup
+        }));
+    }
+
+    runtime_registry()
+        .read()
+        .unwrap()
+        .get(name)
+        .copied()
+        .ok_or_else(|| SupplierError::invalid_params(format!("unknown supplier: {name}")))
+}
+
+/// Add supplier built from definition, replaces previously registered one with same name
+fn register_supplier(supplier: DeclarativeContentSupplier) -> SupplierResult<String> {
+    check_not_built_in(supplier.name())?;
+    Ok(insert_runtime_supplier(supplier))
+}
+
+fn check_not_built_in(name: &str) -> SupplierResult<()> {
+    if registry().contains_key(name) {
+        return Err(SupplierError::invalid_params(format!(
+            "supplier {name} is built in"
+        )));
+    }
+    Ok(())
+}
+
+fn insert_runtime_supplier(supplier: DeclarativeContentSupplier) -> String {
+    let name = supplier.name().to_owned();
+    let mut runtime_registry = runtime_registry().write().unwrap();
+
+    // host app registers same definitions on every start or directory rescan
+    if let Some(AllContentSuppliers::DeclarativeContentSupplier(registered)) =
+        runtime_registry.get(&name)
+        && registered.definition() == supplier.definition()
+    {
+        return name;
+    }
+
+    let supplier: &'static AllContentSuppliers = Box::leak(Box::new(supplier.into()));
+    register_mirrors(supplier);
+    runtime_registry.insert(name.clone(), supplier);
+
+    name
+}
+
+// sites without mirrors are left unregistered, so their requests follow redirects to other hosts
+fn register_mirrors(sup: &AllContentSuppliers) {
+    let site_mirrors = sup.get_mirrors();
+    if site_mirrors.is_empty() {
+        return;
+    }
+
+    if let Err(err) = mirrors::register_mirrors(&sup.get_home_url(), &site_mirrors) {
+        warn!(
+            "[mirrors] {} mirrors are ignored: {err}",
+            sup.get_display_name()
+        );
+    }
+}
+
+/// Mirrors of supplier site in failover order, home url first unless overridden
+pub fn get_supplier_mirrors(name: &str) -> SupplierResult<Vec<String>> {
+    let sup = get_supplier(name)?;
+    Ok(mirrors::mirrors(&sup.get_home_url()))
+}
+
+/// Replace mirrors of supplier site, empty list restores supplier defaults
+pub fn set_supplier_mirrors(name: &str, mirrors: &[String]) -> SupplierResult<()> {
+    let sup = get_supplier(name)?;
+    mirrors::set_mirrors(&sup.get_home_url(), mirrors)
+}
+
+/// Route requests to supplier site and its mirrors (with subdomains) through own proxy,
+/// hosts of extractors used by supplier keep default proxy
+pub fn set_supplier_proxy(name: &str, supplier_proxy: &SupplierProxy) -> SupplierResult<()> {
+    let sup = get_supplier(name)?;
+    let canonical = sup.get_home_url();
+
+    match supplier_proxy {
+        SupplierProxy::Default => {
+            proxy::reset_site(&canonical);
+            Ok(())
+        }
+        SupplierProxy::Direct => proxy::set_site(&canonical, None),
+        SupplierProxy::Proxy { config } => proxy::set_site(&canonical, Some(config)),
+    }
+}
+
+/// Set or rotate secret of supplier, empty value removes it
+pub fn set_supplier_credential(name: &str, credential: &str, value: &str) -> SupplierResult<()> {
+    let sup = get_supplier(name)?;
+    if !sup.get_credentials().iter().any(|c| c == credential) {
+        return Err(SupplierError::invalid_params(format!(
+            "supplier {name} has no credential {credential}"
+        )));
+    }
+
+    credentials::set(name, credential, value);
+    Ok(())
+}
+
+/// Remove all secrets of supplier
+pub fn clear_supplier_credentials(name: &str) -> SupplierResult<()> {
+    get_supplier(name)?;
+    credentials::clear(name);
+    Ok(())
+}
+
+fn find_setting(
+    sup: &AllContentSuppliers,
+    name: &str,
+    key: &str,
+) -> SupplierResult<SupplierSetting> {
+    sup.get_settings()
+        .into_iter()
+        .find(|s| s.key == key)
+        .ok_or_else(|| {
+            SupplierError::invalid_params(format!("supplier {name} has no setting {key}"))
+        })
+}
+
+/// Current values of supplier setting, defaults when user didn't change it
+pub fn get_supplier_setting(name: &str, key: &str) -> SupplierResult<Vec<String>> {
+    let setting = find_setting(get_supplier(name)?, name, key)?;
+    Ok(settings::values(name, &setting))
+}
+
+/// Validate and store values of supplier setting, empty values restore default
+pub fn set_supplier_setting(name: &str, key: &str, values: Vec<String>) -> SupplierResult<()> {
+    let setting = find_setting(get_supplier(name)?, name, key)?;
+    settings::set(name, &setting, values)
+}
+
+/// Restore defaults of all supplier settings
+pub fn reset_supplier_settings(name: &str) -> SupplierResult<()> {
+    get_supplier(name)?;
+    settings::reset(name);
+    Ok(())
+}
+
+/// Parse supplier definition (json or toml) and register it
+pub fn register_supplier_definition(source: &str) -> SupplierResult<String> {
+    register_supplier(declarative::parse(source)?)
+}
+
+/// Register all definitions from directory, fails without registering anything on first invalid
+pub fn load_supplier_definitions(dir: &str) -> SupplierResult<Vec<String>> {
+    let suppliers = declarative::load_dir(dir)?;
+    for supplier in &suppliers {
+        check_not_built_in(supplier.name())?;
+    }
+
+    Ok(suppliers.into_iter().map(insert_runtime_supplier).collect())
+}
+
+pub fn get_supplier_info(name: &str) -> SupplierResult<SupplierInfo> {
+    let sup = get_supplier(name)?;
+
+    Ok(SupplierInfo {
+        name: name.to_owned(),
+        display_name: sup.get_display_name(),
+        home_url: sup.get_home_url(),
+        icon_url: sup.get_icon_url(),
+        channels: sup.get_channels(),
+        default_channels: sup.get_default_channels(),
+        supported_types: sup.get_supported_types(),
+        supported_languages: sup.get_supported_languages(),
+        search_paging: sup.supports_search_paging(),
+        channel_paging: sup.supports_channel_paging(),
+        manga_pages: AllMangaPagesLoaders::try_from(sup).is_ok(),
+        hls_proxy: sup.needs_hls_proxy(),
+        credentials: sup.get_credentials(),
+        settings: sup.get_settings(),
+    })
+}
+
+pub fn get_manga_pages_loader(name: &str) -> SupplierResult<AllMangaPagesLoaders<'static>> {
+    get_supplier(name)?.try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_build_supplier_once() {
+        let first = get_supplier("UAKinoClub").unwrap();
+        let second = get_supplier("UAKinoClub").unwrap();
+        assert!(std::ptr::eq(first, second));
+    }
+
+    #[test]
+    fn should_share_manga_loader_with_supplier() {
+        let supplier = get_supplier("MangaDex").unwrap();
+        let loader = get_manga_pages_loader("MangaDex").unwrap();
+
+        match (supplier, loader) {
+            (
+                AllContentSuppliers::MangaDexContentSupplier(s),
+                AllMangaPagesLoaders::MangaDexContentSupplier(l),
+            ) => assert!(std::ptr::eq(s, l)),
+            _ => panic!("unexpected supplier variant"),
+        }
+    }
+
+    #[test]
+    fn should_describe_supplier() {
+        let info = get_supplier_info("MangaDex").unwrap();
+        assert_eq!(info.icon_url, "https://mangadex.org/favicon.ico");
+        assert!(info.manga_pages);
+        assert!(info.search_paging);
+
+        let info = get_supplier_info("Anizone").unwrap();
+        assert!(!info.manga_pages);
+        assert!(!info.channel_paging);
+    }
+
+    #[test]
+    fn should_reject_unknown_supplier_and_missing_loader() {
+        assert!(matches!(
+            get_supplier("Unknown"),
+            Err(SupplierError::InvalidParams { .. })
+        ));
+        assert!(matches!(
+            get_manga_pages_loader("UAFlix"),
+            Err(SupplierError::Unsupported { .. })
+        ));
+    }
+
+    #[test]
+    fn should_expose_and_validate_settings() {
+        let info = get_supplier_info("MangaDex").unwrap();
+        let ratings = info
+            .settings
+            .iter()
+            .find(|s| s.key == "content_ratings")
+            .unwrap();
+        assert_eq!(ratings.default_values, ratings.allowed_values);
+
+        assert!(matches!(
+            set_supplier_setting("MangaDex", "unknown", vec!["safe".into()]),
+            Err(SupplierError::InvalidParams { .. })
+        ));
+        assert!(matches!(
+            set_supplier_setting("TMDB", "language", vec!["xx-XX".into()]),
+            Err(SupplierError::InvalidParams { .. })
+        ));
+
+        set_supplier_setting("Anikoto", "subtitle_languages", vec!["uk".into()]).unwrap();
+        assert_eq!(
+            get_supplier_setting("Anikoto", "subtitle_languages").unwrap(),
+            vec!["uk"]
+        );
+        reset_supplier_settings("Anikoto").unwrap();
+        assert_eq!(
+            get_supplier_setting("Anikoto", "subtitle_languages").unwrap(),
+            vec!["en", "uk", "ja"]
+        );
+    }
+
+    #[test]
+    fn should_accept_only_declared_credentials() {
+        let info = get_supplier_info("TMDB").unwrap();
+        assert_eq!(info.credentials, vec!["api_key"]);
+
+        assert!(matches!(
+            set_supplier_credential("TMDB", "token", "secret"),
+            Err(SupplierError::InvalidParams { .. })
+        ));
+        assert!(matches!(
+            set_supplier_credential("UAFlix", "api_key", "secret"),
+            Err(SupplierError::InvalidParams { .. })
+        ));
+    }
 }
@@ -0,0 +1,314 @@
+use std::{collections::HashMap, future::Future};
+
+use futures::StreamExt;
+
+use crate::{
+    errors::SupplierResult,
+    models::{ContentInfo, ContentType, SearchAllEvent, SearchAllItem, SearchFilters},
+    utils::text,
+};
+
+use super::{AllContentSuppliers, ContentSupplier, avalaible_suppliers, get_supplier};
+
+const SEARCH_CONCURRENCY: usize = 4;
+
+/// Search in all matching suppliers at once, results are pushed per supplier as soon as it answers
+pub async fn search_all(query: &str, filters: &SearchFilters, on_event: impl Fn(SearchAllEvent)) {
+    // futures are created upfront, stream only limits how many of them run at once
+    let searches: Vec<_> = select_suppliers(filters)
+        .into_iter()
+        .map(|(name, sup)| {
+            let types = sup.get_supported_types();
+            (name, types, AllContentSuppliers::search(sup, query, 1))
+        })
+        .collect();
+
+    merge_results(searches, on_event).await;
+}
+
+async fn merge_results<F>(
+    searches: Vec<(String, Vec<ContentType>, F)>,
+    on_event: impl Fn(SearchAllEvent),
+) where
+    F: Future<Output = SupplierResult<Vec<ContentInfo>>>,
+{
+    let mut titles = TitlesIndex::default();
+
+    let searches = searches
+        .into_iter()
+        .map(|(name, types, search)| async move { (name, types, search.await) });
+    let mut results = futures::stream::iter(searches).buffer_unordered(SEARCH_CONCURRENCY);
+
+    while let Some((supplier, types, res)) = results.next().await {
+        let items = match res {
+            Ok(items) => items,
+            Err(err) => {
+                on_event(SearchAllEvent::Failed {
+                    supplier,
+                    error: err.to_string(),
+                });
+                continue;
+            }
+        };
+
+        let mut unique = vec![];
+        for info in items {
+            match titles.find_or_insert(&supplier, &types, &info) {
+                Some((original_supplier, original_id)) => on_event(SearchAllEvent::Duplicate {
+                    item: SearchAllItem {
+                        supplier: supplier.clone(),
+                        info,
+                    },
+                    original_supplier,
+                    original_id,
+                }),
+                None => unique.push(SearchAllItem {
+                    supplier: supplier.clone(),
+                    info,
+                }),
+            }
+        }
+
+        if !unique.is_empty() {
+            on_event(SearchAllEvent::Results {
+                supplier,
+                items: unique,
+            });
+        }
+    }
+
+    on_event(SearchAllEvent::Completed);
+}
+
+fn select_suppliers(filters: &SearchFilters) -> Vec<(String, &'static AllContentSuppliers)> {
+    let names = if filters.suppliers.is_empty() {
+        avalaible_suppliers()
+    } else {
+        filters.suppliers.clone()
+    };
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let sup = get_supplier(&name).ok()?;
+
+            let type_matches = filters.content_types.is_empty()
+                || sup
+                    .get_supported_types()
+                    .iter()
+                    .any(|t| filters.content_types.contains(t));
+
+            let lang_matches = filters.languages.is_empty()
+                || sup
+                    .get_supported_languages()
+                    .iter()
+                    .any(|l| filters.languages.contains(l));
+
+            (type_matches && lang_matches).then_some((name, sup))
+        })
+        .collect()
+}
+
+struct IndexedTitle {
+    year: Option<u16>,
+    types: Vec<ContentType>,
+    supplier: String,
+    id: String,
+}
+
+/// Normalized title to first supplier that returned it
+#[derive(Default)]
+struct TitlesIndex {
+    titles: HashMap<String, Vec<IndexedTitle>>,
+}
+
+impl TitlesIndex {
+    /// Titles match when release years are equal (if both are known)
+    /// and suppliers have common content type, so manga never hides same named anime
+    fn find_or_insert(
+        &mut self,
+        supplier: &str,
+        types: &[ContentType],
+        info: &ContentInfo,
+    ) -> Option<(String, String)> {
+        // secondary title is often generic (original name, alt spelling) and is only used for year
+        let key = text::normalize_title(&info.title);
+        if key.is_empty() {
+            return None;
+        }
+
+        let year = text::extract_year(&info.title)
+            .or_else(|| info.secondary_title.as_deref().and_then(text::extract_year));
+
+        let found = self.titles.get(&key).and_then(|indexed| {
+            indexed.iter().find(|t| {
+                t.supplier != supplier
+                    && t.types.iter().any(|t| types.contains(t))
+                    && match (t.year, year) {
+                        (Some(a), Some(b)) => a == b,
+                        _ => true,
+                    }
+            })
+        });
+
+        if let Some(original) = found {
+            return Some((original.supplier.clone(), original.id.clone()));
+        }
+
+        self.titles.entry(key).or_default().push(IndexedTitle {
+            year,
+            types: types.to_vec(),
+            supplier: supplier.to_owned(),
+            id: info.id.clone(),
+        });
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::errors::SupplierError;
+
+    use super::*;
+
+    async fn stub(res: SupplierResult<Vec<ContentInfo>>) -> SupplierResult<Vec<ContentInfo>> {
+        res
+    }
+
+    fn info(id: &str, title: &str, secondary_title: Option<&str>) -> ContentInfo {
+        ContentInfo {
+            id: id.into(),
+            title: title.into(),
+            secondary_title: secondary_title.map(String::from),
+            image: String::new(),
+        }
+    }
+
+    const MOVIES: &[ContentType] = &[ContentType::Movie, ContentType::Series];
+    const MANGA: &[ContentType] = &[ContentType::Manga];
+
+    #[test]
+    fn should_match_title_across_suppliers() {
+        let mut index = TitlesIndex::default();
+
+        assert_eq!(
+            index.find_or_insert(
+                "UAKinoClub",
+                MOVIES,
+                &info("1", "Dune", Some("Дюна (2021)"))
+            ),
+            None
+        );
+        assert_eq!(
+            index.find_or_insert("TMDB", MOVIES, &info("movie/438631", "Dune: 2021", None)),
+            Some(("UAKinoClub".into(), "1".into()))
+        );
+        // other release year is different title
+        assert_eq!(
+            index.find_or_insert("TMDB", MOVIES, &info("movie/841", "Dune (1984)", None)),
+            None
+        );
+    }
+
+    #[test]
+    fn should_not_match_by_secondary_title() {
+        let mut index = TitlesIndex::default();
+
+        assert_eq!(
+            index.find_or_insert("UAKinoClub", MOVIES, &info("1", "Воно", Some("It"))),
+            None
+        );
+        assert_eq!(
+            index.find_or_insert("UASerial", MOVIES, &info("2", "Це", Some("It"))),
+            None
+        );
+    }
+
+    #[test]
+    fn should_not_match_other_content_type() {
+        let mut index = TitlesIndex::default();
+
+        assert_eq!(
+            index.find_or_insert("AniTube", &[ContentType::Anime], &info("1", "Naruto", None)),
+            None
+        );
+        assert_eq!(
+            index.find_or_insert("MangaDex", MANGA, &info("2", "Naruto", None)),
+            None
+        );
+    }
+
+    #[test]
+    fn should_keep_titles_from_same_supplier() {
+        let mut index = TitlesIndex::default();
+
+        assert_eq!(
+            index.find_or_insert("AniTube", &[ContentType::Anime], &info("1", "Naruto", None)),
+            None
+        );
+        assert_eq!(
+            index.find_or_insert("AniTube", &[ContentType::Anime], &info("2", "Naruto", None)),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn should_merge_results_of_all_suppliers() {
+        let searches = vec![
+            (
+                "MangaDex".to_owned(),
+                MANGA.to_vec(),
+                stub(Ok(vec![info("md-1", "Frieren", None)])),
+            ),
+            (
+                "WeebCentral".to_owned(),
+                MANGA.to_vec(),
+                stub(Ok(vec![
+                    info("wc-1", "Frieren", None),
+                    info("wc-2", "Frieren: Side Stories", None),
+                ])),
+            ),
+            (
+                "MangaFire".to_owned(),
+                MANGA.to_vec(),
+                stub(Err(SupplierError::Timeout)),
+            ),
+        ];
+
+        let events = Mutex::new(vec![]);
+        merge_results(searches, |event| {
+            let event = match event {
+                SearchAllEvent::Results { supplier, items } => {
+                    let ids: Vec<_> = items.into_iter().map(|item| item.info.id).collect();
+                    format!("{supplier} {ids:?}")
+                }
+                SearchAllEvent::Duplicate {
+                    item,
+                    original_supplier,
+                    original_id,
+                } => format!(
+                    "{} {} duplicates {original_supplier} {original_id}",
+                    item.supplier, item.info.id
+                ),
+                SearchAllEvent::Failed { supplier, .. } => format!("{supplier} failed"),
+                SearchAllEvent::Completed => "completed".to_owned(),
+            };
+            events.lock().unwrap().push(event);
+        })
+        .await;
+
+        assert_eq!(
+            events.into_inner().unwrap(),
+            [
+                r#"MangaDex ["md-1"]"#,
+                "WeebCentral wc-1 duplicates MangaDex md-1",
+                r#"WeebCentral ["wc-2"]"#,
+                "MangaFire failed",
+                "completed",
+            ]
+        );
+    }
+}
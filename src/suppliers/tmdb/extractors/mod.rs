@@ -8,18 +8,22 @@ mod vidrock;
 mod vidup;
 mod vidzee;
 
-use std::time;
-
 use futures::future::BoxFuture;
-use log::{info, warn};
 use serde::{Deserialize, Serialize};
 
-use crate::models::ContentMediaItemSource;
+use crate::{
+    errors::{SupplierError, SupplierResult},
+    models::ContentMediaItemSource,
+    utils::{
+        params::{self, LegacyParams, MediaParams},
+        sources_sink::SourcesSink,
+    },
+};
 
 type BoxExtractor =
     for<'a> fn(&'a SourceParams) -> BoxFuture<'a, anyhow::Result<Vec<ContentMediaItemSource>>>;
 
-const EXTRACTORS: [(&str, BoxExtractor); 8] = [
+const EXTRACTORS: [(&str, BoxExtractor); 7] = [
     ("vidup", vidup::extract_boxed),
     ("vidcore", vidcore::extract_boxed),
     ("vidfast", vidfast::extract_boxed),
@@ -27,30 +31,22 @@ const EXTRACTORS: [(&str, BoxExtractor); 8] = [
     ("vidrock", vidrock::extract_boxed),
     ("vidlink", vidlink::extract_boxed),
     ("two_embed", two_embed::extract_boxed),
-    ("open_subs", open_subs::extract_boxed),
 ];
 
-pub async fn run_extractors(params: &SourceParams) -> Vec<ContentMediaItemSource> {
-    let etractors_itr = EXTRACTORS.into_iter().map(|(name, f)| async move {
-        let start_ts = time::Instant::now();
-        let res = match f(params).await {
-            Ok(r) => r,
-            Err(err) => {
-                warn!("[tmdb] extractor '{name}' failed: {err}");
-                vec![]
-            }
-        };
-        let end_ts = time::Instant::now();
-        let duration = end_ts.duration_since(start_ts);
-        info!("[tmdb] extractor '{name}' finished in {duration:?}");
-        res
-    });
-
-    futures::future::join_all(etractors_itr)
-        .await
+pub async fn run_extractors(params: &SourceParams, subtitle_langs: &[String], sink: &SourcesSink) {
+    let tasks = EXTRACTORS
         .into_iter()
-        .flatten()
-        .collect()
+        .map(|(name, f)| (name, f(params)))
+        .chain([(
+            "open_subs",
+            open_subs::extract_boxed(params, subtitle_langs),
+        )])
+        .map(|(name, extract)| {
+            let task = async move { Ok(extract.await?) };
+            (name.to_owned(), task)
+        });
+
+    sink.fan_out(tasks).await
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -67,3 +63,52 @@ pub struct SourceParams {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ep: Option<Episode>,
 }
+
+impl MediaParams for SourceParams {
+    const KIND: &'static str = "tmdb_sources";
+    const VERSION: u32 = 2;
+
+    // v1 is bare SourceParams json
+    fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+        match legacy {
+            LegacyParams::Raw(raw) => {
+                let json = params::required(raw, 0, "source params")?;
+                Ok(serde_json::from_str(json)?)
+            }
+            LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(format!(
+                "tmdb source params version {version} is not supported"
+            ))),
+        }
+    }
+
+    fn validate(&self) -> SupplierResult<()> {
+        if self.id == 0 {
+            return Err(SupplierError::invalid_params("tmdb id expected"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::models::ContentMediaItemSource;
+
+    /// Cassettes of all extractors are kept in `fixtures/tmdb/`
+    pub const FIXTURES: &str = "tmdb";
+
+    /// Description and link of each source in extractor order
+    pub fn links(sources: &[ContentMediaItemSource]) -> Vec<(&str, &str)> {
+        sources
+            .iter()
+            .map(|source| match source {
+                ContentMediaItemSource::Video {
+                    description, link, ..
+                }
+                | ContentMediaItemSource::Subtitle {
+                    description, link, ..
+                } => (description.as_str(), link.as_str()),
+                ContentMediaItemSource::Manga { .. } => panic!("video or subtitle expected"),
+            })
+            .collect()
+    }
+}
@@ -7,17 +7,17 @@ use super::SourceParams;
 
 const STREIO_URL: &str = "https://opensubtitles.stremio.homes";
 
-pub fn extract_boxed<'a>(params: &'a SourceParams) -> BoxFuture<'a, anyhow::Result<Vec<ContentMediaItemSource>>> {
-    Box::pin(extract(params))
+pub fn extract_boxed<'a>(params: &'a SourceParams, subtitle_langs: &'a [String]) -> BoxFuture<'a, anyhow::Result<Vec<ContentMediaItemSource>>> {
+    Box::pin(extract(params, subtitle_langs))
 }
 
-pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaItemSource>> {
+pub async fn extract(params: &SourceParams, subtitle_langs: &[String]) -> anyhow::Result<Vec<ContentMediaItemSource>> {
     let imdb_id = match &params.imdb_id {
         Some(v) => v,
         None => return Ok(vec![]),
     };
 
-    let langs_str = "en|uk";
+    let langs_str = subtitle_langs.join("|");
     let base_url =
         format!("{STREIO_URL}/{langs_str}/ai-translated=true|from=all|Cauto-adjustment=true");
 
@@ -30,10 +30,7 @@ pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaIt
         None => format!("{base_url}/subtitles/movie/{imdb_id}.json"),
     };
 
-    let res_str = utils::create_client_builder()
-        .build()?
-        .get(link)
-        .send()
+    let res_str = utils::send(utils::create_client_builder().build()?.get(link))
         .await?
         .text()
         .await?;
@@ -77,34 +74,63 @@ pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaIt
 
 #[cfg(test)]
 mod tests {
-    use crate::suppliers::tmdb::extractors::Episode;
+    use crate::{
+        suppliers::tmdb::extractors::{
+            Episode,
+            tests::{FIXTURES, links},
+        },
+        utils::golden,
+    };
 
     use super::*;
+
     #[tokio::test]
     async fn should_extract_movie() {
-        let res = extract(
-            &SourceParams {
-                id: 280,
-                imdb_id: Some("tt0103064".to_owned()),
-                ep: None,
-            }
+        let res = golden::cassette(
+            FIXTURES,
+            "open_subs_movie",
+            extract(
+                &SourceParams {
+                    id: 280,
+                    imdb_id: Some("tt0103064".to_owned()),
+                    ep: None,
+                },
+                &["en".into(), "uk".into()],
+            ),
         )
-        .await;
-
-        println!("{res:#?}")
+        .await
+        .unwrap();
+
+        assert_eq!(
+            links(&res),
+            vec![
+                ("[open sub] 1. eng", "https://opensubtitles.stremio.homes/sub/1954781035.srt"),
+                ("[open sub] 2. eng", "https://opensubtitles.stremio.homes/sub/1954633010.srt"),
+                ("[open sub] 3. ukr (AI)", "https://opensubtitles.stremio.homes/sub/1956298143.srt"),
+            ]
+        );
     }
 
     #[tokio::test]
     async fn should_extract_tv() {
-        let res = extract(
-            &SourceParams {
-                id: 655,
-                imdb_id: Some("tt0092455".to_owned()),
-                ep: Some(Episode { e: 1, s: 1 }),
-            }
+        let res = golden::cassette(
+            FIXTURES,
+            "open_subs_tv",
+            extract(
+                &SourceParams {
+                    id: 655,
+                    imdb_id: Some("tt0092455".to_owned()),
+                    ep: Some(Episode { e: 1, s: 1 }),
+                },
+                &["en".into(), "uk".into()],
+            ),
         )
-        .await;
+        .await
+        .unwrap();
 
-        println!("{res:#?}")
+        assert_eq!(
+            links(&res),
+            vec![("[open sub] 1. eng", "https://opensubtitles.stremio.homes/sub/1952104477.srt")]
+        );
     }
 }
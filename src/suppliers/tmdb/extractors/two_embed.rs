@@ -33,10 +33,7 @@ pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaIt
         None => format!("{URL}/embed/{id}"),
     };
 
-    let res = utils::create_client()
-        .get(&url)
-        .header("Referer", &url)
-        .send()
+    let res = utils::send(utils::create_client().get(&url).header("Referer", &url))
         .await?
         .text()
         .await?;
@@ -65,16 +62,40 @@ pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaIt
 
 #[cfg(test)]
 mod test {
+    use crate::{
+        suppliers::tmdb::extractors::tests::{FIXTURES, links},
+        utils::golden,
+    };
+
     use super::*;
 
     #[tokio::test]
     async fn two_embed_should_load_movie() {
-        let res = extract(&SourceParams {
-            id: 0,
-            imdb_id: Some("tt0088247".to_string()),
-            ep: None,
-        })
-        .await;
-        println!("{res:#?}")
+        let res = golden::cassette(
+            FIXTURES,
+            "two_embed_movie",
+            extract(&SourceParams {
+                id: 0,
+                imdb_id: Some("tt0088247".to_string()),
+                ep: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        // relative links are resolved against player host
+        assert_eq!(
+            links(&res),
+            vec![
+                (
+                    "Two Embed 4.",
+                    "https://uqloads.xyz/stream/Kx3mPq9TzW/hls4/master.m3u8"
+                ),
+                (
+                    "Two Embed 2.",
+                    "https://be6721.rcr72.waw04.i8yz83pn.com/hls2/01/02326/n1kbhx78xlja_o/master.m3u8?t=LmJXV3Da&s=1734702290&e=129600"
+                ),
+            ]
+        );
     }
 }
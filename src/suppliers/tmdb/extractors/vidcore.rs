@@ -63,7 +63,10 @@ struct Track {
 async fn vidcore_enc(text: &str) -> anyhow::Result<EncResult> {
     let url = format!("{ENC_DEC_APP_URL}/api/enc-vidcore?text={text}");
 
-    let res_str = create_json_client().get(url).send().await?.text().await?;
+    let res_str = utils::send(create_json_client().get(url))
+        .await?
+        .text()
+        .await?;
 
     let res: EncResponse = serde_json::from_str(&res_str)?;
 
@@ -73,15 +76,12 @@ async fn vidcore_enc(text: &str) -> anyhow::Result<EncResult> {
 async fn vidcore_dec_servers(text: &str) -> anyhow::Result<Vec<Server>> {
     let url = format!("{ENC_DEC_APP_URL}/api/dec-vidcore");
 
-    let res_str = create_json_client()
-        .post(url)
-        .json(&DecRequest {
-            text: text.to_string(),
-        })
-        .send()
-        .await?
-        .text()
-        .await?;
+    let res_str = utils::send(create_json_client().post(url).json(&DecRequest {
+        text: text.to_string(),
+    }))
+    .await?
+    .text()
+    .await?;
 
     let res: DecResponse<Vec<Server>> = serde_json::from_str(&res_str)?;
 
@@ -91,15 +91,12 @@ async fn vidcore_dec_servers(text: &str) -> anyhow::Result<Vec<Server>> {
 async fn vidcore_dec_stream(text: &str) -> anyhow::Result<StreamResult> {
     let url = format!("{ENC_DEC_APP_URL}/api/dec-vidcore");
 
-    let res_str = create_json_client()
-        .post(url)
-        .json(&DecRequest {
-            text: text.to_string(),
-        })
-        .send()
-        .await?
-        .text()
-        .await?;
+    let res_str = utils::send(create_json_client().post(url).json(&DecRequest {
+        text: text.to_string(),
+    }))
+    .await?
+    .text()
+    .await?;
 
     let res: DecResponse<StreamResult> = serde_json::from_str(&res_str)?;
 
@@ -124,13 +121,14 @@ pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaIt
     };
 
     let client = utils::create_client();
-    let page_html = client
-        .get(&page_url)
-        .header("Referer", format!("{VIDCORE_URL}/"))
-        .send()
-        .await?
-        .text()
-        .await?;
+    let page_html = utils::send(
+        client
+            .get(&page_url)
+            .header("Referer", format!("{VIDCORE_URL}/")),
+    )
+    .await?
+    .text()
+    .await?;
 
     // Extract encrypted text from page
     let text = extract_text(&page_html)?;
@@ -143,15 +141,16 @@ pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaIt
     let stream_url = enc_result.stream;
 
     // POST to servers URL with CSRF token
-    let servers_encrypted = client
-        .post(&servers_url)
-        .header("Referer", format!("{VIDCORE_URL}/"))
-        .header("X-Requested-With", "XMLHttpRequest")
-        .header("X-CSRF-Token", &token)
-        .send()
-        .await?
-        .text()
-        .await?;
+    let servers_encrypted = utils::send(
+        client
+            .post(&servers_url)
+            .header("Referer", format!("{VIDCORE_URL}/"))
+            .header("X-Requested-With", "XMLHttpRequest")
+            .header("X-CSRF-Token", &token),
+    )
+    .await?
+    .text()
+    .await?;
 
     // Decrypt servers list
     let servers = vidcore_dec_servers(&servers_encrypted).await?;
@@ -200,15 +199,16 @@ async fn load_server_stream(
 ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
     // POST to stream/{data} to get encrypted stream
     let url = format!("{stream_url}/{data}");
-    let stream_encrypted = client
-        .post(&url)
-        .header("Referer", format!("{VIDCORE_URL}/"))
-        .header("X-Requested-With", "XMLHttpRequest")
-        .header("X-CSRF-Token", token)
-        .send()
-        .await?
-        .text()
-        .await?;
+    let stream_encrypted = utils::send(
+        client
+            .post(&url)
+            .header("Referer", format!("{VIDCORE_URL}/"))
+            .header("X-Requested-With", "XMLHttpRequest")
+            .header("X-CSRF-Token", token),
+    )
+    .await?
+    .text()
+    .await?;
 
     // Decrypt stream data
     let stream_data = vidcore_dec_stream(&stream_encrypted).await?;
@@ -246,29 +246,85 @@ async fn load_server_stream(
 
 #[cfg(test)]
 mod tests {
-    use crate::suppliers::tmdb::extractors::Episode;
+    use crate::{
+        suppliers::tmdb::extractors::{
+            Episode,
+            tests::{FIXTURES, links},
+        },
+        utils::golden,
+    };
 
     use super::*;
 
     #[test_log::test(tokio::test)]
     async fn vidcore_should_extract_tv() {
-        let res = extract(&SourceParams {
-            id: 604,
-            imdb_id: None,
-            ep: Some(Episode { s: 1, e: 1 }),
-        })
-        .await;
-        println!("{res:#?}")
+        let res = golden::cassette(
+            FIXTURES,
+            "vidcore_tv",
+            extract(&SourceParams {
+                id: 604,
+                imdb_id: None,
+                ep: Some(Episode { s: 1, e: 1 }),
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            links(&res),
+            vec![(
+                "[VidCore] Server 1",
+                "https://fsl.hlsplay.xyz/v4/tv/604/1/1/tV9a1x/master.m3u8"
+            )]
+        );
     }
 
     #[test_log::test(tokio::test)]
     async fn vidcore_should_extract_movie() {
-        let res = extract(&SourceParams {
-            id: 533535,
-            imdb_id: None,
-            ep: None,
-        })
-        .await;
-        println!("{res:#?}")
+        let res = golden::cassette(
+            FIXTURES,
+            "vidcore_movie",
+            extract(&SourceParams {
+                id: 533535,
+                imdb_id: None,
+                ep: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            links(&res),
+            vec![
+                (
+                    "[VidCore] Server 1",
+                    "https://fsl.hlsplay.xyz/v4/mv/533535/aL1p4a/master.m3u8"
+                ),
+                (
+                    "[VidCore] English",
+                    "https://fsl.hlsplay.xyz/subs/533535/eng.vtt"
+                ),
+                (
+                    "[VidCore] Ukrainian",
+                    "https://fsl.hlsplay.xyz/subs/533535/ukr.vtt"
+                ),
+                (
+                    "[VidCore] Server 2",
+                    "https://fsl.hlsplay.xyz/v4/mv/533535/bE2t4b/index.m3u8"
+                ),
+            ]
+        );
+        // referer is sent unless server allows to drop it
+        assert!(matches!(
+            &res[0],
+            ContentMediaItemSource::Video {
+                headers: Some(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            &res[3],
+            ContentMediaItemSource::Video { headers: None, .. }
+        ));
     }
 }
@@ -64,7 +64,10 @@ struct Track {
 async fn vidfast_enc(text: &str) -> anyhow::Result<EncResult> {
     let url = format!("{ENC_DEC_APP_URL}/api/enc-vidfast?text={text}&version=1");
 
-    let res_str = create_json_client().get(url).send().await?.text().await?;
+    let res_str = utils::send(create_json_client().get(url))
+        .await?
+        .text()
+        .await?;
 
     let res: EncResponse = serde_json::from_str(&res_str)?;
 
@@ -74,16 +77,13 @@ async fn vidfast_enc(text: &str) -> anyhow::Result<EncResult> {
 async fn vidfast_dec_servers(text: &str) -> anyhow::Result<Vec<Server>> {
     let url = format!("{ENC_DEC_APP_URL}/api/dec-vidfast");
 
-    let res_str = create_json_client()
-        .post(url)
-        .json(&VidFastRequest {
-            text: text.to_string(),
-            version: "1".to_string(),
-        })
-        .send()
-        .await?
-        .text()
-        .await?;
+    let res_str = utils::send(create_json_client().post(url).json(&VidFastRequest {
+        text: text.to_string(),
+        version: "1".to_string(),
+    }))
+    .await?
+    .text()
+    .await?;
 
     let res: DecResponse<Vec<Server>> = serde_json::from_str(&res_str)?;
 
@@ -93,16 +93,13 @@ async fn vidfast_dec_servers(text: &str) -> anyhow::Result<Vec<Server>> {
 async fn vidfast_dec_stream(text: &str) -> anyhow::Result<StreamResult> {
     let url = format!("{ENC_DEC_APP_URL}/api/dec-vidfast");
 
-    let res_str = create_json_client()
-        .post(url)
-        .json(&VidFastRequest {
-            text: text.to_string(),
-            version: "1".to_string(),
-        })
-        .send()
-        .await?
-        .text()
-        .await?;
+    let res_str = utils::send(create_json_client().post(url).json(&VidFastRequest {
+        text: text.to_string(),
+        version: "1".to_string(),
+    }))
+    .await?
+    .text()
+    .await?;
 
     let res: DecResponse<StreamResult> = serde_json::from_str(&res_str)?;
 
@@ -127,13 +124,14 @@ pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaIt
     };
 
     let client = utils::create_client();
-    let page_html = client
-        .get(&page_url)
-        .header("Referer", format!("{VIDFAST_URL}/"))
-        .send()
-        .await?
-        .text()
-        .await?;
+    let page_html = utils::send(
+        client
+            .get(&page_url)
+            .header("Referer", format!("{VIDFAST_URL}/")),
+    )
+    .await?
+    .text()
+    .await?;
 
     // Extract encrypted text from page
     let text = extract_text(&page_html)?;
@@ -146,15 +144,16 @@ pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaIt
     let stream_url = enc_result.stream;
 
     // POST to servers URL with CSRF token
-    let servers_encrypted = client
-        .post(&servers_url)
-        .header("Referer", format!("{VIDFAST_URL}/"))
-        .header("X-Requested-With", "XMLHttpRequest")
-        .header("X-CSRF-Token", &token)
-        .send()
-        .await?
-        .text()
-        .await?;
+    let servers_encrypted = utils::send(
+        client
+            .post(&servers_url)
+            .header("Referer", format!("{VIDFAST_URL}/"))
+            .header("X-Requested-With", "XMLHttpRequest")
+            .header("X-CSRF-Token", &token),
+    )
+    .await?
+    .text()
+    .await?;
 
     // Decrypt servers list
     let servers = vidfast_dec_servers(&servers_encrypted).await?;
@@ -203,15 +202,16 @@ async fn load_server_stream(
 ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
     // POST to stream/{data} to get encrypted stream
     let url = format!("{stream_url}/{data}");
-    let stream_encrypted = client
-        .post(&url)
-        .header("Referer", format!("{VIDFAST_URL}/"))
-        .header("X-Requested-With", "XMLHttpRequest")
-        .header("X-CSRF-Token", token)
-        .send()
-        .await?
-        .text()
-        .await?;
+    let stream_encrypted = utils::send(
+        client
+            .post(&url)
+            .header("Referer", format!("{VIDFAST_URL}/"))
+            .header("X-Requested-With", "XMLHttpRequest")
+            .header("X-CSRF-Token", token),
+    )
+    .await?
+    .text()
+    .await?;
 
     // Decrypt stream data
     let stream_data = vidfast_dec_stream(&stream_encrypted).await?;
@@ -251,29 +251,85 @@ async fn load_server_stream(
 
 #[cfg(test)]
 mod tests {
-    use crate::suppliers::tmdb::extractors::Episode;
+    use crate::{
+        suppliers::tmdb::extractors::{
+            Episode,
+            tests::{FIXTURES, links},
+        },
+        utils::golden,
+    };
 
     use super::*;
 
     #[test_log::test(tokio::test)]
     async fn vidfast_should_extract_tv() {
-        let res = extract(&SourceParams {
-            id: 1399,
-            imdb_id: None,
-            ep: Some(Episode { s: 1, e: 1 }),
-        })
-        .await;
-        println!("{res:#?}")
+        let res = golden::cassette(
+            FIXTURES,
+            "vidfast_tv",
+            extract(&SourceParams {
+                id: 1399,
+                imdb_id: None,
+                ep: Some(Episode { s: 1, e: 1 }),
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            links(&res),
+            vec![(
+                "[VidFast] Server 1",
+                "https://hls2.fastcdn.ws/v4/tv/1399/1/1/tV9a1x/master.m3u8"
+            )]
+        );
     }
 
     #[test_log::test(tokio::test)]
     async fn vidfast_should_extract_movie() {
-        let res = extract(&SourceParams {
-            id: 533535,
-            imdb_id: None,
-            ep: None,
-        })
-        .await;
-        println!("{res:#?}")
+        let res = golden::cassette(
+            FIXTURES,
+            "vidfast_movie",
+            extract(&SourceParams {
+                id: 533535,
+                imdb_id: None,
+                ep: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            links(&res),
+            vec![
+                (
+                    "[VidFast] Server 1",
+                    "https://hls2.fastcdn.ws/v4/mv/533535/aL1p4a/master.m3u8"
+                ),
+                (
+                    "[VidFast] English",
+                    "https://hls2.fastcdn.ws/subs/533535/eng.vtt"
+                ),
+                (
+                    "[VidFast] Ukrainian",
+                    "https://hls2.fastcdn.ws/subs/533535/ukr.vtt"
+                ),
+                (
+                    "[VidFast] Server 2",
+                    "https://hls2.fastcdn.ws/v4/mv/533535/bE2t4b/index.m3u8"
+                ),
+            ]
+        );
+        // referer is sent unless server allows to drop it
+        assert!(matches!(
+            &res[0],
+            ContentMediaItemSource::Video {
+                headers: Some(_),
+                ..
+            }
+        ));
+        assert!(matches!(
+            &res[3],
+            ContentMediaItemSource::Video { headers: None, .. }
+        ));
     }
 }
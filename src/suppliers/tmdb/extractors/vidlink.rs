@@ -18,7 +18,10 @@ const VIDLINK_API: &str = "https://vidlink.pro/api/b";
 async fn vidlink_enc(tmdb_id: &str) -> anyhow::Result<String> {
     let url = format!("{ENC_DEC_APP_URL}/api/enc-vidlink?text={tmdb_id}");
 
-    let res_str = create_json_client().get(url).send().await?.text().await?;
+    let res_str = utils::send(create_json_client().get(url))
+        .await?
+        .text()
+        .await?;
 
     let res: GenericResponse = serde_json::from_str(&res_str)?;
 
@@ -46,14 +49,15 @@ pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaIt
     };
 
     let client = utils::create_client();
-    let res_str = client
-        .get(&url)
-        .header("Origin", VIDLINK_URL)
-        .header("Referer", format!("{VIDLINK_URL}/"))
-        .send()
-        .await?
-        .text()
-        .await?;
+    let res_str = utils::send(
+        client
+            .get(&url)
+            .header("Origin", VIDLINK_URL)
+            .header("Referer", format!("{VIDLINK_URL}/")),
+    )
+    .await?
+    .text()
+    .await?;
 
     // println!("{res_str}");
 
@@ -124,29 +128,87 @@ struct VidlinkCaption {
 
 #[cfg(test)]
 mod tests {
-    use crate::suppliers::tmdb::extractors::Episode;
+    use crate::{
+        suppliers::tmdb::extractors::{
+            Episode,
+            tests::{FIXTURES, links},
+        },
+        utils::golden,
+    };
 
     use super::*;
 
     #[test_log::test(tokio::test)]
     async fn vidlink_should_extract_tv() {
-        let res = extract(&SourceParams {
-            id: 105248,
-            imdb_id: None,
-            ep: Some(Episode { s: 1, e: 1 }),
-        })
-        .await;
-        println!("{res:#?}")
+        let res = golden::cassette(
+            FIXTURES,
+            "vidlink_tv",
+            extract(&SourceParams {
+                id: 105248,
+                imdb_id: None,
+                ep: Some(Episode { s: 1, e: 1 }),
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            links(&res),
+            vec![(
+                "[Vidlink] primewire - 1080p",
+                "https://storm.vodvidl.site/proxy/file2/105248/1/1/1080.mp4"
+            )]
+        );
     }
 
     #[test_log::test(tokio::test)]
     async fn vidlink_should_extract_movie() {
-        let res = extract(&SourceParams {
-            id: 786892,
-            imdb_id: None,
-            ep: None,
-        })
-        .await;
-        println!("{res:#?}")
+        let res = golden::cassette(
+            FIXTURES,
+            "vidlink_movie",
+            extract(&SourceParams {
+                id: 786892,
+                imdb_id: None,
+                ep: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        // best quality first, labels that are not numbers go last
+        assert_eq!(
+            links(&res),
+            vec![
+                (
+                    "[Vidlink] primewire - 1080p",
+                    "https://storm.vodvidl.site/proxy/file2/786892/1080.mp4"
+                ),
+                (
+                    "[Vidlink] primewire - 720p",
+                    "https://storm.vodvidl.site/proxy/file2/786892/720.mp4"
+                ),
+                (
+                    "[Vidlink] primewire - 360p",
+                    "https://storm.vodvidl.site/proxy/file2/786892/360.mp4"
+                ),
+                (
+                    "[Vidlink] primewire - 4K",
+                    "https://storm.vodvidl.site/proxy/file2/786892/4k.mp4"
+                ),
+                (
+                    "[Vidlink] English",
+                    "https://storm.vodvidl.site/proxy/file2/786892/en.vtt"
+                ),
+                (
+                    "[Vidlink] Ukrainian",
+                    "https://storm.vodvidl.site/proxy/file2/786892/uk.vtt"
+                ),
+            ]
+        );
+        assert!(matches!(
+            &res[0],
+            ContentMediaItemSource::Video { headers: Some(headers), .. }
+                if headers["referer"] == "https://videostr.net/"
+        ));
     }
 }
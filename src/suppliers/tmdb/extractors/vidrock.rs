@@ -9,7 +9,7 @@ use serde::Deserialize;
 use crate::{
     models::ContentMediaItemSource,
     suppliers::tmdb::URL,
-    utils::{create_json_client, crypto},
+    utils::{self, create_json_client, crypto},
 };
 
 use super::SourceParams;
@@ -39,10 +39,7 @@ pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaIt
 
     let client = create_json_client();
     // println!("{link}");
-    let res_str = client
-        .get(link)
-        .header("Referer", URL)
-        .send()
+    let res_str = utils::send(client.get(link).header("Referer", URL))
         .await?
         .text()
         .await?;
@@ -100,10 +97,7 @@ async fn load_playlist(
         url: String,
     }
 
-    let res_str = client
-        .get(url)
-        .header("Referer", URL)
-        .send()
+    let res_str = utils::send(client.get(url).header("Referer", URL))
         .await?
         .text()
         .await?;
@@ -191,7 +185,15 @@ fn decrypt_url(url: &str) -> anyhow::Result<String> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{suppliers::tmdb::extractors::Episode, utils};
+    use crate::{
+        suppliers::tmdb::extractors::{
+            Episode,
+            tests::{FIXTURES, links},
+        },
+        utils::{self, golden},
+    };
+
+    use super::*;
 
     #[test]
     fn vidrock_decrypt_url1() {
@@ -199,7 +201,10 @@ mod tests {
             "WhRfCLIBMUSrMC_QD_URsp3Kyk_YErof5UXEAspf13xG_rC0aSsQd6kJA6JPQFTUtzcy9zmIoksXSIO4HraSuOPYyjPyxyiGRz2A96P9DRQZsShf9tgGVpi9EXg5qrmEKLeehOUNOBcwbn3bIyIBKDo2jk7mKSMbvqdANPUE1YizXqS8clmMm5ZWelUU3IbNUFOq5iVvUr_8RwKV",
         );
 
-        println!("{res:#?}")
+        assert_eq!(
+            res.unwrap(),
+            "https://shy-smoke-85df.xxw8bjzldt.workers.dev/file1/ZjBhMGU2ZmUzYmI3MjYzYzc3MTYwMmYzOWFjNGViZmIxNzg0MTk3NDgxODAzOTM0/master.m3u8"
+        );
     }
 
     #[test]
@@ -208,42 +213,106 @@ mod tests {
             "NZ75OJ2xASa5XUOgX31RrCciDAZu8-zQ3bHPEnLKfaas5pBzUZW3r0EjQ9toNF4n1eLw11mQaWyG0eRhX-ub_CWrWYuCsRSFQLp9IFZNrxKVowA",
         );
 
-        println!("{res:#?}")
+        assert_eq!(
+            res.unwrap(),
+            "https://streamrk.site/playlist/78f2bdd1999acb93fdfc912b"
+        );
     }
 
-    use super::*;
     #[test_log::test(tokio::test)]
     async fn vidrock_should_extract_movies() {
-        let res = extract(&SourceParams {
-            id: 533535,
-            imdb_id: None,
-            ep: None,
-        })
-        .await;
-
-        println!("{res:#?}")
+        let res = golden::cassette(
+            FIXTURES,
+            "vidrock_movie",
+            extract(&SourceParams {
+                id: 533535,
+                imdb_id: None,
+                ep: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        // servers come from a map, playlist keeps its own order
+        let mut links = links(&res);
+        links.sort();
+        assert_eq!(
+            links,
+            vec![
+                (
+                    "[Vidrocks] 1. Astra. English - 1080",
+                    "https://streamrk.site/v/78f2bdd1999acb93fdfc912b/1080/index.m3u8"
+                ),
+                (
+                    "[Vidrocks] 1. Astra. English - 360",
+                    "https://streamrk.site/v/78f2bdd1999acb93fdfc912b/360/index.m3u8"
+                ),
+                (
+                    "[Vidrocks] 1. Astra. English - 720",
+                    "https://streamrk.site/v/78f2bdd1999acb93fdfc912b/720/index.m3u8"
+                ),
+                (
+                    "[Vidrocks] Atlas. English",
+                    "https://shy-smoke-85df.xxw8bjzldt.workers.dev/file1/533535/master.m3u8"
+                ),
+            ]
+        );
     }
 
     #[test_log::test(tokio::test)]
     async fn vidrock_should_extract_tv() {
-        let res = extract(&SourceParams {
-            id: 655,
-            imdb_id: None,
-            ep: Some(Episode { e: 1, s: 1 }),
-        })
-        .await;
-
-        println!("{res:#?}")
+        let res = golden::cassette(
+            FIXTURES,
+            "vidrock_tv",
+            extract(&SourceParams {
+                id: 655,
+                imdb_id: None,
+                ep: Some(Episode { e: 1, s: 1 }),
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            links(&res),
+            vec![(
+                "[Vidrocks] Atlas. English",
+                "https://shy-smoke-85df.xxw8bjzldt.workers.dev/file1/655/1/1/master.m3u8"
+            )]
+        );
     }
 
     #[test_log::test(tokio::test)]
     async fn vidrock_playlist_load() {
-        let res = load_playlist(
-            utils::create_json_client(),
-            "https://streamrk.site/playlist/78f2bdd1999acb93fdfc912b",
-            "en",
+        let res = golden::cassette(
+            FIXTURES,
+            "vidrock_playlist",
+            load_playlist(
+                utils::create_json_client(),
+                "https://streamrk.site/playlist/78f2bdd1999acb93fdfc912b",
+                "en",
+            ),
         )
-        .await;
-        println!("{res:#?}")
+        .await
+        .unwrap();
+
+        // best quality first
+        assert_eq!(
+            links(&res),
+            vec![
+                (
+                    "[Vidrocks] 1. en - 1080",
+                    "https://streamrk.site/v/78f2bdd1999acb93fdfc912b/1080/index.m3u8"
+                ),
+                (
+                    "[Vidrocks] 1. en - 720",
+                    "https://streamrk.site/v/78f2bdd1999acb93fdfc912b/720/index.m3u8"
+                ),
+                (
+                    "[Vidrocks] 1. en - 360",
+                    "https://streamrk.site/v/78f2bdd1999acb93fdfc912b/360/index.m3u8"
+                ),
+            ]
+        );
     }
 }
@@ -12,7 +12,7 @@ use sha2::Sha256;
 use crate::{
     models::ContentMediaItemSource,
     suppliers::tmdb::extractors::SourceParams,
-    utils::{create_client, crypto},
+    utils::{self, create_client, crypto},
 };
 
 // https://player.vidzee.wtf/_next/static/chunks/app/embed/%5B%5B...params%5D%5D/page-7bcef97531d2a94a.js
@@ -31,9 +31,7 @@ pub fn extract_boxed<'a>(
 pub async fn extract(params: &SourceParams) -> anyhow::Result<Vec<ContentMediaItemSource>> {
     let client = create_client();
 
-    let api_key = client
-        .get(format!("{CORE_URL}/api-key"))
-        .send()
+    let api_key = utils::send(client.get(format!("{CORE_URL}/api-key")))
         .await?
         .text()
         .await?;
@@ -145,10 +143,7 @@ async fn load_server(
         link = format!("{}&ss={}&ep={}", link, e.s, e.e);
     }
 
-    let server_res_str = client
-        .get(link)
-        .header("Referer", PLAYER_URL)
-        .send()
+    let server_res_str = utils::send(client.get(link).header("Referer", PLAYER_URL))
         .await?
         .text()
         .await?;
@@ -237,20 +232,43 @@ fn decode_link(link: &str, pass: &[u8]) -> anyhow::Result<String> {
 
 #[cfg(test)]
 mod test {
-    use crate::suppliers::tmdb::extractors::Episode;
+    use crate::{
+        suppliers::tmdb::extractors::{
+            Episode,
+            tests::{FIXTURES, links},
+        },
+        utils::golden,
+    };
 
     use super::*;
 
     #[test_log::test(tokio::test)]
     async fn vidzee_should_load_source() {
-        let res = extract(&SourceParams {
-            // id: 655,
-            id: 1399,
-            imdb_id: None, //Some("tt18259086".into()),
-            // ep: None,
-            ep: Some(Episode { s: 1, e: 1 }),
-        })
-        .await;
-        println!("{res:#?}")
+        let res = golden::cassette(
+            FIXTURES,
+            "vidzee_tv",
+            extract(&SourceParams {
+                id: 1399,
+                imdb_id: None,
+                ep: Some(Episode { s: 1, e: 1 }),
+            }),
+        )
+        .await
+        .unwrap();
+
+        // only hls links are taken, failed and empty servers are skipped
+        assert_eq!(
+            links(&res),
+            vec![
+                (
+                    "[Vidzee] Nflim - English",
+                    "https://rapid-cloud.vdzcdn.site/hls/1399/1/1/en/master.m3u8"
+                ),
+                (
+                    "[Vidzee] Glory - Hindi",
+                    "https://glory.vdzcdn.site/tv/1399/1/1/hi/index.m3u8"
+                ),
+            ]
+        );
     }
 }
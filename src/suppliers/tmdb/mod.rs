@@ -1,13 +1,18 @@
 mod extractors;
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::{
     models::{
         ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
-        MediaType,
+        MediaType, SupplierSetting,
+    },
+    utils::{
+        self, credentials, lang,
+        params::{self, LegacyParams, MediaParams},
+        settings,
+        sources_sink::SourcesSink,
     },
-    utils::{self},
 };
-use anyhow::{Ok, anyhow};
 use extractors::{Episode, SourceParams, run_extractors};
 use indexmap::IndexMap;
 use reqwest::header;
@@ -15,7 +20,8 @@ use serde::{Deserialize, Serialize};
 
 use super::ContentSupplier;
 
-static SECRET: &str = env!("TMDB_SECRET");
+const NAME: &str = "TMDB";
+const API_KEY: &str = "api_key";
 const URL: &str = "https://api.themoviedb.org/3";
 const IMAGES_URL: &str = "http://image.tmdb.org/t/p";
 
@@ -60,20 +66,45 @@ impl ContentSupplier for TMDBContentSupplier {
         vec!["en".into()]
     }
 
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    fn get_display_name(&self) -> String {
+        "TMDB".into()
+    }
+
+    fn get_home_url(&self) -> String {
+        "https://www.themoviedb.org".into()
+    }
+
+    fn get_credentials(&self) -> Vec<String> {
+        vec![API_KEY.into()]
+    }
+
+    fn get_settings(&self) -> Vec<SupplierSetting> {
+        vec![language_setting(), subtitle_languages_setting()]
+    }
+
+    fn supports_search_paging(&self) -> bool {
+        false
+    }
+
+    fn needs_hls_proxy(&self) -> bool {
+        // vidrock streams are served through local hls proxy
+        true
+    }
+
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         if page > 1 {
             return Ok(vec![]);
         }
 
-        let res: TMDBSearchResponse = utils::create_json_client()
-            .get(format!("{URL}/search/multi"))
-            .header(header::AUTHORIZATION, format!("Bearer {SECRET}"))
-            .header(header::ACCEPT, "application/json")
-            .query(&[("query", query), ("langauge", "en-US")])
-            .send()
-            .await?
-            .json()
-            .await?;
+        let language = language();
+        let res: TMDBSearchResponse = utils::fetch_json(
+            utils::create_json_client()
+                .get(format!("{URL}/search/multi"))
+                .header(header::AUTHORIZATION, authorization()?)
+                .header(header::ACCEPT, "application/json")
+                .query(&[("query", query), ("language", language.as_str())]),
+        )
+        .await?;
 
         Ok(res
             .results
@@ -85,21 +116,24 @@ impl ContentSupplier for TMDBContentSupplier {
             .collect())
     }
 
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    async fn load_channel(&self, channel: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         let (fallback_media_type, path) = match self.channels_map.get(channel) {
             Some(params) => params,
-            None => return Err(anyhow!("Unknow channel")),
+            None => return Err(SupplierError::unknown_channel(channel)),
         };
 
-        let res: TMDBSearchResponse = utils::create_json_client()
-            .get(format!("{URL}{path}"))
-            .header(header::AUTHORIZATION, format!("Bearer {SECRET}"))
-            .header(header::ACCEPT, "application/json")
-            .query(&[("page", page.to_string().as_str()), ("langauge", "en-US")])
-            .send()
-            .await?
-            .json()
-            .await?;
+        let language = language();
+        let res: TMDBSearchResponse = utils::fetch_json(
+            utils::create_json_client()
+                .get(format!("{URL}{path}"))
+                .header(header::AUTHORIZATION, authorization()?)
+                .header(header::ACCEPT, "application/json")
+                .query(&[
+                    ("page", page.to_string().as_str()),
+                    ("language", language.as_str()),
+                ]),
+        )
+        .await?;
 
         Ok(res
             .results
@@ -108,15 +142,18 @@ impl ContentSupplier for TMDBContentSupplier {
             .collect())
     }
 
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
-        let res: TMDBDetailsResponse = utils::create_json_client()
-            .get(format!("{URL}/{id}"))
-            .header(header::AUTHORIZATION, format!("Bearer {SECRET}"))
-            .query(&[("append_to_response", "external_ids,credits,recommendations")])
-            .send()
-            .await?
-            .json()
-            .await?;
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>> {
+        let language = language();
+        let res: TMDBDetailsResponse = utils::fetch_json(
+            utils::create_json_client()
+                .get(format!("{URL}/{id}"))
+                .header(header::AUTHORIZATION, authorization()?)
+                .query(&[
+                    ("append_to_response", "external_ids,credits,recommendations"),
+                    ("language", language.as_str()),
+                ]),
+        )
+        .await?;
 
         let details = build_content_details(res);
 
@@ -127,27 +164,32 @@ impl ContentSupplier for TMDBContentSupplier {
         &self,
         id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
-        if params.is_empty() {
-            return Err(anyhow!("external_ids and season nums expected"));
-        }
-
-        build_media_items(id, params).await
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
+        let media_items_params = params::decode(&params)?;
+        build_media_items(id, media_items_params).await
     }
 
     async fn load_media_item_sources(
         &self,
-        _id: &str,
+        id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        if params.is_empty() {
-            return Err(anyhow!("Source params expected"));
-        }
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        let sink = SourcesSink::default();
+        self.stream_media_item_sources(id, params, &sink).await?;
 
-        let source_params: SourceParams = serde_json::from_str(&params[0])?;
-        let sources = run_extractors(&source_params).await;
+        Ok(sink.into_sources())
+    }
+
+    async fn stream_media_item_sources(
+        &self,
+        _id: &str,
+        params: Vec<String>,
+        sink: &SourcesSink,
+    ) -> SupplierResult<()> {
+        let source_params: SourceParams = params::decode(&params)?;
+        run_extractors(&source_params, &subtitle_languages(), sink).await;
 
-        Ok(sources)
+        Ok(())
     }
 }
 
@@ -255,6 +297,36 @@ struct TMDBExternalIds {
     imdb_id: Option<String>,
 }
 
+#[derive(Deserialize, Serialize, Debug)]
+struct MediaItemsParams {
+    imdb_id: Option<String>,
+    last_season: Option<u32>,
+}
+
+impl MediaParams for MediaItemsParams {
+    const KIND: &'static str = "tmdb_media_items";
+    const VERSION: u32 = 2;
+
+    // v1 is [external_ids json, last season number?]
+    fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+        match legacy {
+            LegacyParams::Raw(raw) => {
+                let external_ids: TMDBExternalIds =
+                    serde_json::from_str(params::required(raw, 0, "external ids")?)?;
+                let last_season = raw.get(1).map(|s| s.parse::<u32>()).transpose()?;
+
+                Ok(Self {
+                    imdb_id: external_ids.imdb_id,
+                    last_season,
+                })
+            }
+            LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(format!(
+                "tmdb media items params version {version} is not supported"
+            ))),
+        }
+    }
+}
+
 #[derive(Deserialize, Debug)]
 struct TMDBSeason {
     season_number: u32,
@@ -311,19 +383,18 @@ fn build_content_details(res: TMDBDetailsResponse) -> ContentDetails {
         .map(|v| v.to_content_info(""))
         .collect();
 
-    let mut params = vec![serde_json::to_string(&res.external_ids).unwrap()];
-
-    if let Some(seasons) = res.seasons {
-        let last_season_num_maybe = seasons
+    let last_season = res.seasons.and_then(|seasons| {
+        seasons
             .into_iter()
             .filter(|s| s.season_number != 0)
-            .map(|s| s.season_number.to_string())
-            .next_back();
+            .map(|s| s.season_number)
+            .next_back()
+    });
 
-        if let Some(last_season_num) = last_season_num_maybe {
-            params.push(last_season_num);
-        }
-    };
+    let params = params::encode(&MediaItemsParams {
+        imdb_id: res.external_ids.imdb_id,
+        last_season,
+    });
 
     ContentDetails {
         title,
@@ -338,25 +409,31 @@ fn build_content_details(res: TMDBDetailsResponse) -> ContentDetails {
     }
 }
 
-async fn build_media_items(id: &str, params: Vec<String>) -> anyhow::Result<Vec<ContentMediaItem>> {
-    let external_ids: TMDBExternalIds = serde_json::from_str(&params[0])?;
-    let (_, tmdb_str) = id.split_once('/').ok_or_else(|| anyhow!("invalid id"))?;
+async fn build_media_items(
+    id: &str,
+    params: MediaItemsParams,
+) -> SupplierResult<Vec<ContentMediaItem>> {
+    let external_ids = TMDBExternalIds {
+        imdb_id: params.imdb_id,
+    };
+    let (_, tmdb_str) = id
+        .split_once('/')
+        .ok_or_else(|| SupplierError::invalid_params("invalid id"))?;
     let tmdb = tmdb_str.parse::<u32>()?;
 
-    match params.get(1) {
-        Some(last_season_num_param) => {
-            let last_season_num = last_season_num_param.parse::<u32>()?;
+    match params.last_season {
+        Some(last_season_num) => {
             let client = &utils::create_json_client();
 
             let seasons_res_itr = (1..=last_season_num).map(|season_number| async move {
-                client
-                    .get(format!("{URL}/{id}/season/{season_number}"))
-                    .header(header::AUTHORIZATION, format!("Bearer {SECRET}"))
-                    .header(header::ACCEPT, "application/json")
-                    .send()
-                    .await?
-                    .json::<TMDBSeasonResponse>()
-                    .await
+                utils::fetch_json::<TMDBSeasonResponse>(
+                    client
+                        .get(format!("{URL}/{id}/season/{season_number}"))
+                        .header(header::AUTHORIZATION, authorization()?)
+                        .header(header::ACCEPT, "application/json")
+                        .query(&[("language", language())]),
+                )
+                .await
             });
 
             let media_items: Vec<_> = futures::future::try_join_all(seasons_res_itr)
@@ -364,35 +441,27 @@ async fn build_media_items(id: &str, params: Vec<String>) -> anyhow::Result<Vec<
                 .into_iter()
                 .flat_map(|season| season.episodes)
                 .map(|episode| {
-                    let media_item_param = serde_json::to_string(&SourceParams::new_episode(
-                        tmdb,
-                        &external_ids,
-                        &episode,
-                    ))
-                    .unwrap();
+                    let media_item_params =
+                        params::encode(&SourceParams::new_episode(tmdb, &external_ids, &episode));
                     ContentMediaItem {
                         title: episode.name,
                         image: episode.still_path.map(poster_image),
                         section: Some(episode.season_number.to_string()),
                         sources: None,
-                        params: vec![media_item_param],
+                        params: media_item_params,
                     }
                 })
                 .collect();
 
             Ok(media_items)
         }
-        None => {
-            let media_item_param =
-                serde_json::to_string(&SourceParams::new_movie(tmdb, &external_ids)).unwrap();
-            Ok(vec![ContentMediaItem {
-                title: "".into(),
-                section: None,
-                image: None,
-                sources: None,
-                params: vec![media_item_param],
-            }])
-        }
+        None => Ok(vec![ContentMediaItem {
+            title: "".into(),
+            section: None,
+            image: None,
+            sources: None,
+            params: params::encode(&SourceParams::new_movie(tmdb, &external_ids)),
+        }]),
     }
 }
 
@@ -404,6 +473,37 @@ fn poster_image(path: String) -> String {
     }
 }
 
+fn language_setting() -> SupplierSetting {
+    settings::choice(
+        "language",
+        &[
+            "en-US", "uk-UA", "ja-JP", "es-ES", "fr-FR", "de-DE", "pt-BR", "it-IT",
+        ],
+        "en-US",
+    )
+}
+
+fn subtitle_languages_setting() -> SupplierSetting {
+    settings::multi_choice("subtitle_languages", &lang::LANGUAGES, &["en", "uk"])
+}
+
+fn language() -> String {
+    settings::value(NAME, &language_setting())
+}
+
+fn subtitle_languages() -> Vec<String> {
+    settings::values(NAME, &subtitle_languages_setting())
+}
+
+// release builds may still bake in default key with `TMDB_SECRET` env var
+fn authorization() -> SupplierResult<String> {
+    credentials::get(NAME, API_KEY)
+        .or_else(|| option_env!("TMDB_SECRET").map(Into::into))
+        .filter(|key| !key.is_empty())
+        .map(|key| format!("Bearer {key}"))
+        .ok_or_else(|| SupplierError::not_configured(NAME, API_KEY))
+}
+
 fn original_poster_image(path: String) -> String {
     if path.starts_with("/") {
         format!("{IMAGES_URL}/original{path}")
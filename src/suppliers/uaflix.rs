@@ -1,7 +1,8 @@
-use anyhow::anyhow;
 use indexmap::IndexMap;
 use scraper::{ElementRef, Selector};
+use serde::{Deserialize, Serialize};
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::{
     models::{
         ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
@@ -11,6 +12,8 @@ use crate::{
     utils::{
         self,
         html::{self, DOMProcessor, ItrDOMProcessor, attr_value_map},
+        mirrors,
+        params::{self, LegacyParams, MediaParams},
     },
 };
 
@@ -33,7 +36,7 @@ pub struct UAFlixSupplier {
     selector_episode_link: Selector,
     selector_episode_image: Selector,
     selector_pages: Selector,
-    processor_content_details: html::ScopeProcessor<ContentDetails>,
+    processor_content_details: html::Required<Option<ContentDetails>>,
     processor_content_info_items: html::ItemsProcessor<ContentInfo>,
     processor_content_info_channel_items: html::ItemsProcessor<ContentInfo>,
 }
@@ -63,6 +66,7 @@ impl Default for UAFlixSupplier {
                             html::text_value("#ftitle"),
                         ],
                     )
+                    .required()
                     .boxed(),
                     original_title: html::optional_text_value(".eng-rus"),
                     image: html::Any::new(
@@ -89,15 +93,17 @@ impl Default for UAFlixSupplier {
                     .boxed(),
                 }
                 .boxed(),
-            ),
+            )
+            .required(),
             processor_content_info_items: html::ItemsProcessor::new(
                 ".sres-wrap",
                 html::ContentInfoProcessor {
                     id: html::AttrValue::new("href")
                         .map_optional(extract_id_from_url)
                         .unwrap_or_default()
+                        .required()
                         .boxed(),
-                    title: html::text_value("h2"),
+                    title: html::required(html::text_value("h2")),
                     secondary_title: html::default_value(),
                     image: html::self_hosted_image(URL, ".sres-img img", "src"),
                 }
@@ -106,8 +112,8 @@ impl Default for UAFlixSupplier {
             processor_content_info_channel_items: html::ItemsProcessor::new(
                 "#dle-content .video-item",
                 html::ContentInfoProcessor {
-                    id: attr_value_map(".vi-img", "href", extract_id_from_url),
-                    title: html::text_value(".vi-desc .vi-title"),
+                    id: html::required(attr_value_map(".vi-img", "href", extract_id_from_url)),
+                    title: html::required(html::text_value(".vi-desc .vi-title")),
                     secondary_title: html::default_value(),
                     image: html::self_hosted_image(URL, ".vi-img img", "src"),
                 }
@@ -134,7 +140,19 @@ impl ContentSupplier for UAFlixSupplier {
         vec!["uk".to_string()]
     }
 
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    fn get_display_name(&self) -> String {
+        "UAFlix".into()
+    }
+
+    fn get_home_url(&self) -> String {
+        URL.into()
+    }
+
+    fn get_mirrors(&self) -> Vec<String> {
+        vec!["https://uaflix.net".into()]
+    }
+
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         let client = utils::create_client();
 
         let request = client
@@ -147,7 +165,7 @@ impl ContentSupplier for UAFlixSupplier {
         Ok(results)
     }
 
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    async fn load_channel(&self, channel: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         let url = utils::datalife::get_channel_url(&self.channels_map, channel, page)?;
 
         utils::scrap_page(
@@ -157,16 +175,11 @@ impl ContentSupplier for UAFlixSupplier {
         .await
     }
 
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>> {
         let url = format!("{URL}/{id}/");
-        let html = utils::create_client()
-            .get(&url)
-            .send()
-            .await?
-            .text()
-            .await?;
+        let html = utils::fetch_text(utils::create_client().get(&url)).await?;
 
-        let (mut maybe_content_details, episodes) = self.try_load_content_details(&html);
+        let (mut maybe_content_details, episodes) = self.try_load_content_details(&html)?;
 
         if let Some(&mut ref mut content_details) = maybe_content_details.as_mut()
             && !episodes.episodes.is_empty()
@@ -197,14 +210,13 @@ impl ContentSupplier for UAFlixSupplier {
         &self,
         _id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
-        if params.len() != 1 {
-            return Err(anyhow!("single param expected"));
-        }
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
+        // iframe link is scraped straight from details page
+        let iframe_url = params::required(&params, 0, "iframe url")?;
 
         let media_items = utils::playerjs::load_and_parse_playerjs(
             utils::create_client()
-                .get(&params[0])
+                .get(iframe_url)
                 .header("Referer", URL),
             utils::playerjs::convert_strategy_dub_season_ep,
         )
@@ -217,12 +229,10 @@ impl ContentSupplier for UAFlixSupplier {
         &self,
         id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        if params.len() != 1 {
-            return Err(anyhow!("single param expected"));
-        }
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        let EpisodeParams { episode_id } = params::decode(&params)?;
 
-        let url = format!("{}/{}/{}/", URL, id, params[0]);
+        let url = format!("{URL}/{id}/{episode_id}/");
         let iframe_url = self.extract_iframe_url(&url).await?;
 
         let sources = utils::playerjs::load_and_parse_playerjs_sources(
@@ -237,22 +247,46 @@ impl ContentSupplier for UAFlixSupplier {
     }
 }
 
+#[derive(Deserialize, Serialize)]
+struct EpisodeParams {
+    episode_id: String,
+}
+
+impl MediaParams for EpisodeParams {
+    const KIND: &'static str = "uaflix_episode";
+    const VERSION: u32 = 2;
+
+    // v1 is bare [episode_id]
+    fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+        match legacy {
+            LegacyParams::Raw(raw) => Ok(Self {
+                episode_id: params::required(raw, 0, "episode id")?.to_owned(),
+            }),
+            LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(format!(
+                "uaflix episode params version {version} is not supported"
+            ))),
+        }
+    }
+
+    fn validate(&self) -> SupplierResult<()> {
+        if self.episode_id.is_empty() {
+            return Err(SupplierError::invalid_params("episode id expected"));
+        }
+        Ok(())
+    }
+}
+
 impl UAFlixSupplier {
-    async fn load_next_page_episodes(&self, page_url: &str) -> anyhow::Result<Episodes> {
-        let html = utils::create_client()
-            .get(page_url)
-            .send()
-            .await?
-            .text()
-            .await?;
+    async fn load_next_page_episodes(&self, page_url: &str) -> SupplierResult<Episodes> {
+        let html = utils::fetch_text(utils::create_client().get(page_url)).await?;
 
         let document = scraper::Html::parse_document(&html);
 
         Ok(self.try_extract_episodes_links(document.root_element()))
     }
 
-    async fn extract_iframe_url(&self, url: &str) -> anyhow::Result<String> {
-        let html = utils::create_client().get(url).send().await?.text().await?;
+    async fn extract_iframe_url(&self, url: &str) -> SupplierResult<String> {
+        let html = utils::fetch_text(utils::create_client().get(url)).await?;
 
         let root = scraper::Html::parse_document(&html);
         let selector = scraper::Selector::parse(".video-box iframe").unwrap();
@@ -260,11 +294,11 @@ impl UAFlixSupplier {
         let iframe_el = root
             .select(&selector)
             .next()
-            .ok_or_else(|| anyhow!("iframe not found"))?;
+            .ok_or_else(|| SupplierError::parse("iframe not found"))?;
 
         let link = iframe_el
             .attr("src")
-            .ok_or_else(|| anyhow!("iframe has no link"))?;
+            .ok_or_else(|| SupplierError::parse("iframe has no link"))?;
 
         Ok(link.to_string())
     }
@@ -293,20 +327,25 @@ impl UAFlixSupplier {
                     sources: None,
                     section: Some(s_num.to_string()),
                     image,
-                    params: vec![id.to_string()],
+                    params: params::encode(&EpisodeParams {
+                        episode_id: id.to_owned(),
+                    }),
                 })
             })
             .for_each(|item| content_media_items.push(item));
     }
 
-    fn try_load_content_details(&self, html: &str) -> (Option<ContentDetails>, Episodes) {
+    fn try_load_content_details(
+        &self,
+        html: &str,
+    ) -> SupplierResult<(Option<ContentDetails>, Episodes)> {
         let document = scraper::Html::parse_document(html);
         let root = document.root_element();
 
-        let maybe_details = self.processor_content_details.process(&root);
+        let maybe_details = html::process_checked(&self.processor_content_details, &root)?;
         let episodes_links = self.try_extract_episodes_links(root);
 
-        (maybe_details, episodes_links)
+        Ok((maybe_details, episodes_links))
     }
 
     fn try_extract_episodes_links(&self, root: ElementRef<'_>) -> Episodes {
@@ -337,8 +376,8 @@ impl UAFlixSupplier {
     }
 }
 
-fn extract_id_from_url(mut url: String) -> String {
-    url.drain((URL.len() + 1)..(url.len() - 1)).collect()
+fn extract_id_from_url(url: String) -> String {
+    mirrors::strip_base(URL, &url).trim_matches('/').to_owned()
 }
 
 #[cfg(test)]
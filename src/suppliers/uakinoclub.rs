@@ -1,27 +1,30 @@
-use anyhow::anyhow;
 use indexmap::IndexMap;
-use std::time::Instant;
 
-use anyhow::Ok;
-
-use super::ContentSupplier;
-use crate::models::{
-    ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType, MediaType,
-};
+use super::datalife::{DataLifeConfig, DataLifeSite, DataLifeSupplier, Playlist, SearchPaging};
+use crate::models::{ContentInfo, ContentType, MediaType};
 use crate::utils::html::{DOMProcessor, ItrDOMProcessor};
 use crate::utils::{self, datalife, html, playerjs};
 
 const URL: &str = "https://uakino.best";
 
-pub struct UAKinoClubContentSupplier {
-    channels_map: IndexMap<&'static str, String>,
-    processor_content_info_items: html::ItemsProcessor<ContentInfo>,
-    processor_content_details: html::ScopeProcessor<ContentDetails>,
-}
-
-impl Default for UAKinoClubContentSupplier {
-    fn default() -> Self {
-        Self {
+pub type UAKinoClubContentSupplier = DataLifeSupplier<UAKinoClub>;
+
+#[derive(Default)]
+pub struct UAKinoClub;
+
+impl DataLifeSite for UAKinoClub {
+    fn config(&self) -> DataLifeConfig {
+        DataLifeConfig {
+            url: URL,
+            display_name: "UAKino Club",
+            mirrors: vec!["https://uakino.me", "https://uakino.club"],
+            supported_types: vec![
+                ContentType::Movie,
+                ContentType::Cartoon,
+                ContentType::Series,
+                ContentType::Anime,
+            ],
+            supported_languages: vec!["uk"],
             channels_map: IndexMap::from([
                 ("Новинки", format!("{URL}/page/")),
                 ("Фільми", format!("{URL}/filmy/page/")),
@@ -29,23 +32,34 @@ impl Default for UAKinoClubContentSupplier {
                 ("Аніме", format!("{URL}/animeukr/page/")),
                 ("Мультфільми", format!("{URL}/cartoon/page/")),
             ]),
-            processor_content_info_items: html::ItemsProcessor::new(
-                "#dle-content .movie-item",
-                html::ContentInfoProcessor {
-                    id: html::attr_value_map(".movie-title", "href", |s| {
-                        datalife::extract_id_from_url(URL, s)
-                    }),
-                    title: html::text_value_map(".movie-title", |s| utils::text::sanitize_text(&s)),
-                    secondary_title: html::optional_text_value(".full-quality"),
-                    image: html::self_hosted_image(URL, ".movie-img > img", "src"),
-                }
+            search_paging: SearchPaging::Param("from_page"),
+            content_info_items: html::required_scope(
+                "#dle-content",
+                html::ItemsProcessor::new(
+                    ".movie-item",
+                    html::ContentInfoProcessor {
+                        id: html::required(html::attr_value_map(".movie-title", "href", |s| {
+                            datalife::extract_id_from_url(URL, s)
+                        })),
+                        title: html::required(html::text_value_map(".movie-title", |s| {
+                            utils::text::sanitize_text(&s)
+                        })),
+                        secondary_title: html::optional_text_value(".full-quality"),
+                        image: html::self_hosted_image(URL, ".movie-img > img", "src"),
+                    }
+                    .boxed(),
+                )
+                // search also lists news and franchise pages
+                .filter(|ci: &ContentInfo| {
+                    !ci.id.starts_with("news") && !ci.id.starts_with("franchise")
+                })
                 .boxed(),
             ),
-            processor_content_details: html::ScopeProcessor::new(
+            content_details: html::ScopeProcessor::new(
                 "#dle-content",
                 html::ContentDetailsProcessor {
                     media_type: MediaType::Video,
-                    title: html::text_value(".solototle"),
+                    title: html::required(html::text_value(".solototle")),
                     original_title: html::optional_text_value(".origintitle"),
                     image: html::self_hosted_image(URL, ".film-poster img", "src"),
                     description: html::text_value_map("div[itemprop=description]", |s| {
@@ -80,136 +94,20 @@ impl Default for UAKinoClubContentSupplier {
                         .boxed(),
                 }
                 .boxed(),
-            ),
-        }
-    }
-}
-
-impl ContentSupplier for UAKinoClubContentSupplier {
-    fn get_channels(&self) -> Vec<String> {
-        self.channels_map.keys().map(|&s| s.into()).collect()
-    }
-
-    fn get_default_channels(&self) -> Vec<String> {
-        vec![]
-    }
-
-    fn get_supported_types(&self) -> Vec<ContentType> {
-        vec![
-            ContentType::Movie,
-            ContentType::Cartoon,
-            ContentType::Series,
-            ContentType::Anime,
-        ]
-    }
-
-    fn get_supported_languages(&self) -> Vec<String> {
-        vec!["uk".into()]
-    }
-
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        let result = utils::scrap_page(
-            datalife::search_request(URL, query).query(&[("from_page", page.to_string())]),
-            &self.processor_content_info_items,
-        )
-        .await?;
-
-        let filtered_results = result
-            .into_iter()
-            .filter(|ci| !ci.id.starts_with("news") && !ci.id.starts_with("franchise"))
-            .collect();
-
-        Ok(filtered_results)
-    }
-
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        let url = datalife::get_channel_url(&self.channels_map, channel, page)?;
-
-        utils::scrap_page(
-            utils::create_client().get(&url),
-            &self.processor_content_info_items,
-        )
-        .await
-    }
-
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
-        let url = datalife::format_id_from_url(URL, id);
-
-        utils::scrap_page(
-            utils::create_client().get(&url),
-            &self.processor_content_details,
-        )
-        .await
-    }
-
-    async fn load_media_items(
-        &self,
-        id: &str,
-        params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
-        if !params.is_empty() {
-            playerjs::load_and_parse_playerjs(
-                utils::create_client().get(&params[0]),
-                playerjs::convert_strategy_dub,
             )
-            .await
-        } else {
-            let maybe_news_id = id
-                .rsplit_once("/")
-                .and_then(|(_, s)| s.split_once("-"))
-                .map(|(s, _)| s);
-
-            let news_id = match maybe_news_id {
-                Some(news_id) => news_id,
-                None => return Err(anyhow!("No news id found")),
-            };
-
-            let referer = datalife::format_id_from_url(URL, id);
-            let playlist_req = utils::create_client()
-                .get(format!("{URL}/engine/ajax/playlists.php"))
-                .query(&[
-                    ("xfield", "playlist"),
-                    ("news_id", news_id),
-                    (
-                        "time",
-                        Instant::now().elapsed().as_millis().to_string().as_str(),
-                    ),
-                ])
-                .header("Referer", referer);
-
-            datalife::load_ajax_playlist(playlist_req).await
-        }
-    }
-
-    async fn load_media_item_sources(
-        &self,
-        _id: &str,
-        mut params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        if !params.len().is_multiple_of(2) {
-            return Err(anyhow!("Wrong params size"));
-        }
-
-        let mut results = vec![];
-        while !params.is_empty() {
-            let description = params.remove(0);
-            let url = params.remove(0);
-
-            let mut sources = playerjs::load_and_parse_playerjs_sources(
-                utils::create_client().get(&url),
-                &description,
-            )
-            .await?;
-            results.append(&mut sources);
+            .required()
+            .boxed(),
+            playlist: Some(Playlist::PlayerJSOrAjax(playerjs::convert_strategy_dub)),
+            login_hash: false,
         }
-
-        Ok(results)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::suppliers::ContentSupplier;
+
     #[tokio::test]
     async fn uakinoclub_should_load_channel() {
         let res = UAKinoClubContentSupplier::default()
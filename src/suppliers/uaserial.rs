@@ -1,6 +1,6 @@
-use anyhow::anyhow;
 use indexmap::IndexMap;
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::{
     models::{
         ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
@@ -9,7 +9,7 @@ use crate::{
     utils::{
         self,
         html::{self, DOMProcessor},
-        playerjs,
+        params, playerjs,
     },
 };
 
@@ -22,7 +22,7 @@ pub struct UAserialContentSupplier {
     channels_map: IndexMap<&'static str, String>,
     processor_content_channel_items: html::ItemsProcessor<ContentInfo>,
     processor_search_items: html::ItemsProcessor<ContentInfo>,
-    processor_content_details: html::ScopeProcessor<ContentDetails>,
+    processor_content_details: html::Required<Option<ContentDetails>>,
 }
 
 impl Default for UAserialContentSupplier {
@@ -47,7 +47,7 @@ impl Default for UAserialContentSupplier {
                 "#container",
                 html::ContentDetailsProcessor {
                     media_type: MediaType::Video,
-                    title: html::text_value(".header--title  .title"),
+                    title: html::required(html::text_value(".header--title  .title")),
                     original_title: html::optional_text_value(".header--title .original"),
                     image: html::self_hosted_image(URL, ".poster img", "src"),
                     description: html::text_value(".player__info .player__description .text"),
@@ -79,15 +79,18 @@ impl Default for UAserialContentSupplier {
                     )]),
                 }
                 .boxed(),
-            ),
+            )
+            .required(),
         }
     }
 }
 
 fn content_info_processor() -> Box<html::ContentInfoProcessor> {
     html::ContentInfoProcessor {
-        id: html::attr_value_map(".item > a", "href", |s| extract_id_from_url(&s)),
-        title: html::text_value(".item__data > a .name"),
+        id: html::required(html::attr_value_map(".item > a", "href", |s| {
+            extract_id_from_url(&s)
+        })),
+        title: html::required(html::text_value(".item__data > a .name")),
         secondary_title: html::ItemsProcessor::new(
             ".item__data .info__item",
             html::TextValue::new().boxed(),
@@ -121,7 +124,19 @@ impl ContentSupplier for UAserialContentSupplier {
         vec!["uk".into()]
     }
 
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    fn get_display_name(&self) -> String {
+        "UASerial".into()
+    }
+
+    fn get_home_url(&self) -> String {
+        URL.into()
+    }
+
+    fn supports_search_paging(&self) -> bool {
+        false
+    }
+
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         if page > 1 {
             return Ok(vec![]);
         }
@@ -133,10 +148,10 @@ impl ContentSupplier for UAserialContentSupplier {
         utils::scrap_page(request_builder, &self.processor_search_items).await
     }
 
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    async fn load_channel(&self, channel: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         let url = match self.channels_map.get(channel) {
             Some(url) => format!("{url}{page}"),
-            None => return Err(anyhow!("unknown channel")),
+            None => return Err(SupplierError::unknown_channel(channel)),
         };
 
         utils::scrap_page(
@@ -146,29 +161,25 @@ impl ContentSupplier for UAserialContentSupplier {
         .await
     }
 
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>> {
         let url = format!("{URL}/{id}");
 
-        let html = utils::create_client().get(url).send().await?.text().await?;
-        let document = scraper::Html::parse_document(&html);
-        let root = document.root_element();
-        let maybe_details = self.processor_content_details.process(&root);
-
-        Ok(maybe_details)
+        utils::scrap_page(
+            utils::create_client().get(url),
+            &self.processor_content_details,
+        )
+        .await
     }
 
     async fn load_media_items(
         &self,
         _id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
-        if params.len() != 1 {
-            return Err(anyhow!("Wrong params size"));
-        }
-
-        let url = &params[0];
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
+        // iframe link is scraped straight from details page
+        let iframe_url = params::required(&params, 0, "iframe url")?;
         let sources = playerjs::load_and_parse_playerjs(
-            utils::create_client().get(url),
+            utils::create_client().get(iframe_url),
             playerjs::convert_strategy_dub_season_ep,
         )
         .await?;
@@ -180,8 +191,8 @@ impl ContentSupplier for UAserialContentSupplier {
         &self,
         _id: &str,
         _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        Err(anyhow!("unimplemented"))
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        Err(SupplierError::unsupported("load_media_item_sources"))
     }
 }
 
@@ -200,6 +211,7 @@ fn extract_id_from_url(id: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+
     #[tokio::test]
     async fn uaserials_should_load_channel() {
         let res = UAserialContentSupplier::default()
@@ -1,51 +1,61 @@
-use anyhow::anyhow;
 use indexmap::IndexMap;
 
-use super::ContentSupplier;
-use crate::models::{
-    ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType, MediaType,
-};
+use super::datalife::{DataLifeConfig, DataLifeSite, DataLifeSupplier, Playlist, SearchPaging};
+use crate::models::{ContentType, MediaType};
 use crate::utils::html::DOMProcessor;
-use crate::utils::{self, datalife, html, playerjs};
+use crate::utils::{datalife, html, playerjs};
 
 const URL: &str = "https://uaserials.my";
 
-pub struct UASerialsProContentSupplier {
-    channels_map: IndexMap<&'static str, String>,
-    processor_content_info_items: html::ItemsProcessor<ContentInfo>,
-    processor_content_details: html::ScopeProcessor<ContentDetails>,
-}
-
-impl Default for UASerialsProContentSupplier {
-    fn default() -> Self {
-        Self {
+pub type UASerialsProContentSupplier = DataLifeSupplier<UASerialsPro>;
+
+#[derive(Default)]
+pub struct UASerialsPro;
+
+impl DataLifeSite for UASerialsPro {
+    fn config(&self) -> DataLifeConfig {
+        DataLifeConfig {
+            url: URL,
+            display_name: "UASerials Pro",
+            mirrors: vec![],
+            supported_types: vec![
+                ContentType::Movie,
+                ContentType::Series,
+                ContentType::Cartoon,
+                ContentType::Anime,
+            ],
+            supported_languages: vec!["uk"],
             channels_map: IndexMap::from([
                 ("Фільми", format!("{URL}/films/page/")),
                 ("Серіали", format!("{URL}/series/page/")),
                 ("Мультфільми", format!("{URL}/fcartoon/page/")),
                 ("Мультсеріали", format!("{URL}/cartoon/page/")),
             ]),
-            processor_content_info_items: html::ItemsProcessor::new(
-                "div.short-item",
-                html::ContentInfoProcessor {
-                    id: html::attr_value_map("a.short-img", "href", |id| {
-                        datalife::extract_id_from_url(URL, id)
-                    }),
-                    title: html::text_value("div.th-title"),
-                    secondary_title: html::optional_text_value("div.th-title-oname"),
-                    image: html::ExtractValue::new(|el| {
-                        el.attr("data-src")
-                            .or(el.attr("src"))
-                            .map(|url| html::self_hosted_url(URL, url))
-                            .unwrap_or_default()
-                    })
-                    .in_scope("a.short-img img")
-                    .unwrap_or_default()
-                    .boxed(), // html::self_hosted_image(URL, "a.short-img img", "data-src"),
-                }
-                .boxed(),
+            search_paging: SearchPaging::Param("search_start"),
+            content_info_items: html::required_scope(
+                "#dle-content",
+                html::items_processor(
+                    "div.short-item",
+                    html::ContentInfoProcessor {
+                        id: html::required(html::attr_value_map("a.short-img", "href", |id| {
+                            datalife::extract_id_from_url(URL, id)
+                        })),
+                        title: html::required(html::text_value("div.th-title")),
+                        secondary_title: html::optional_text_value("div.th-title-oname"),
+                        image: html::ExtractValue::new(|el| {
+                            el.attr("data-src")
+                                .or(el.attr("src"))
+                                .map(|url| html::self_hosted_url(URL, url))
+                                .unwrap_or_default()
+                        })
+                        .in_scope("a.short-img img")
+                        .unwrap_or_default()
+                        .boxed(), // html::self_hosted_image(URL, "a.short-img img", "data-src"),
+                    }
+                    .boxed(),
+                ),
             ),
-            processor_content_details: html::ScopeProcessor::new(
+            content_details: html::ScopeProcessor::new(
                 "#dle-content",
                 html::ContentDetailsProcessor {
                     media_type: MediaType::Video,
@@ -65,91 +75,19 @@ impl Default for UASerialsProContentSupplier {
                     ),
                 }
                 .boxed(),
-            ),
-        }
-    }
-}
-
-impl ContentSupplier for UASerialsProContentSupplier {
-    fn get_channels(&self) -> Vec<String> {
-        self.channels_map.keys().map(|&s| s.into()).collect()
-    }
-
-    fn get_default_channels(&self) -> Vec<String> {
-        vec![]
-    }
-
-    fn get_supported_types(&self) -> Vec<ContentType> {
-        vec![
-            ContentType::Movie,
-            ContentType::Series,
-            ContentType::Cartoon,
-            ContentType::Anime,
-        ]
-    }
-
-    fn get_supported_languages(&self) -> Vec<String> {
-        vec!["uk".into()]
-    }
-
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        let url = datalife::get_channel_url(&self.channels_map, channel, page)?;
-
-        utils::scrap_page(
-            utils::create_client().get(&url),
-            &self.processor_content_info_items,
-        )
-        .await
-    }
-
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        utils::scrap_page(
-            datalife::search_request(URL, query).query(&[("search_start", page.to_string())]),
-            &self.processor_content_info_items,
-        )
-        .await
-    }
-
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
-        let url = datalife::format_id_from_url(URL, id);
-
-        println!("{url}");
-
-        utils::scrap_page(
-            utils::create_client().get(&url),
-            &self.processor_content_details,
-        )
-        .await
-    }
-
-    async fn load_media_items(
-        &self,
-        _id: &str,
-        params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
-        if !params.is_empty() {
-            playerjs::load_and_parse_playerjs(
-                utils::create_client().get(&params[0]),
-                playerjs::convert_strategy_season_dub_ep,
             )
-            .await
-        } else {
-            Err(anyhow!("iframe url expected"))
+            .required()
+            .boxed(),
+            playlist: Some(Playlist::PlayerJS(playerjs::convert_strategy_season_dub_ep)),
+            login_hash: false,
         }
     }
-
-    async fn load_media_item_sources(
-        &self,
-        _id: &str,
-        _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        Err(anyhow!("unimplemented"))
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::suppliers::ContentSupplier;
     #[tokio::test]
     async fn should_load_channel() {
         let res = UASerialsProContentSupplier::default()
@@ -1,32 +1,42 @@
+use crate::errors::SupplierResult;
 use crate::{
-    models::{
-        ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
-        MediaType,
-    },
+    models::{ContentMediaItem, ContentMediaItemSource, ContentType, MediaType},
     utils::{
         self, datalife,
         html::{self, DOMProcessor},
+        params,
     },
 };
 
-use super::ContentSupplier;
+use super::datalife::{DataLifeConfig, DataLifeSite, DataLifeSupplier, SearchPaging};
 
-use anyhow::{Ok, anyhow};
 use indexmap::IndexMap;
 use regex::Regex;
 
 const URL: &str = "https://ufdub.com";
 
-pub struct UFDubContentSupplier {
-    channels_map: IndexMap<&'static str, String>,
-    processor_content_info_items: html::ItemsProcessor<ContentInfo>,
-    processor_content_details: html::ScopeProcessor<ContentDetails>,
+pub type UFDubContentSupplier = DataLifeSupplier<UFDub>;
+
+pub struct UFDub {
     re_video_links: Regex,
 }
 
-impl Default for UFDubContentSupplier {
+impl Default for UFDub {
     fn default() -> Self {
         Self {
+            re_video_links: Regex::new(r#"\['(?<title>[^']*)','mp4','(?<url>https://ufdub\.com/video/VIDEOS\.php\?[^']*?)'\]"#).unwrap(),
+        }
+    }
+}
+
+impl DataLifeSite for UFDub {
+    fn config(&self) -> DataLifeConfig {
+        DataLifeConfig {
+            url: URL,
+            display_name: "UFDub",
+            mirrors: vec![],
+            supported_types: vec![ContentType::Anime],
+            supported_languages: vec!["uk"],
             channels_map: IndexMap::from([
                 ("Новинки", format!("{URL}/page/")),
                 ("Фільми", format!("{URL}/film/page/")),
@@ -36,30 +46,36 @@ impl Default for UFDubContentSupplier {
                 ("Мультсеріали", format!("{URL}/cartoon-serial/page/")),
                 ("Дорами", format!("{URL}/dorama/page/")),
             ]),
-            processor_content_info_items: html::ItemsProcessor::new(
-                ".cont .short",
-                html::ContentInfoProcessor {
-                    id: html::attr_value_map(".short-text > .short-t", "href", |s| {
-                        datalife::extract_id_from_url(URL, s)
-                    }),
-                    title: html::text_value(".short-text > .short-t"),
-                    secondary_title: html::ItemsProcessor::new(
-                        ".short-text > .short-c > a",
-                        html::TextValue::new().boxed(),
-                    )
-                    .map(|v| Some(v.join(",")))
+            search_paging: SearchPaging::FirstPage,
+            content_info_items: html::required_scope(
+                ".cont",
+                html::items_processor(
+                    ".short",
+                    html::ContentInfoProcessor {
+                        id: html::required(html::attr_value_map(
+                            ".short-text > .short-t",
+                            "href",
+                            |s| datalife::extract_id_from_url(URL, s),
+                        )),
+                        title: html::required(html::text_value(".short-text > .short-t")),
+                        secondary_title: html::ItemsProcessor::new(
+                            ".short-text > .short-c > a",
+                            html::TextValue::new().boxed(),
+                        )
+                        .map(|v| Some(v.join(",")))
+                        .boxed(),
+                        image: html::self_hosted_image(URL, ".short-i img", "src"),
+                    }
                     .boxed(),
-                    image: html::self_hosted_image(URL, ".short-i img", "src"),
-                }
-                .boxed(),
+                ),
             ),
-            processor_content_details: html::ScopeProcessor::new(
+            content_details: html::ScopeProcessor::new(
                 "div.cols",
                 html::ContentDetailsProcessor {
                     media_type: MediaType::Video,
-                    title: html::text_value_map("article .full-title > h1", |s| {
+                    title: html::required(html::text_value_map("article .full-title > h1", |s| {
                         s.trim().to_owned()
-                    }),
+                    })),
                     original_title: html::TextValue::new()
                         .map(|s| s.trim().to_owned())
                         .in_scope("article > .full-title > h1 > .short-t-or")
@@ -101,76 +117,23 @@ impl Default for UFDubContentSupplier {
                     params: html::attr_value_map("article input", "value", |s| vec![s]),
                 }
                 .boxed(),
-            ),
-            re_video_links: Regex::new(r#"\['(?<title>[^']*)','mp4','(?<url>https://ufdub\.com/video/VIDEOS\.php\?[^']*?)'\]"#).unwrap()
-        }
-    }
-}
-
-impl ContentSupplier for UFDubContentSupplier {
-    fn get_channels(&self) -> Vec<String> {
-        self.channels_map.keys().map(|&s| s.into()).collect()
-    }
-
-    fn get_default_channels(&self) -> Vec<String> {
-        vec![]
-    }
-
-    fn get_supported_types(&self) -> Vec<ContentType> {
-        vec![ContentType::Anime]
-    }
-
-    fn get_supported_languages(&self) -> Vec<String> {
-        vec!["uk".into()]
-    }
-
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        if page > 1 {
-            return Ok(vec![]);
+            )
+            .required()
+            .boxed(),
+            playlist: None,
+            login_hash: false,
         }
-
-        utils::scrap_page(
-            datalife::search_request(URL, query),
-            &self.processor_content_info_items,
-        )
-        .await
-    }
-
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
-        let url = datalife::get_channel_url(&self.channels_map, channel, page)?;
-
-        utils::scrap_page(
-            utils::create_client().get(&url),
-            &self.processor_content_info_items,
-        )
-        .await
-    }
-
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
-        let url = datalife::format_id_from_url(URL, id);
-
-        utils::scrap_page(
-            utils::create_client().get(&url),
-            &self.processor_content_details,
-        )
-        .await
     }
 
     async fn load_media_items(
         &self,
+        _config: &DataLifeConfig,
         _id: &str,
         params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
-        if params.is_empty() {
-            return Err(anyhow!("iframe url expected"));
-        }
-
-        let html = utils::create_client()
-            .get(&params[0])
-            .send()
-            .await?
-            .text()
-            .await?;
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
+        // iframe link is scraped straight from details page
+        let iframe_url = params::required(&params, 0, "iframe url")?;
+        let html = utils::fetch_text(utils::create_client().get(iframe_url)).await?;
 
         let result: Vec<_> = self
             .re_video_links
@@ -198,19 +161,13 @@ impl ContentSupplier for UFDubContentSupplier {
 
         Ok(result)
     }
-
-    async fn load_media_item_sources(
-        &self,
-        _id: &str,
-        _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        todo!()
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::suppliers::ContentSupplier;
+
     #[tokio::test]
     async fn should_load_channel() {
         let res = UFDubContentSupplier::default()
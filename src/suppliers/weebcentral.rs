@@ -1,9 +1,10 @@
 use std::sync::OnceLock;
 
-use anyhow::anyhow;
 use indexmap::IndexMap;
 use scraper::Selector;
+use serde::{Deserialize, Serialize};
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::{
     models::{
         ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, ContentType,
@@ -13,6 +14,7 @@ use crate::{
     utils::{
         self, create_client,
         html::{self, DOMProcessor, ItrDOMProcessor},
+        params::{self, LegacyParams, MediaParams},
     },
 };
 
@@ -22,7 +24,7 @@ const PAGE_SIZE: u16 = 32;
 pub struct WeebCentralContentSupplier {
     channels_map: IndexMap<&'static str, &'static str>,
     processor_content_info_items: html::ItemsProcessor<ContentInfo>,
-    processor_content_details: html::ScopeProcessor<ContentDetails>,
+    processor_content_details: html::Required<Option<ContentDetails>>,
     processor_content_media_item: html::FilterProcessor<ContentMediaItem>,
 }
 
@@ -40,8 +42,11 @@ impl Default for WeebCentralContentSupplier {
                     id: html::AttrValue::new("href")
                         .map_optional(extract_id)
                         .unwrap_or_default()
+                        .required()
                         .boxed(),
-                    title: html::text_value("article > div:not([class]) > div.bottom-0 > div"),
+                    title: html::required(html::text_value(
+                        "article > div:not([class]) > div.bottom-0 > div",
+                    )),
                     secondary_title: html::default_value(),
                     image: html::attr_value("article > picture > img", "src"),
                 }
@@ -50,8 +55,8 @@ impl Default for WeebCentralContentSupplier {
             processor_content_details: html::ScopeProcessor::new(
                 "main",
                 html::ContentDetailsProcessor {
-                    media_type: MediaType::Video,
-                    title: html::text_value("#top > section > section > h1"),
+                    media_type: MediaType::Manga,
+                    title: html::required(html::text_value("#top > section > section > h1")),
                     original_title: html::default_value(),
                     image: html::attr_value(
                         "#top > section > section:nth-child(1) > section > picture > img",
@@ -82,7 +87,8 @@ impl Default for WeebCentralContentSupplier {
                     params: html::default_value(),
                 }
                 .boxed(),
-            ),
+            )
+            .required(),
             processor_content_media_item: html::ItemsProcessor {
                 scope: None,
                 item_processor: html::ContentMediaItemProcessor {
@@ -94,7 +100,7 @@ impl Default for WeebCentralContentSupplier {
                             description: "Default".to_string(),
                             headers: None,
                             pages: None,
-                            params: vec![chapter_id],
+                            params: params::encode(&ChapterParams { chapter_id }),
                         }])
                     }),
                     params: html::default_value(),
@@ -123,7 +129,15 @@ impl ContentSupplier for WeebCentralContentSupplier {
         vec!["en".to_string()]
     }
 
-    async fn search(&self, query: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    fn get_display_name(&self) -> String {
+        "Weeb Central".into()
+    }
+
+    fn get_home_url(&self) -> String {
+        URL.into()
+    }
+
+    async fn search(&self, query: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         let mut request_builder = utils::create_client()
             .get(format!("{URL}/search/data"))
             .query(&[
@@ -144,10 +158,10 @@ impl ContentSupplier for WeebCentralContentSupplier {
         utils::scrap_fragment(request_builder, &self.processor_content_info_items).await
     }
 
-    async fn load_channel(&self, channel: &str, page: u16) -> anyhow::Result<Vec<ContentInfo>> {
+    async fn load_channel(&self, channel: &str, page: u16) -> SupplierResult<Vec<ContentInfo>> {
         let sort = match self.channels_map.get(channel) {
             Some(&sort) => sort,
-            None => return Err(anyhow!("unknown channel")),
+            None => return Err(SupplierError::unknown_channel(channel)),
         };
 
         let mut request_builder = utils::create_client()
@@ -169,7 +183,7 @@ impl ContentSupplier for WeebCentralContentSupplier {
         utils::scrap_fragment(request_builder, &self.processor_content_info_items).await
     }
 
-    async fn get_content_details(&self, id: &str) -> anyhow::Result<Option<ContentDetails>> {
+    async fn get_content_details(&self, id: &str) -> SupplierResult<Option<ContentDetails>> {
         let url = format!("{URL}/series/{id}");
 
         utils::scrap_page(
@@ -183,11 +197,11 @@ impl ContentSupplier for WeebCentralContentSupplier {
         &self,
         id: &str,
         _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItem>> {
+    ) -> SupplierResult<Vec<ContentMediaItem>> {
         let maybe_actual_id = id.split_once("/").and_then(|(left, _)| left.into());
         let actual_id = match maybe_actual_id {
             Some(s) => s,
-            None => return Err(anyhow!("incorrect id value")),
+            None => return Err(SupplierError::invalid_params("incorrect id value")),
         };
 
         let url = format!("{URL}/series/{actual_id}/full-chapter-list");
@@ -205,26 +219,50 @@ impl ContentSupplier for WeebCentralContentSupplier {
         &self,
         _id: &str,
         _params: Vec<String>,
-    ) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-        Err(anyhow!("unimplemented"))
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        Err(SupplierError::unsupported("load_media_item_sources"))
     }
 }
 
-impl MangaPagesLoader for WeebCentralContentSupplier {
-    async fn load_pages(&self, _id: &str, params: Vec<String>) -> anyhow::Result<Vec<String>> {
-        if params.len() != 1 {
-            return Err(anyhow!("expected singe param"));
+#[derive(Deserialize, Serialize)]
+struct ChapterParams {
+    chapter_id: String,
+}
+
+impl MediaParams for ChapterParams {
+    const KIND: &'static str = "weebcentral_chapter";
+    const VERSION: u32 = 2;
+
+    // v1 is bare [chapter_id]
+    fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+        match legacy {
+            LegacyParams::Raw(raw) => Ok(Self {
+                chapter_id: params::required(raw, 0, "chapter id")?.to_owned(),
+            }),
+            LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(format!(
+                "weebcentral chapter params version {version} is not supported"
+            ))),
         }
+    }
+
+    fn validate(&self) -> SupplierResult<()> {
+        if self.chapter_id.is_empty() {
+            return Err(SupplierError::invalid_params("chapter id expected"));
+        }
+        Ok(())
+    }
+}
 
-        let chapter_id = &params[0];
+impl MangaPagesLoader for WeebCentralContentSupplier {
+    async fn load_pages(&self, _id: &str, params: Vec<String>) -> SupplierResult<Vec<String>> {
+        let ChapterParams { chapter_id } = params::decode(&params)?;
 
-        let html = utils::create_client()
-            .get(format!("{URL}/chapters/{chapter_id}/images"))
-            .query(&[("is_prev", "False"), ("reading_style", "long_strip")])
-            .send()
-            .await?
-            .text()
-            .await?;
+        let html = utils::fetch_text(
+            utils::create_client()
+                .get(format!("{URL}/chapters/{chapter_id}/images"))
+                .query(&[("is_prev", "False"), ("reading_style", "long_strip")]),
+        )
+        .await?;
 
         static SELECTOR: OnceLock<Selector> = OnceLock::new();
         let selector = SELECTOR.get_or_init(|| Selector::parse("section > img").unwrap());
@@ -244,7 +282,7 @@ impl MangaPagesLoader for WeebCentralContentSupplier {
 
 fn extract_id(text: String) -> String {
     static OFFSET: usize = URL.len() + 8usize;
-    text[OFFSET..].to_string()
+    text.get(OFFSET..).unwrap_or_default().to_string()
 }
 
 #[cfg(test)]
@@ -17,10 +17,7 @@ pub async fn search_anime(query: &str, page: u16) -> anyhow::Result<Vec<ContentI
 
     let body = json!({"query": gql, "variables": variables,});
 
-    let result: SearchResponse = utils::create_json_client()
-        .post(URL)
-        .json(&body)
-        .send()
+    let result: SearchResponse = utils::send(utils::create_json_client().post(URL).json(&body))
         .await?
         .json()
         .await?;
@@ -42,10 +39,7 @@ pub async fn get_anime(id: &str) -> anyhow::Result<Option<ContentDetails>> {
 
     let body = json!({"query": gql, "variables": variables,});
 
-    let result: GetAnimeResponse = utils::create_json_client()
-        .post(URL)
-        .json(&body)
-        .send()
+    let result: GetAnimeResponse = utils::send(utils::create_json_client().post(URL).json(&body))
         .await?
         .json()
         .await?;
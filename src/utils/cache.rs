@@ -0,0 +1,382 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::{
+        Mutex, OnceLock, RwLock,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use log::{debug, warn};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::errors::{SupplierError, SupplierResult};
+
+const MEMORY_ENTRIES_LIMIT: usize = 256;
+const MAX_STALE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const DISK_SIZE_LIMIT: u64 = 64 * 1024 * 1024;
+// disk dir is checked against the limit once per this many writes
+const DISK_PRUNE_INTERVAL: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheOperation {
+    Search,
+    Channel,
+    ContentDetails,
+    MediaItems,
+    MediaItemSources,
+    MangaPages,
+}
+
+impl CacheOperation {
+    pub fn ttl(&self) -> Option<Duration> {
+        match self {
+            CacheOperation::Search => Some(Duration::from_secs(10 * 60)),
+            CacheOperation::Channel => Some(Duration::from_secs(15 * 60)),
+            CacheOperation::ContentDetails => Some(Duration::from_secs(6 * 60 * 60)),
+            CacheOperation::MediaItems => Some(Duration::from_secs(60 * 60)),
+            // stream and page links are usually signed and expire quickly
+            CacheOperation::MediaItemSources | CacheOperation::MangaPages => None,
+        }
+    }
+}
+
+tokio::task_local! {
+    static OPERATION: CacheOperation;
+}
+
+/// Run future with cache policy of given operation, requests outside of a scope are never cached
+pub async fn scoped<F: Future>(operation: CacheOperation, fut: F) -> F::Output {
+    OPERATION.scope(operation, fut).await
+}
+
+pub fn current_ttl() -> Option<Duration> {
+    let ttl = OPERATION.try_with(|op| op.ttl()).ok().flatten()?;
+
+    // bypass still refreshes stored entries and keeps stale fallback on network errors
+    if BYPASS.load(Ordering::Relaxed) {
+        return Some(Duration::ZERO);
+    }
+
+    Some(ttl)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub body: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub stored_at: u64,
+}
+
+impl CacheEntry {
+    pub fn new(body: String, etag: Option<String>, last_modified: Option<String>) -> Self {
+        Self {
+            body,
+            etag,
+            last_modified,
+            stored_at: now_secs(),
+        }
+    }
+
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        now_secs().saturating_sub(self.stored_at) < ttl.as_secs()
+    }
+
+    pub fn is_usable_stale(&self) -> bool {
+        now_secs().saturating_sub(self.stored_at) < MAX_STALE.as_secs()
+    }
+
+    pub fn touch(&mut self) {
+        self.stored_at = now_secs();
+    }
+}
+
+static BYPASS: AtomicBool = AtomicBool::new(false);
+static DISK_WRITES: AtomicUsize = AtomicUsize::new(0);
+
+fn cache_dir() -> &'static RwLock<Option<PathBuf>> {
+    static CACHE_DIR: OnceLock<RwLock<Option<PathBuf>>> = OnceLock::new();
+    CACHE_DIR.get_or_init(|| RwLock::new(None))
+}
+
+fn memory() -> &'static Mutex<HashMap<String, CacheEntry>> {
+    static MEMORY: OnceLock<Mutex<HashMap<String, CacheEntry>>> = OnceLock::new();
+    MEMORY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub fn set_cache_dir(dir: &str) -> SupplierResult<()> {
+    let path = PathBuf::from(dir);
+    std::fs::create_dir_all(&path)
+        .map_err(|err| SupplierError::storage(format!("cache dir {dir}: {err}")))?;
+
+    prune_dir(&path, DISK_SIZE_LIMIT);
+    *cache_dir().write().unwrap() = Some(path);
+    Ok(())
+}
+
+pub fn set_bypass(bypass: bool) {
+    BYPASS.store(bypass, Ordering::Relaxed);
+}
+
+pub fn clear() -> SupplierResult<()> {
+    memory().lock().unwrap().clear();
+
+    let maybe_dir = cache_dir().read().unwrap().clone();
+    if let Some(dir) = maybe_dir {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => return Ok(()),
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                std::fs::remove_file(&path).map_err(|err| {
+                    SupplierError::storage(format!("fail to remove cache file {path:?}: {err}"))
+                })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Request level headers (authorization, language, tokens) are part of the key,
+/// client defaults are the same for every request and are not
+pub fn cache_key(method: &str, url: &str, headers: &HeaderMap, body: Option<&[u8]>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b" ");
+    hasher.update(url.as_bytes());
+
+    let mut headers: Vec<_> = headers.iter().collect();
+    headers.sort_by(|(a, a_value), (b, b_value)| {
+        (a.as_str(), a_value.as_bytes()).cmp(&(b.as_str(), b_value.as_bytes()))
+    });
+    for (name, value) in headers {
+        hasher.update(b"\n");
+        hasher.update(name.as_str().as_bytes());
+        hasher.update(b": ");
+        hasher.update(value.as_bytes());
+    }
+
+    if let Some(body) = body {
+        hasher.update(b"\n");
+        hasher.update(body);
+    }
+    hex::encode(hasher.finalize())
+}
+
+pub async fn get(key: &str) -> Option<CacheEntry> {
+    if let Some(entry) = memory().lock().unwrap().get(key) {
+        return Some(entry.clone());
+    }
+
+    let path = entry_path(key)?;
+    let content = tokio::fs::read(&path).await.ok()?;
+    let touched = path.clone();
+    tokio::task::spawn_blocking(move || touch_file(&touched));
+    let entry: CacheEntry = match serde_json::from_slice(&content) {
+        Ok(entry) => entry,
+        Err(err) => {
+            warn!("[cache] broken entry {path:?}: {err}");
+            let _ = tokio::fs::remove_file(&path).await;
+            return None;
+        }
+    };
+
+    put_memory(key, entry.clone());
+    Some(entry)
+}
+
+pub async fn put(key: &str, entry: CacheEntry) {
+    if let Some(path) = entry_path(key) {
+        match serde_json::to_vec(&entry) {
+            Ok(content) => {
+                if let Err(err) = tokio::fs::write(&path, content).await {
+                    warn!("[cache] fail to persist entry {path:?}: {err}");
+                }
+
+                if DISK_WRITES
+                    .fetch_add(1, Ordering::Relaxed)
+                    .is_multiple_of(DISK_PRUNE_INTERVAL)
+                    && let Some(dir) = path.parent().map(Path::to_path_buf)
+                {
+                    let _ =
+                        tokio::task::spawn_blocking(move || prune_dir(&dir, DISK_SIZE_LIMIT)).await;
+                }
+            }
+            Err(err) => warn!("[cache] fail to serialize entry: {err}"),
+        }
+    }
+
+    put_memory(key, entry);
+}
+
+fn put_memory(key: &str, entry: CacheEntry) {
+    let mut memory = memory().lock().unwrap();
+
+    if memory.len() >= MEMORY_ENTRIES_LIMIT && !memory.contains_key(key) {
+        let oldest = memory
+            .iter()
+            .min_by_key(|(_, e)| e.stored_at)
+            .map(|(k, _)| k.clone());
+
+        if let Some(oldest) = oldest {
+            debug!("[cache] evict {oldest}");
+            memory.remove(&oldest);
+        }
+    }
+
+    memory.insert(key.to_owned(), entry);
+}
+
+/// Remove least recently used entries until disk dir fits into `limit` bytes
+fn prune_dir(dir: &Path, limit: u64) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut files: Vec<_> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            let modified = meta.modified().unwrap_or(UNIX_EPOCH);
+            Some((entry.path(), meta.len(), modified))
+        })
+        .collect();
+
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    if total <= limit {
+        return;
+    }
+
+    files.sort_by_key(|(_, _, modified)| *modified);
+    for (path, len, _) in files {
+        if total <= limit {
+            break;
+        }
+
+        debug!("[cache] evict {path:?}");
+        if std::fs::remove_file(&path).is_ok() {
+            total -= len;
+        }
+    }
+}
+
+// modification time is used as last access time by disk eviction
+fn touch_file(path: &Path) {
+    if let Ok(file) = std::fs::File::options().append(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+}
+
+fn entry_path(key: &str) -> Option<PathBuf> {
+    cache_dir()
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|dir| dir.join(format!("{key}.json")))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_apply_ttl_only_inside_scope() {
+        assert_eq!(current_ttl(), None);
+
+        let ttl = scoped(CacheOperation::ContentDetails, async { current_ttl() }).await;
+        assert_eq!(ttl, CacheOperation::ContentDetails.ttl());
+
+        let ttl = scoped(CacheOperation::MediaItemSources, async { current_ttl() }).await;
+        assert_eq!(ttl, None);
+    }
+
+    #[test]
+    fn should_include_body_in_key() {
+        let headers = HeaderMap::new();
+        let search_a = cache_key(
+            "POST",
+            "https://uakino.best/index.php",
+            &headers,
+            Some(b"story=a"),
+        );
+        let search_b = cache_key(
+            "POST",
+            "https://uakino.best/index.php",
+            &headers,
+            Some(b"story=b"),
+        );
+        assert_ne!(search_a, search_b);
+    }
+
+    #[test]
+    fn should_include_headers_in_key() {
+        let url = "https://api.themoviedb.org/3/movie/550";
+        let mut uk = HeaderMap::new();
+        uk.insert("accept-language", "uk".parse().unwrap());
+        let mut en = HeaderMap::new();
+        en.insert("accept-language", "en".parse().unwrap());
+
+        assert_ne!(
+            cache_key("GET", url, &uk, None),
+            cache_key("GET", url, &en, None)
+        );
+        assert_eq!(
+            cache_key("GET", url, &uk, None),
+            cache_key("GET", url, &uk.clone(), None)
+        );
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_files() {
+        let dir = std::env::temp_dir().join(format!("strumok_cache_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for (i, name) in ["old", "used", "new"].iter().enumerate() {
+            let path = dir.join(format!("{name}.json"));
+            std::fs::write(&path, [0u8; 100]).unwrap();
+            let modified = UNIX_EPOCH + Duration::from_secs(1_000 + i as u64);
+            std::fs::File::options()
+                .append(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        }
+        touch_file(&dir.join("used.json"));
+
+        prune_dir(&dir, 200);
+
+        assert!(!dir.join("old.json").exists());
+        assert!(dir.join("used.json").exists());
+        assert!(dir.join("new.json").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn should_expire_entry() {
+        let mut entry = CacheEntry::new("body".into(), None, None);
+        assert!(entry.is_fresh(Duration::from_secs(60)));
+
+        entry.stored_at -= 120;
+        assert!(!entry.is_fresh(Duration::from_secs(60)));
+        assert!(entry.is_usable_stale());
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{
+        Arc, Mutex, OnceLock,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+    },
+};
+
+use log::debug;
+use tokio::sync::Notify;
+
+use crate::{
+    errors::{SupplierError, SupplierResult},
+    models::CancelToken,
+};
+
+#[derive(Default)]
+struct CancelState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancelState {
+    async fn cancelled(&self) {
+        loop {
+            // register waiter before checking flag, so cancel between check and await is not lost
+            let notified = self.notify.notified();
+            if self.cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+fn tokens() -> &'static Mutex<HashMap<CancelToken, Arc<CancelState>>> {
+    static TOKENS: OnceLock<Mutex<HashMap<CancelToken, Arc<CancelState>>>> = OnceLock::new();
+    TOKENS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub fn create_token() -> CancelToken {
+    static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+    let token = CancelToken {
+        id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+    };
+    tokens()
+        .lock()
+        .unwrap()
+        .insert(token, Arc::new(CancelState::default()));
+    token
+}
+
+/// Abort all calls running with token, token stays cancelled until released
+pub fn cancel(token: CancelToken) {
+    if let Some(state) = tokens().lock().unwrap().get(&token) {
+        debug!("[cancel] token {} cancelled", token.id);
+        state.cancelled.store(true, Ordering::SeqCst);
+        state.notify.notify_waiters();
+    }
+}
+
+pub fn release(token: CancelToken) {
+    tokens().lock().unwrap().remove(&token);
+}
+
+pub fn is_cancelled(token: CancelToken) -> bool {
+    tokens()
+        .lock()
+        .unwrap()
+        .get(&token)
+        .is_some_and(|state| state.cancelled.load(Ordering::SeqCst))
+}
+
+/// Run future until it completes or token is cancelled.
+/// Cancelling drops the future, which aborts in-flight requests and every branch of join_all fan-outs
+pub async fn run<T, F>(token: Option<CancelToken>, fut: F) -> SupplierResult<T>
+where
+    F: Future<Output = SupplierResult<T>>,
+{
+    let maybe_state = token.and_then(|token| tokens().lock().unwrap().get(&token).cloned());
+
+    let Some(state) = maybe_state else {
+        return fut.await;
+    };
+
+    tokio::select! {
+        biased;
+        _ = state.cancelled() => Err(SupplierError::Cancelled),
+        res = fut => res,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_abort_running_future() {
+        let token = create_token();
+
+        let handle = tokio::spawn(run(Some(token), async {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            Ok(())
+        }));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        cancel(token);
+
+        let res = tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("future should be aborted")
+            .unwrap();
+        assert!(matches!(res, Err(SupplierError::Cancelled)));
+        assert!(is_cancelled(token));
+
+        release(token);
+        assert!(!is_cancelled(token));
+    }
+
+    #[tokio::test]
+    async fn should_not_start_with_cancelled_token() {
+        let token = create_token();
+        cancel(token);
+
+        let res = run(Some(token), async { Ok(1) }).await;
+        assert!(matches!(res, Err(SupplierError::Cancelled)));
+
+        release(token);
+    }
+}
@@ -0,0 +1,224 @@
+//! Detection of anti-bot interstitials (Cloudflare, DDoS-Guard, captcha walls).
+//!
+//! Such pages are reported as `SupplierError::Blocked` with url host app opens
+//! in WebView, once user passes it cookies are imported and call is retried.
+
+use reqwest::{Response, StatusCode, header::HeaderMap};
+
+use crate::errors::{SupplierError, SupplierResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protection {
+    Cloudflare,
+    DdosGuard,
+    Captcha,
+}
+
+impl Protection {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Protection::Cloudflare => "cloudflare",
+            Protection::DdosGuard => "ddos-guard",
+            Protection::Captcha => "captcha",
+        }
+    }
+}
+
+// present only on challenge pages, checked for any status
+const STRONG_MARKERS: &[(&str, Protection)] = &[
+    ("window._cf_chl_opt", Protection::Cloudflare),
+    ("cf-browser-verification", Protection::Cloudflare),
+    ("check.ddos-guard.net/check.js", Protection::DdosGuard),
+    ("/.well-known/ddos-guard/", Protection::DdosGuard),
+];
+
+// may appear on regular pages too (comment forms, footers), checked for error statuses only
+const WEAK_MARKERS: &[(&str, Protection)] = &[
+    ("<title>Just a moment...</title>", Protection::Cloudflare),
+    (
+        "<title>Attention Required! | Cloudflare</title>",
+        Protection::Cloudflare,
+    ),
+    ("<title>DDoS-Guard</title>", Protection::DdosGuard),
+    // cloudflare injects its jsd script into regular pages of protected sites
+    ("/cdn-cgi/challenge-platform/", Protection::Cloudflare),
+    ("g-recaptcha", Protection::Captcha),
+    ("h-captcha", Protection::Captcha),
+    ("cf-turnstile", Protection::Captcha),
+];
+
+/// Challenge recognised from headers alone, lets callers skip reading body and retrying
+pub fn detect_by_headers(status: StatusCode, headers: &HeaderMap) -> Option<Protection> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_lowercase())
+            .unwrap_or_default()
+    };
+
+    if header("cf-mitigated") == "challenge" {
+        return Some(Protection::Cloudflare);
+    }
+
+    if !is_challenge_status(status) {
+        return None;
+    }
+
+    match header("server").as_str() {
+        "ddos-guard" => Some(Protection::DdosGuard),
+        _ => None,
+    }
+}
+
+pub fn detect(status: StatusCode, headers: &HeaderMap, body: &str) -> Option<Protection> {
+    if let Some(protection) = detect_by_headers(status, headers) {
+        return Some(protection);
+    }
+
+    let find = |markers: &[(&str, Protection)]| {
+        markers
+            .iter()
+            .find(|(marker, _)| body.contains(marker))
+            .map(|(_, protection)| *protection)
+    };
+
+    find(STRONG_MARKERS).or_else(|| {
+        if is_challenge_status(status) {
+            find(WEAK_MARKERS)
+        } else {
+            None
+        }
+    })
+}
+
+/// Body of successful response, `Blocked` error for challenge page and `HttpStatus` for other failures
+pub async fn read_text(res: Response) -> SupplierResult<String> {
+    let status = res.status();
+    let headers = res.headers().clone();
+    let url = res.url().to_string();
+
+    if let Some(protection) = detect_by_headers(status, &headers) {
+        return Err(blocked(protection, url));
+    }
+
+    if status.is_success() {
+        let body = res.text().await?;
+        return match detect(status, &headers, &body) {
+            Some(protection) => Err(blocked(protection, url)),
+            None => Ok(body),
+        };
+    }
+
+    if is_challenge_status(status) {
+        // body of error page is only read to look for markers
+        let body = res.text().await.unwrap_or_default();
+        if let Some(protection) = detect(status, &headers, &body) {
+            return Err(blocked(protection, url));
+        }
+    }
+
+    Err(SupplierError::HttpStatus {
+        status: status.as_u16(),
+        url,
+    })
+}
+
+fn blocked(protection: Protection, url: String) -> SupplierError {
+    SupplierError::Blocked {
+        protection: protection.name().to_owned(),
+        url,
+    }
+}
+
+fn is_challenge_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS | StatusCode::SERVICE_UNAVAILABLE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        headers
+    }
+
+    #[test]
+    fn should_detect_challenge_pages() {
+        let cloudflare = r#"<html><head><title>Just a moment...</title></head>
+            <body><script>window._cf_chl_opt={cvId: '3'};</script></body></html>"#;
+        assert_eq!(
+            detect(
+                StatusCode::SERVICE_UNAVAILABLE,
+                &HeaderMap::new(),
+                cloudflare
+            ),
+            Some(Protection::Cloudflare)
+        );
+        assert_eq!(
+            detect(
+                StatusCode::FORBIDDEN,
+                &headers(&[("cf-mitigated", "challenge")]),
+                ""
+            ),
+            Some(Protection::Cloudflare)
+        );
+        assert_eq!(
+            detect(
+                StatusCode::FORBIDDEN,
+                &headers(&[("server", "ddos-guard")]),
+                ""
+            ),
+            Some(Protection::DdosGuard)
+        );
+        assert_eq!(
+            detect(
+                StatusCode::TOO_MANY_REQUESTS,
+                &HeaderMap::new(),
+                r#"<div class="g-recaptcha" data-sitekey="x"></div>"#
+            ),
+            Some(Protection::Captcha)
+        );
+    }
+
+    #[test]
+    fn should_not_flag_regular_pages() {
+        let page = r#"<html><body><form><div class="g-recaptcha"></div></form></body></html>"#;
+        assert_eq!(detect(StatusCode::OK, &HeaderMap::new(), page), None);
+        assert_eq!(
+            detect(
+                StatusCode::FORBIDDEN,
+                &headers(&[("server", "cloudflare")]),
+                "Forbidden"
+            ),
+            None
+        );
+        assert_eq!(
+            detect(
+                StatusCode::OK,
+                &headers(&[("server", "ddos-guard")]),
+                "<html></html>"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn should_not_flag_page_with_cloudflare_script() {
+        let page = r#"<html><body><div class="catalog"></div>
+            <script src="/cdn-cgi/challenge-platform/scripts/jsd/main.js"></script></body></html>"#;
+        assert_eq!(detect(StatusCode::OK, &HeaderMap::new(), page), None);
+        assert_eq!(
+            detect(StatusCode::FORBIDDEN, &HeaderMap::new(), page),
+            Some(Protection::Cloudflare)
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//! Cookie jar shared by every client crate builds.
+//!
+//! Host app imports cookies (e.g. challenge clearance solved in WebView) and
+//! exports them back. When cookies file is set jar is loaded from it and
+//! saved after changes, so logins survive restart. Response cookies are saved
+//! in batches, at most once per `SAVE_DEBOUNCE`.
+
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc, Mutex, OnceLock, RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use cookie_store::{Cookie, CookieDomain, CookieExpiration, CookieStore};
+use log::warn;
+use reqwest::{Url, header::HeaderValue};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::{SupplierError, SupplierResult},
+    models::HttpCookie,
+};
+
+const SAVE_DEBOUNCE: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+pub struct SharedJar {
+    store: RwLock<CookieStore>,
+    file: RwLock<Option<PathBuf>>,
+    // save of response cookies is scheduled
+    save_pending: AtomicBool,
+    // one writer of cookies file at a time
+    write_lock: Mutex<()>,
+}
+
+// on disk form of `HttpCookie`
+#[derive(Serialize, Deserialize)]
+struct StoredCookie {
+    name: String,
+    value: String,
+    domain: String,
+    path: Option<String>,
+    expires: Option<i64>,
+    secure: bool,
+    http_only: bool,
+}
+
+pub fn jar() -> Arc<SharedJar> {
+    static JAR: OnceLock<Arc<SharedJar>> = OnceLock::new();
+    JAR.get_or_init(Default::default).clone()
+}
+
+/// Load jar from file and keep it in sync with every change, cookies already in jar are kept
+pub fn set_cookies_file(path: &str) -> SupplierResult<()> {
+    let path = PathBuf::from(path);
+    let jar = jar();
+
+    match std::fs::read(&path) {
+        Ok(content) => match serde_json::from_slice::<Vec<StoredCookie>>(&content) {
+            Ok(stored) => {
+                let mut store = jar.store.write().unwrap();
+                for cookie in stored.into_iter().map(HttpCookie::from) {
+                    if let Err(err) = insert(&mut store, &cookie) {
+                        warn!("[cookies] skip stored cookie {}: {err}", cookie.name);
+                    }
+                }
+            }
+            Err(err) => warn!("[cookies] broken cookies file {path:?}: {err}"),
+        },
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => {
+            return Err(SupplierError::invalid_params(format!(
+                "cookies file {path:?}: {err}"
+            )));
+        }
+    }
+
+    *jar.file.write().unwrap() = Some(path);
+    jar.save();
+    Ok(())
+}
+
+/// Add or replace cookies, e.g. copied from WebView after challenge was passed
+pub fn import(cookies: &[HttpCookie]) -> SupplierResult<()> {
+    let jar = jar();
+    {
+        let mut store = jar.store.write().unwrap();
+        for cookie in cookies {
+            insert(&mut store, cookie)?;
+        }
+    }
+    jar.save();
+    Ok(())
+}
+
+/// Unexpired cookies of domain (and its subdomains) or all cookies
+pub fn export(domain: Option<&str>) -> Vec<HttpCookie> {
+    let jar = jar();
+    let store = jar.store.read().unwrap();
+    store
+        .iter_unexpired()
+        .filter(|c| domain.is_none_or(|d| in_domain(c, d)))
+        .map(to_model)
+        .collect()
+}
+
+/// Remove cookies of domain (and its subdomains) or all cookies
+pub fn clear(domain: Option<&str>) {
+    let jar = jar();
+    {
+        let mut store = jar.store.write().unwrap();
+        match domain {
+            Some(domain) => {
+                let kept = store
+                    .iter_unexpired()
+                    .filter(|c| !in_domain(c, domain))
+                    .cloned()
+                    .map(Ok::<_, ()>);
+                *store = CookieStore::from_cookies(kept, false).unwrap_or_default();
+            }
+            None => store.clear(),
+        }
+    }
+    jar.save();
+}
+
+impl SharedJar {
+    // responses often set cookies in bursts, so they are written once burst is over
+    fn schedule_save(&self) {
+        if self.file.read().unwrap().is_none() || self.save_pending.swap(true, Ordering::AcqRel) {
+            return;
+        }
+
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            self.save_pending.store(false, Ordering::Release);
+            self.save();
+            return;
+        };
+
+        // only shared jar is handed to clients
+        runtime.spawn(async {
+            tokio::time::sleep(SAVE_DEBOUNCE).await;
+            let jar = jar();
+            jar.save_pending.store(false, Ordering::Release);
+            let _ = tokio::task::spawn_blocking(move || jar.save()).await;
+        });
+    }
+
+    fn save(&self) {
+        let Some(path) = self.file.read().unwrap().clone() else {
+            return;
+        };
+
+        let _write = self.write_lock.lock().unwrap();
+
+        let stored: Vec<_> = {
+            let store = self.store.read().unwrap();
+            store
+                .iter_unexpired()
+                .map(|c| StoredCookie::from(to_model(c)))
+                .collect()
+        };
+
+        // temp file is renamed once written, so interrupted write keeps previous file
+        let tmp = path.with_extension("tmp");
+        let res = serde_json::to_vec(&stored)
+            .map_err(|err| err.to_string())
+            .and_then(|content| std::fs::write(&tmp, content).map_err(|err| err.to_string()))
+            .and_then(|_| std::fs::rename(&tmp, &path).map_err(|err| err.to_string()));
+        if let Err(err) = res {
+            warn!("[cookies] fail to persist cookies {path:?}: {err}");
+        }
+    }
+}
+
+impl reqwest::cookie::CookieStore for SharedJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let cookies: Vec<_> = cookie_headers
+            .filter_map(|val| val.to_str().ok())
+            .filter_map(|val| cookie_store::RawCookie::parse(val.to_owned()).ok())
+            .collect();
+        if cookies.is_empty() {
+            return;
+        }
+
+        self.store
+            .write()
+            .unwrap()
+            .store_response_cookies(cookies.into_iter(), url);
+        self.schedule_save();
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let value = self
+            .store
+            .read()
+            .unwrap()
+            .get_request_values(url)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if value.is_empty() {
+            return None;
+        }
+
+        HeaderValue::from_str(&value).ok()
+    }
+}
+
+fn insert(store: &mut CookieStore, cookie: &HttpCookie) -> SupplierResult<()> {
+    let invalid =
+        |reason: String| SupplierError::invalid_params(format!("cookie {}: {reason}", cookie.name));
+
+    let domain = cookie.domain.trim().trim_start_matches('.');
+    let path = cookie.path.as_deref().unwrap_or("/");
+    let url =
+        Url::parse(&format!("https://{domain}{path}")).map_err(|err| invalid(err.to_string()))?;
+
+    let mut set_cookie = format!(
+        "{}={}; Domain={domain}; Path={path}",
+        cookie.name, cookie.value
+    );
+    if let Some(expires) = cookie.expires {
+        set_cookie.push_str(&format!("; Max-Age={}", expires - now()));
+    }
+    if cookie.secure {
+        set_cookie.push_str("; Secure");
+    }
+    if cookie.http_only {
+        set_cookie.push_str("; HttpOnly");
+    }
+
+    let raw = cookie_store::RawCookie::parse(set_cookie).map_err(|err| invalid(err.to_string()))?;
+    let parsed = Cookie::try_from_raw_cookie(&raw, &url).map_err(|err| invalid(err.to_string()))?;
+    store
+        .insert(parsed.into_owned(), &url)
+        .map_err(|err| invalid(err.to_string()))?;
+    Ok(())
+}
+
+fn in_domain(cookie: &Cookie, domain: &str) -> bool {
+    let domain = domain.trim().trim_start_matches('.').to_lowercase();
+    cookie.domain.as_cow().is_some_and(|d| {
+        d == domain
+            || d.strip_suffix(domain.as_str())
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
+fn to_model(cookie: &Cookie) -> HttpCookie {
+    let domain = match &cookie.domain {
+        CookieDomain::Suffix(d) => format!(".{d}"),
+        other => other.as_cow().unwrap_or_default().into_owned(),
+    };
+
+    HttpCookie {
+        name: cookie.name().to_owned(),
+        value: cookie.value().to_owned(),
+        domain,
+        path: Some(String::from(&cookie.path)),
+        expires: match &cookie.expires {
+            CookieExpiration::AtUtc(at) => Some(at.unix_timestamp()),
+            CookieExpiration::SessionEnd => None,
+        },
+        secure: cookie.secure().unwrap_or(false),
+        http_only: cookie.http_only().unwrap_or(false),
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+impl From<HttpCookie> for StoredCookie {
+    fn from(c: HttpCookie) -> Self {
+        StoredCookie {
+            name: c.name,
+            value: c.value,
+            domain: c.domain,
+            path: c.path,
+            expires: c.expires,
+            secure: c.secure,
+            http_only: c.http_only,
+        }
+    }
+}
+
+impl From<StoredCookie> for HttpCookie {
+    fn from(c: StoredCookie) -> Self {
+        HttpCookie {
+            name: c.name,
+            value: c.value,
+            domain: c.domain,
+            path: c.path,
+            expires: c.expires,
+            secure: c.secure,
+            http_only: c.http_only,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::cookie::CookieStore as _;
+
+    fn cookie(name: &str, domain: &str) -> HttpCookie {
+        HttpCookie {
+            name: name.into(),
+            value: "value".into(),
+            domain: domain.into(),
+            path: None,
+            expires: Some(now() + 3600),
+            secure: true,
+            http_only: true,
+        }
+    }
+
+    #[test]
+    fn should_import_export_and_clear_per_domain() {
+        import(&[
+            cookie("cf_clearance", ".jar-test.example"),
+            cookie("session", "other-jar-test.example"),
+        ])
+        .unwrap();
+
+        let header = jar().cookies(&Url::parse("https://api.jar-test.example/x").unwrap());
+        assert_eq!(header.unwrap(), "cf_clearance=value");
+
+        let exported = export(Some("jar-test.example"));
+        assert_eq!(exported.len(), 1);
+        assert_eq!(exported[0].domain, ".jar-test.example");
+        assert_eq!(exported[0].path.as_deref(), Some("/"));
+
+        clear(Some("jar-test.example"));
+        assert!(export(Some("jar-test.example")).is_empty());
+        assert_eq!(export(Some("other-jar-test.example")).len(), 1);
+        clear(Some("other-jar-test.example"));
+    }
+
+    #[tokio::test]
+    async fn should_save_response_cookies_once_burst_is_over() {
+        let path =
+            std::env::temp_dir().join(format!("strumok_cookies_{}.json", std::process::id()));
+        set_cookies_file(path.to_str().unwrap()).unwrap();
+
+        let url = Url::parse("https://save-test.example/").unwrap();
+        for name in ["first", "second"] {
+            let header = HeaderValue::from_str(&format!("{name}=value; Max-Age=3600")).unwrap();
+            jar().set_cookies(&mut [&header].into_iter(), &url);
+        }
+
+        assert!(jar().save_pending.load(Ordering::Acquire));
+
+        tokio::time::sleep(SAVE_DEBOUNCE * 2).await;
+        let stored = std::fs::read_to_string(&path).unwrap();
+        assert!(stored.contains("first") && stored.contains("second"));
+        assert!(!path.with_extension("tmp").exists());
+
+        clear(Some("save-test.example"));
+        *jar().file.write().unwrap() = None;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn should_reject_invalid_cookie() {
+        assert!(matches!(
+            import(&[cookie("bad", "")]),
+            Err(SupplierError::InvalidParams { .. })
+        ));
+    }
+}
@@ -0,0 +1,70 @@
+//! Secrets of authenticated suppliers (api keys, tokens) pushed by host app at runtime.
+
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+use crate::errors::{SupplierError, SupplierResult};
+
+// supplier -> credential name -> value
+fn store() -> &'static RwLock<HashMap<String, HashMap<String, String>>> {
+    static STORE: OnceLock<RwLock<HashMap<String, HashMap<String, String>>>> = OnceLock::new();
+    STORE.get_or_init(Default::default)
+}
+
+/// Set or rotate credential of supplier, empty value removes it
+pub fn set(supplier: &str, name: &str, value: &str) {
+    let mut store = store().write().unwrap();
+    if value.is_empty() {
+        if let Some(credentials) = store.get_mut(supplier) {
+            credentials.remove(name);
+        }
+        return;
+    }
+
+    store
+        .entry(supplier.to_owned())
+        .or_default()
+        .insert(name.to_owned(), value.to_owned());
+}
+
+pub fn get(supplier: &str, name: &str) -> Option<String> {
+    let store = store().read().unwrap();
+    store.get(supplier)?.get(name).cloned()
+}
+
+/// Credential value or "not configured" error when host app didn't set it
+pub fn required(supplier: &str, name: &str) -> SupplierResult<String> {
+    get(supplier, name).ok_or_else(|| SupplierError::not_configured(supplier, name))
+}
+
+/// Remove all credentials of supplier
+pub fn clear(supplier: &str) {
+    store().write().unwrap().remove(supplier);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_set_rotate_and_clear_credentials() {
+        let supplier = "CredentialsTest";
+        assert!(matches!(
+            required(supplier, "api_key"),
+            Err(SupplierError::NotConfigured { .. })
+        ));
+
+        set(supplier, "api_key", "first");
+        set(supplier, "api_key", "second");
+        assert_eq!(required(supplier, "api_key").unwrap(), "second");
+
+        set(supplier, "api_key", "");
+        assert_eq!(get(supplier, "api_key"), None);
+
+        set(supplier, "token", "value");
+        clear(supplier);
+        assert_eq!(get(supplier, "token"), None);
+    }
+}
@@ -1,15 +1,59 @@
 mod playlist_html;
 mod tests;
 
-use anyhow::anyhow;
 use indexmap::IndexMap;
-use std::collections::BTreeMap;
+use log::warn;
+use std::{borrow::Borrow, collections::BTreeMap, hash::Hash};
 
 use reqwest::{self, RequestBuilder};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use super::html::DOMProcessor;
-use crate::models::ContentMediaItem;
+use super::params::{self, LegacyParams, MediaParams};
+use super::{mirrors, playerjs};
+use crate::errors::{SupplierError, SupplierResult};
+use crate::models::{ContentMediaItem, ContentMediaItemSource};
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct PlaylistFile {
+    pub description: String,
+    pub file: String,
+}
+
+/// Media item params of ajax playlist: one file per dub/player
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct PlaylistParams {
+    pub files: Vec<PlaylistFile>,
+}
+
+impl MediaParams for PlaylistParams {
+    const KIND: &'static str = "dle_playlist";
+    const VERSION: u32 = 2;
+
+    // v1 is flat [description, file, description, file, ...]
+    fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+        match legacy {
+            LegacyParams::Raw(raw) => {
+                if !raw.len().is_multiple_of(2) {
+                    return Err(SupplierError::invalid_params("Wrong params size"));
+                }
+
+                let files = raw
+                    .chunks(2)
+                    .map(|chunk| PlaylistFile {
+                        description: chunk[0].clone(),
+                        file: chunk[1].clone(),
+                    })
+                    .collect();
+
+                Ok(Self { files })
+            }
+            LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(format!(
+                "dle playlist params version {version} is not supported"
+            ))),
+        }
+    }
+}
 
 pub fn search_request(url: &str, query: &str) -> RequestBuilder {
     let client = super::create_client();
@@ -23,11 +67,11 @@ pub fn search_request(url: &str, query: &str) -> RequestBuilder {
     ])
 }
 
-pub fn get_channel_url(
-    channels_map: &IndexMap<&'static str, String>,
+pub fn get_channel_url<K: Borrow<str> + Hash + Eq>(
+    channels_map: &IndexMap<K, String>,
     channel: &str,
     page: u16,
-) -> anyhow::Result<String> {
+) -> SupplierResult<String> {
     match channels_map.get(channel) {
         Some(url) => {
             if url.ends_with("/page/") {
@@ -36,25 +80,85 @@ pub fn get_channel_url(
                 Ok(url.into())
             }
         }
-        _ => Err(anyhow!("unknown channel")),
+        _ => Err(SupplierError::unknown_channel(channel)),
     }
 }
 
-pub fn extract_id_from_url(url: &str, mut id: String) -> String {
-    if id.len() <= (url.len() + 5) {
+/// Id is page path without `.html`, same for links of any mirror of `url`
+pub fn extract_id_from_url(url: &str, id: String) -> String {
+    let path = mirrors::strip_base(url, &id).trim_start_matches('/');
+    if path.len() <= 5 {
         return String::new();
     }
 
-    id.drain((url.len() + 1)..(id.len() - 5)).collect()
+    path.get(..path.len() - 5).unwrap_or_default().to_owned()
 }
 
 pub fn format_id_from_url(url: &str, id: &str) -> String {
     format!("{url}/{id}.html")
 }
 
+/// News id is numeric prefix of last id segment: `films/123-title` -> `123`
+pub fn extract_news_id(id: &str) -> SupplierResult<&str> {
+    id.rsplit('/')
+        .next()
+        .and_then(|s| s.split_once('-'))
+        .map(|(s, _)| s)
+        .ok_or_else(|| SupplierError::invalid_params(format!("cant extract news_id for id: {id}")))
+}
+
+/// Ajax playlist request of news, sites with login hash check it instead of cache busting time
+pub fn ajax_playlist_request(
+    url: &str,
+    id: &str,
+    user_hash: Option<&str>,
+) -> SupplierResult<RequestBuilder> {
+    let news_id = extract_news_id(id)?;
+
+    let request = super::create_client()
+        .get(format!("{url}/engine/ajax/playlists.php"))
+        .query(&[("xfield", "playlist"), ("news_id", news_id)])
+        .header("Referer", format_id_from_url(url, id));
+
+    Ok(match user_hash {
+        Some(user_hash) => request.query(&[("user_hash", user_hash)]),
+        None => request.query(&[("time", "0")]),
+    })
+}
+
+/// Resolve every playerjs file of ajax playlist media item, broken players are skipped
+/// as long as other players resolved something, otherwise last error is returned
+pub async fn load_playlist_sources(
+    params: &[String],
+) -> SupplierResult<Vec<ContentMediaItemSource>> {
+    let playlist: PlaylistParams = params::decode(params)?;
+
+    let mut results = vec![];
+    let mut last_error = None;
+    for PlaylistFile { description, file } in &playlist.files {
+        match playerjs::load_and_parse_playerjs_sources(
+            super::create_client().get(file),
+            description,
+        )
+        .await
+        {
+            Ok(mut sources) => results.append(&mut sources),
+            Err(err) => {
+                warn!("playlist file {file} failed: {err}");
+                last_error = Some(err);
+            }
+        }
+    }
+
+    match last_error {
+        Some(err) if results.is_empty() => Err(err),
+        _ => Ok(results),
+    }
+}
+
 pub async fn load_ajax_playlist(
     playlist_req: reqwest::RequestBuilder,
-) -> anyhow::Result<Vec<ContentMediaItem>> {
+) -> SupplierResult<Vec<ContentMediaItem>> {
     const ALLOWED_VIDEO_HOSTS: &[&str] = &["ashdi", "tortuga", "moonanime", "monstro"];
 
     #[derive(Deserialize, Debug)]
@@ -62,21 +166,15 @@ pub async fn load_ajax_playlist(
         response: String,
     }
 
-    let res_text = playlist_req
-        .header("X-Requested-With", "XMLHttpRequest")
-        .send()
-        .await?
-        .text()
-        .await?;
-
-    let res: AjaxPlaylistResponse = serde_json::from_str(&res_text)?;
+    let res: AjaxPlaylistResponse =
+        super::fetch_json(playlist_req.header("X-Requested-With", "XMLHttpRequest")).await?;
 
     let html_fragment = scraper::Html::parse_fragment(&res.response);
     let root = &html_fragment.root_element();
 
     let playlist = playlist_html::AjaxPlaylistProcessor::new().process(root);
 
-    let mut sorted_media_items: BTreeMap<u32, ContentMediaItem> = BTreeMap::new();
+    let mut sorted_media_items: BTreeMap<u32, (ContentMediaItem, PlaylistParams)> = BTreeMap::new();
 
     for video in playlist.videos {
         if !ALLOWED_VIDEO_HOSTS
@@ -86,16 +184,16 @@ pub async fn load_ajax_playlist(
             continue;
         }
 
-        let media_item =
-            sorted_media_items
-                .entry(video.number)
-                .or_insert_with(|| ContentMediaItem {
-                    title: video.name,
-                    section: None,
-                    image: None,
-                    sources: None,
-                    params: vec![],
-                });
+        let (_, media_item_params) = sorted_media_items.entry(video.number).or_insert_with(|| {
+            let media_item = ContentMediaItem {
+                title: video.name,
+                section: None,
+                image: None,
+                sources: None,
+                params: vec![],
+            };
+            (media_item, PlaylistParams::default())
+        });
 
         let description = playlist
             .lables
@@ -105,14 +203,23 @@ pub async fn load_ajax_playlist(
             .collect::<Vec<String>>()
             .join(" ");
 
-        media_item.params.push(description);
-
         let mut file = video.file;
         if file.starts_with("//") {
             file.insert_str(0, "https:");
         }
-        media_item.params.push(file);
+
+        media_item_params
+            .files
+            .push(PlaylistFile { description, file });
     }
 
-    Ok(sorted_media_items.into_values().collect())
+    let media_items = sorted_media_items
+        .into_values()
+        .map(|(mut media_item, media_item_params)| {
+            media_item.params = params::encode(&media_item_params);
+            media_item
+        })
+        .collect();
+
+    Ok(media_items)
 }
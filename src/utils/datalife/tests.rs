@@ -8,6 +8,51 @@ async fn shold_load_large_dle_playlist() {
         .get("https://anitube.in.ua/engine/ajax/playlists.php?news_id=94&xfield=playlist&user_hash=867ca5be02de10b799c164d7b7c31e6eece1bb10");
 
     let _ = super::load_ajax_playlist(playlist_req).await.unwrap();
+}
+
+#[test]
+fn should_extract_news_id() {
+    assert_eq!(super::extract_news_id("31-zapisnik-smert").unwrap(), "31");
+    assert_eq!(
+        super::extract_news_id("seriesss/drama_series/24161-bilyy-lotos-3-sezon").unwrap(),
+        "24161"
+    );
+    assert!(super::extract_news_id("news/").is_err());
+}
+
+#[tokio::test]
+async fn should_skip_broken_player_unless_all_failed() {
+    use crate::errors::SupplierError;
+    use crate::utils::{
+        har::{self, StubResponse},
+        params,
+    };
+
+    let playlist = |files: &[&str]| {
+        params::encode(&super::PlaylistParams {
+            files: files
+                .iter()
+                .map(|&file| super::PlaylistFile {
+                    description: "Озвучка".into(),
+                    file: file.into(),
+                })
+                .collect(),
+        })
+    };
+    let player = || {
+        vec![StubResponse {
+            method: "GET".into(),
+            url: "https://ashdi.test/vod/1".into(),
+            content_type: "text/html".into(),
+            body: r#"new Playerjs({id:"player", file:"https://cdn.test/1.m3u8"});"#.into(),
+        }]
+    };
+
+    let params = playlist(&["https://ashdi.test/vod/1", "https://ashdi.test/vod/2"]);
+    let sources = har::stub(player(), super::load_playlist_sources(&params)).await;
+    assert_eq!(sources.unwrap().len(), 1);
 
-    
+    let params = playlist(&["https://ashdi.test/vod/2"]);
+    let sources = har::stub(player(), super::load_playlist_sources(&params)).await;
+    assert!(matches!(sources, Err(SupplierError::NotFound { .. })));
 }
@@ -1,47 +1,377 @@
+//! DNS resolution of supplier clients.
+//!
+//! Hosts are answered from static overrides first, then by the chain of
+//! resolvers (system, DoH and DoT providers) in configured order.
+
 use hickory_resolver::Resolver;
 use hickory_resolver::TokioResolver;
-use hickory_resolver::config::ResolverConfig;
+use hickory_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
 use hickory_resolver::name_server::TokioConnectionProvider;
+use log::{debug, warn};
+use reqwest::Url;
 use reqwest::dns::Addrs;
 use reqwest::dns::Name;
 use reqwest::dns::Resolve;
 use reqwest::dns::Resolving;
-use std::net::SocketAddr;
-use std::sync::Arc;
-use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
+use tokio::sync::OnceCell;
 
-#[derive(Debug, Default, Clone)]
-pub struct DoHResolver {
-    state: Arc<OnceLock<TokioResolver>>,
-}
-
-impl DoHResolver {
-    fn init_resolver(&self) -> TokioResolver {
-        Resolver::builder(TokioConnectionProvider::default())
-            .unwrap_or_else(|_| {
-                Resolver::builder_with_config(
-                    ResolverConfig::cloudflare(),
-                    TokioConnectionProvider::default(),
-                )
+use crate::errors::{SupplierError, SupplierResult};
+use crate::models::{DnsConfig, DnsLookup, DnsProtocol, DnsResolver};
+
+const DOT_PORT: u16 = 853;
+
+struct Upstream {
+    spec: DnsResolver,
+    // built on first lookup, DoH host of custom resolver may need bootstrap lookup
+    resolver: OnceCell<TokioResolver>,
+}
+
+#[derive(Default)]
+struct Chain {
+    overrides: HashMap<String, Vec<IpAddr>>,
+    upstreams: Vec<Upstream>,
+    ttl: TtlOpts,
+}
+
+#[derive(Default, Clone, Copy)]
+struct TtlOpts {
+    min: Option<Duration>,
+    max: Option<Duration>,
+    negative: Option<Duration>,
+}
+
+fn chain() -> &'static RwLock<Arc<Chain>> {
+    static CHAIN: OnceLock<RwLock<Arc<Chain>>> = OnceLock::new();
+    CHAIN.get_or_init(|| RwLock::new(Arc::new(build_chain(&DnsConfig::default()).unwrap())))
+}
+
+/// Replace resolvers, overrides and ttl of all supplier clients, applies to new connections
+pub fn set_config(config: &DnsConfig) -> SupplierResult<()> {
+    let new_chain = build_chain(config)?;
+    *chain().write().unwrap() = Arc::new(new_chain);
+    Ok(())
+}
+
+/// Resolve host same way supplier clients do, reporting resolver that answered
+pub async fn lookup(host: &str) -> SupplierResult<DnsLookup> {
+    let (addrs, resolver) = resolve_host(host).await?;
+    Ok(DnsLookup {
+        addresses: addrs.iter().map(|a| a.to_string()).collect(),
+        resolver,
+    })
+}
+
+async fn resolve_host(host: &str) -> SupplierResult<(Vec<IpAddr>, String)> {
+    let chain = chain().read().unwrap().clone();
+
+    if let Some(addrs) = chain.overrides.get(&host.to_lowercase()) {
+        return Ok((addrs.clone(), "override".into()));
+    }
+
+    let mut last_err = None;
+    for upstream in &chain.upstreams {
+        let label = label(&upstream.spec);
+        let res = match upstream
+            .resolver
+            .get_or_try_init(|| build_resolver(&upstream.spec, chain.ttl))
+            .await
+        {
+            Ok(resolver) => resolver
+                .lookup_ip(host)
+                .await
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+
+        match res {
+            Ok(lookup) => {
+                debug!("[dns] {host} resolved by {label}");
+                return Ok((lookup.iter().collect(), label));
+            }
+            Err(err) => {
+                warn!("[dns] {label} failed to resolve {host}: {err}");
+                last_err = Some(SupplierError::Network {
+                    message: format!("{label} failed to resolve {host}: {err}"),
+                });
+            }
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| SupplierError::Network {
+        message: format!("no dns resolver for {host}"),
+    }))
+}
+
+fn build_chain(config: &DnsConfig) -> SupplierResult<Chain> {
+    let specs = if config.resolvers.is_empty() {
+        vec![
+            DnsResolver::System,
+            DnsResolver::Cloudflare {
+                protocol: DnsProtocol::Udp,
+            },
+        ]
+    } else {
+        config.resolvers.clone()
+    };
+
+    for spec in &specs {
+        validate(spec)?;
+    }
+
+    let overrides = config
+        .overrides
+        .iter()
+        .map(|(host, ips)| {
+            if ips.is_empty() {
+                return Err(SupplierError::invalid_params(format!(
+                    "no addresses for dns override of {host}"
+                )));
+            }
+            Ok((host.trim().to_lowercase(), parse_ips(ips)?))
+        })
+        .collect::<SupplierResult<_>>()?;
+
+    let secs = |s: Option<u32>| s.map(|s| Duration::from_secs(s as u64));
+    let ttl = TtlOpts {
+        min: secs(config.min_ttl_secs),
+        max: secs(config.max_ttl_secs),
+        negative: secs(config.negative_ttl_secs),
+    };
+    if let (Some(min), Some(max)) = (ttl.min, ttl.max)
+        && min > max
+    {
+        return Err(SupplierError::invalid_params("dns min ttl exceeds max ttl"));
+    }
+
+    Ok(Chain {
+        overrides,
+        upstreams: specs
+            .into_iter()
+            .map(|spec| Upstream {
+                spec,
+                resolver: OnceCell::new(),
             })
-            .build()
+            .collect(),
+        ttl,
+    })
+}
+
+fn validate(spec: &DnsResolver) -> SupplierResult<()> {
+    match spec {
+        DnsResolver::CustomHttps { url, ips } => {
+            parse_doh_url(url)?;
+            parse_ips(ips)?;
+        }
+        DnsResolver::CustomTls { server_name, ips } => {
+            if server_name.is_empty() || ips.is_empty() {
+                return Err(SupplierError::invalid_params(
+                    "DoT resolver needs server name and ips",
+                ));
+            }
+            parse_ips(ips)?;
+        }
+        _ => {}
     }
+    Ok(())
 }
 
+async fn build_resolver(spec: &DnsResolver, ttl: TtlOpts) -> SupplierResult<TokioResolver> {
+    let provider = TokioConnectionProvider::default();
+    let config = match spec {
+        DnsResolver::System => {
+            let mut builder =
+                Resolver::builder(provider).map_err(|err| SupplierError::Network {
+                    message: format!("system dns config: {err}"),
+                })?;
+            apply_ttl(builder.options_mut(), ttl);
+            return Ok(builder.build());
+        }
+        DnsResolver::Cloudflare { protocol } => match protocol {
+            DnsProtocol::Udp => ResolverConfig::cloudflare(),
+            DnsProtocol::Https => ResolverConfig::cloudflare_https(),
+            DnsProtocol::Tls => ResolverConfig::cloudflare_tls(),
+        },
+        DnsResolver::Google { protocol } => match protocol {
+            DnsProtocol::Udp => ResolverConfig::google(),
+            DnsProtocol::Https => ResolverConfig::google_https(),
+            DnsProtocol::Tls => ResolverConfig::google_tls(),
+        },
+        DnsResolver::Quad9 { protocol } => match protocol {
+            DnsProtocol::Udp => ResolverConfig::quad9(),
+            DnsProtocol::Https => ResolverConfig::quad9_https(),
+            DnsProtocol::Tls => ResolverConfig::quad9_tls(),
+        },
+        DnsResolver::CustomHttps { url, ips } => {
+            let url = parse_doh_url(url)?;
+            let host = url.host_str().unwrap_or_default().to_owned();
+            let port = url.port_or_known_default().unwrap_or(443);
+
+            let ips = match parse_ips(ips)? {
+                ips if !ips.is_empty() => ips,
+                // bootstrap with system resolver, custom resolver can't resolve itself
+                _ => tokio::net::lookup_host((host.as_str(), port))
+                    .await
+                    .map_err(|err| SupplierError::Network {
+                        message: format!("DoH host {host}: {err}"),
+                    })?
+                    .map(|addr| addr.ip())
+                    .collect(),
+            };
+
+            let mut group = NameServerConfigGroup::from_ips_https(&ips, port, host, true);
+            for server in group.iter_mut() {
+                server.http_endpoint = Some(url.path().to_owned());
+            }
+            ResolverConfig::from_parts(None, vec![], group)
+        }
+        DnsResolver::CustomTls { server_name, ips } => ResolverConfig::from_parts(
+            None,
+            vec![],
+            NameServerConfigGroup::from_ips_tls(
+                &parse_ips(ips)?,
+                DOT_PORT,
+                server_name.clone(),
+                true,
+            ),
+        ),
+    };
+
+    let mut opts = ResolverOpts::default();
+    apply_ttl(&mut opts, ttl);
+    Ok(Resolver::builder_with_config(config, provider)
+        .with_options(opts)
+        .build())
+}
+
+fn apply_ttl(opts: &mut ResolverOpts, ttl: TtlOpts) {
+    opts.positive_min_ttl = ttl.min.or(opts.positive_min_ttl);
+    opts.positive_max_ttl = ttl.max.or(opts.positive_max_ttl);
+    opts.negative_max_ttl = ttl.negative.or(opts.negative_max_ttl);
+}
+
+fn label(spec: &DnsResolver) -> String {
+    let protocol = |p: &DnsProtocol| match p {
+        DnsProtocol::Udp => "udp",
+        DnsProtocol::Https => "https",
+        DnsProtocol::Tls => "tls",
+    };
+
+    match spec {
+        DnsResolver::System => "system".into(),
+        DnsResolver::Cloudflare { protocol: p } => format!("cloudflare-{}", protocol(p)),
+        DnsResolver::Google { protocol: p } => format!("google-{}", protocol(p)),
+        DnsResolver::Quad9 { protocol: p } => format!("quad9-{}", protocol(p)),
+        DnsResolver::CustomHttps { url, .. } => format!("https {url}"),
+        DnsResolver::CustomTls { server_name, .. } => format!("tls {server_name}"),
+    }
+}
+
+fn parse_doh_url(url: &str) -> SupplierResult<Url> {
+    match Url::parse(url) {
+        Ok(parsed) if parsed.scheme() == "https" && parsed.host_str().is_some() => Ok(parsed),
+        _ => Err(SupplierError::invalid_params(format!(
+            "DoH url has to be https url: {url}"
+        ))),
+    }
+}
+
+fn parse_ips(ips: &[String]) -> SupplierResult<Vec<IpAddr>> {
+    ips.iter()
+        .map(|ip| {
+            ip.trim()
+                .parse()
+                .map_err(|_| SupplierError::invalid_params(format!("invalid ip address: {ip}")))
+        })
+        .collect()
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct DoHResolver;
+
 impl Resolve for DoHResolver {
     fn resolve(&self, name: Name) -> Resolving {
-        let hickory_resolver = self.clone();
-
         Box::pin(async move {
-            let resolver = hickory_resolver
-                .state
-                .get_or_init(|| hickory_resolver.init_resolver());
+            let (ips, _) = resolve_host(name.as_str()).await?;
 
-            let lookup = resolver.lookup_ip(name.as_str()).await?;
-
-            let addrs: Addrs = Box::new(lookup.into_iter().map(|addr| SocketAddr::new(addr, 0)));
+            let addrs: Addrs = Box::new(ips.into_iter().map(|addr| SocketAddr::new(addr, 0)));
 
             Ok(addrs)
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_answer_from_overrides() {
+        let config = DnsConfig {
+            overrides: HashMap::from([(
+                "Poisoned.Example".to_owned(),
+                vec!["203.0.113.7".to_owned()],
+            )]),
+            ..Default::default()
+        };
+        set_config(&config).unwrap();
+
+        let res = lookup("poisoned.example").await.unwrap();
+        assert_eq!(res.addresses, vec!["203.0.113.7"]);
+        assert_eq!(res.resolver, "override");
+
+        set_config(&DnsConfig::default()).unwrap();
+    }
+
+    #[test]
+    fn should_reject_invalid_config() {
+        let invalid = [
+            DnsConfig {
+                resolvers: vec![DnsResolver::CustomHttps {
+                    url: "http://dns.example/dns-query".into(),
+                    ips: vec![],
+                }],
+                ..Default::default()
+            },
+            DnsConfig {
+                resolvers: vec![DnsResolver::CustomTls {
+                    server_name: "dns.example".into(),
+                    ips: vec![],
+                }],
+                ..Default::default()
+            },
+            DnsConfig {
+                overrides: HashMap::from([("a.example".into(), vec!["not ip".into()])]),
+                ..Default::default()
+            },
+            DnsConfig {
+                min_ttl_secs: Some(600),
+                max_ttl_secs: Some(60),
+                ..Default::default()
+            },
+        ];
+
+        for config in invalid {
+            assert!(
+                matches!(
+                    set_config(&config),
+                    Err(SupplierError::InvalidParams { .. })
+                ),
+                "{config:?} is accepted"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn should_resolve_with_doh() {
+        let spec = DnsResolver::Cloudflare {
+            protocol: DnsProtocol::Https,
+        };
+        let resolver = build_resolver(&spec, TtlOpts::default()).await.unwrap();
+        let res = resolver.lookup_ip("uakino.best").await.unwrap();
+        assert!(res.iter().next().is_some());
+    }
+}
@@ -0,0 +1,614 @@
+//! Downloads of video sources for offline viewing.
+//!
+//! HLS segments are fetched concurrently into `<file>.parts` directory and decrypted
+//! there, so download interrupted by error or cancellation continues with missing
+//! segments only. Parts of other variant or playlist (manifest in directory differs)
+//! are discarded. Once all segments are there they are joined into single file.
+//! Plain files are written to `<file>.part` and resumed with range requests.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use futures::{StreamExt, stream};
+use log::info;
+use reqwest::{StatusCode, Url, header};
+use serde::{Deserialize, Serialize};
+use tokio::{fs, io::AsyncWriteExt};
+
+use super::{
+    crypto,
+    hls::{self, MediaPlaylist, RenditionType, Segment, Variant},
+};
+use crate::{
+    errors::{SupplierError, SupplierResult},
+    models::{ContentMediaItemSource, DownloadEvent, DownloadOptions},
+};
+
+const DEFAULT_CONCURRENCY: usize = 4;
+// plain files report progress per chunk, events are throttled to keep Dart side calm
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+const PARTS_MANIFEST: &str = "manifest.json";
+
+// what parts directory was filled from, segment indexes mean nothing for other playlist
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct PartsManifest {
+    uri: String,
+    segments: usize,
+}
+
+/// Download video source to `output` with container extension added, returns path of written file
+pub async fn download(
+    source: ContentMediaItemSource,
+    output: &str,
+    options: &DownloadOptions,
+    on_event: impl Fn(DownloadEvent),
+) -> SupplierResult<String> {
+    let ContentMediaItemSource::Video { link, headers, .. } = source else {
+        return Err(SupplierError::invalid_params(
+            "only video sources can be downloaded",
+        ));
+    };
+    let headers = headers.as_ref();
+
+    let path = match hls::fetch_playlist(&link, headers).await? {
+        Some((playlist, url)) => {
+            let (media, uri) = resolve_media(&playlist, url, headers, options.max_height).await?;
+            download_hls(&media, &uri, headers, output, options, &on_event).await?
+        }
+        None => download_file(&link, headers, output, &on_event).await?,
+    };
+
+    let size = fs::metadata(&path)
+        .await
+        .map_err(|err| io_error(&path, err))?
+        .len();
+    let path = path.to_string_lossy().into_owned();
+    info!("[downloader] {link} saved to {path} ({size} bytes)");
+
+    on_event(DownloadEvent::Completed {
+        path: path.clone(),
+        size,
+    });
+    Ok(path)
+}
+
+async fn resolve_media(
+    playlist: &str,
+    url: Url,
+    headers: Option<&HashMap<String, String>>,
+    max_height: Option<u32>,
+) -> SupplierResult<(MediaPlaylist, Url)> {
+    let Some(master) = hls::parse_master(playlist, &url) else {
+        return Ok((hls::parse_media(playlist, &url), url));
+    };
+
+    let variants = master.sorted_variants();
+    let variant = pick_variant(&variants, max_height)
+        .ok_or_else(|| SupplierError::not_found(format!("no variants in {url}")))?;
+
+    // separate audio tracks would need muxing into video stream
+    if master
+        .renditions_of(RenditionType::Audio, variant.audio.as_deref())
+        .iter()
+        .any(|r| r.uri.is_some())
+    {
+        return Err(SupplierError::unsupported(
+            "download of variant with separate audio tracks",
+        ));
+    }
+
+    let (playlist, url) = hls::fetch_playlist(variant.uri.as_str(), headers)
+        .await?
+        .ok_or_else(|| SupplierError::parse(format!("{} is not a playlist", variant.uri)))?;
+    Ok((hls::parse_media(&playlist, &url), variant.uri.clone()))
+}
+
+// best variant not higher than max height, lowest one when all of them are
+fn pick_variant<'a>(variants: &[&'a Variant], max_height: Option<u32>) -> Option<&'a Variant> {
+    let fits = |variant: &&&Variant| {
+        max_height.is_none_or(|max| variant.resolution.is_none_or(|(_, height)| height <= max))
+    };
+
+    variants.iter().find(fits).or(variants.last()).copied()
+}
+
+async fn download_hls(
+    media: &MediaPlaylist,
+    uri: &Url,
+    headers: Option<&HashMap<String, String>>,
+    output: &str,
+    options: &DownloadOptions,
+    on_event: &impl Fn(DownloadEvent),
+) -> SupplierResult<PathBuf> {
+    if media.segments.is_empty() {
+        return Err(SupplierError::not_found("playlist has no segments"));
+    }
+
+    let keys = fetch_keys(media, headers).await?;
+
+    let extension = if media.init.is_some() { "mp4" } else { "ts" };
+    let path = PathBuf::from(format!("{output}.{extension}"));
+    let parts = PathBuf::from(format!("{output}.{extension}.parts"));
+    prepare_parts(
+        &parts,
+        &PartsManifest {
+            uri: uri.to_string(),
+            segments: media.segments.len(),
+        },
+    )
+    .await?;
+
+    let total_segments = media.segments.len() as u32;
+    let mut completed_segments = 0;
+    let mut downloaded_bytes = 0;
+    let mut missing = vec![];
+    for idx in 0..media.segments.len() {
+        match fs::metadata(part_path(&parts, idx)).await {
+            Ok(meta) => {
+                completed_segments += 1;
+                downloaded_bytes += meta.len();
+            }
+            Err(_) => missing.push(idx),
+        }
+    }
+
+    if completed_segments > 0 {
+        info!("[downloader] resume {output}: {completed_segments}/{total_segments} segments done");
+    }
+
+    let progress = |completed_segments, downloaded_bytes| DownloadEvent::Progress {
+        completed_segments,
+        total_segments,
+        downloaded_bytes,
+        total_bytes: None,
+    };
+    on_event(progress(completed_segments, downloaded_bytes));
+
+    let concurrency = match options.concurrency {
+        0 => DEFAULT_CONCURRENCY,
+        concurrency => concurrency as usize,
+    };
+
+    let mut running = stream::iter(missing)
+        .map(|idx| {
+            let (parts, keys) = (&parts, &keys);
+            async move {
+                let data = fetch_segment(&media.segments[idx], headers, keys).await?;
+                write_part(&part_path(parts, idx), &data).await?;
+                Ok::<_, SupplierError>(data.len() as u64)
+            }
+        })
+        .buffer_unordered(concurrency);
+
+    while let Some(size) = running.next().await {
+        completed_segments += 1;
+        downloaded_bytes += size?;
+        on_event(progress(completed_segments, downloaded_bytes));
+    }
+
+    // init section is small and not counted as segment, so it is fetched right before joining
+    let init = match &media.init {
+        Some(init) => Some(fetch_segment(init, headers, &keys).await?),
+        None => None,
+    };
+
+    let mut file = fs::File::create(&path)
+        .await
+        .map_err(|err| io_error(&path, err))?;
+    if let Some(init) = init {
+        file.write_all(&init)
+            .await
+            .map_err(|err| io_error(&path, err))?;
+    }
+    for idx in 0..media.segments.len() {
+        let part = part_path(&parts, idx);
+        let mut segment = fs::File::open(&part)
+            .await
+            .map_err(|err| io_error(&part, err))?;
+        tokio::io::copy(&mut segment, &mut file)
+            .await
+            .map_err(|err| io_error(&path, err))?;
+    }
+    file.flush().await.map_err(|err| io_error(&path, err))?;
+
+    fs::remove_dir_all(&parts)
+        .await
+        .map_err(|err| io_error(&parts, err))?;
+    Ok(path)
+}
+
+// keeps parts of same playlist only, so resumed download is not mixed with other variant
+async fn prepare_parts(parts: &Path, manifest: &PartsManifest) -> SupplierResult<()> {
+    let manifest_path = parts.join(PARTS_MANIFEST);
+    let stored = fs::read(&manifest_path)
+        .await
+        .ok()
+        .and_then(|content| serde_json::from_slice::<PartsManifest>(&content).ok());
+
+    if stored.as_ref() != Some(manifest) && fs::try_exists(parts).await.unwrap_or_default() {
+        info!("[downloader] discard parts of other playlist in {parts:?}");
+        fs::remove_dir_all(parts)
+            .await
+            .map_err(|err| io_error(parts, err))?;
+    }
+
+    fs::create_dir_all(parts)
+        .await
+        .map_err(|err| io_error(parts, err))?;
+    fs::write(&manifest_path, serde_json::to_vec(manifest)?)
+        .await
+        .map_err(|err| io_error(&manifest_path, err))
+}
+
+// keys are fetched once before segments, playlists rarely rotate more than few of them
+async fn fetch_keys(
+    media: &MediaPlaylist,
+    headers: Option<&HashMap<String, String>>,
+) -> SupplierResult<HashMap<Url, Vec<u8>>> {
+    let mut keys = HashMap::new();
+
+    for key in media
+        .init
+        .iter()
+        .chain(&media.segments)
+        .filter_map(|s| s.key.as_ref())
+    {
+        if key.method != "AES-128" {
+            return Err(SupplierError::unsupported(format!(
+                "download of {} encrypted segments",
+                key.method
+            )));
+        }
+
+        let uri = key
+            .uri
+            .as_ref()
+            .ok_or_else(|| SupplierError::parse("AES-128 key without uri"))?;
+        if keys.contains_key(uri) {
+            continue;
+        }
+
+        let res = super::send(hls::with_headers(uri.as_str(), headers))
+            .await?
+            .error_for_status()?;
+        let key = res.bytes().await?;
+        if key.len() != 16 {
+            return Err(SupplierError::parse(format!(
+                "AES-128 key of {uri} has {} bytes",
+                key.len()
+            )));
+        }
+        keys.insert(uri.clone(), key.to_vec());
+    }
+
+    Ok(keys)
+}
+
+async fn fetch_segment(
+    segment: &Segment,
+    headers: Option<&HashMap<String, String>>,
+    keys: &HashMap<Url, Vec<u8>>,
+) -> SupplierResult<Vec<u8>> {
+    let mut request = hls::with_headers(segment.uri.as_str(), headers);
+    if let Some(range) = segment.byte_range {
+        let last = range.offset + range.length.max(1) - 1;
+        request = request.header(header::RANGE, format!("bytes={}-{last}", range.offset));
+    }
+
+    let data = super::send(request)
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+
+    let Some(key) = &segment.key else {
+        return Ok(data.to_vec());
+    };
+    let key_data = key
+        .uri
+        .as_ref()
+        .and_then(|uri| keys.get(uri))
+        .ok_or_else(|| SupplierError::parse("AES-128 key is not loaded"))?;
+    // without explicit IV media sequence number is used
+    let iv = key
+        .iv
+        .unwrap_or_else(|| u128::from(segment.sequence).to_be_bytes());
+
+    Ok(crypto::decrypt_aes128(key_data, &iv, &data)?)
+}
+
+// temp file is renamed once written, so cancelled write is not taken as finished segment
+async fn write_part(path: &Path, data: &[u8]) -> SupplierResult<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, data)
+        .await
+        .map_err(|err| io_error(&tmp, err))?;
+    fs::rename(&tmp, path)
+        .await
+        .map_err(|err| io_error(path, err))
+}
+
+fn part_path(parts: &Path, idx: usize) -> PathBuf {
+    parts.join(format!("{idx:05}.seg"))
+}
+
+async fn download_file(
+    link: &str,
+    headers: Option<&HashMap<String, String>>,
+    output: &str,
+    on_event: &impl Fn(DownloadEvent),
+) -> SupplierResult<PathBuf> {
+    let url =
+        Url::parse(link).map_err(|err| SupplierError::invalid_params(format!("{link}: {err}")))?;
+    let extension = ["mkv", "webm"]
+        .into_iter()
+        .find(|ext| url.path().ends_with(&format!(".{ext}")))
+        .unwrap_or("mp4");
+
+    let path = PathBuf::from(format!("{output}.{extension}"));
+    let part = PathBuf::from(format!("{output}.{extension}.part"));
+    let mut downloaded_bytes = fs::metadata(&part).await.map(|m| m.len()).unwrap_or(0);
+
+    let mut request = hls::with_headers(link, headers);
+    if downloaded_bytes > 0 {
+        info!("[downloader] resume {link} from {downloaded_bytes} bytes");
+        request = request.header(header::RANGE, format!("bytes={downloaded_bytes}-"));
+    }
+    let mut res = super::send(request).await?;
+
+    let progress = |downloaded_bytes, total_bytes| DownloadEvent::Progress {
+        completed_segments: 0,
+        total_segments: 1,
+        downloaded_bytes,
+        total_bytes,
+    };
+
+    // part already holds whole file
+    let finished = res.status() == StatusCode::RANGE_NOT_SATISFIABLE && downloaded_bytes > 0;
+    if !finished {
+        res = res.error_for_status()?;
+
+        // server ignored range, file starts over
+        if res.status() != StatusCode::PARTIAL_CONTENT {
+            downloaded_bytes = 0;
+        }
+        let total_bytes = res.content_length().map(|len| len + downloaded_bytes);
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(downloaded_bytes > 0)
+            .truncate(downloaded_bytes == 0)
+            .open(&part)
+            .await
+            .map_err(|err| io_error(&part, err))?;
+
+        on_event(progress(downloaded_bytes, total_bytes));
+        let mut reported_at = Instant::now();
+        while let Some(chunk) = res.chunk().await? {
+            file.write_all(&chunk)
+                .await
+                .map_err(|err| io_error(&part, err))?;
+            downloaded_bytes += chunk.len() as u64;
+
+            if reported_at.elapsed() >= PROGRESS_INTERVAL {
+                on_event(progress(downloaded_bytes, total_bytes));
+                reported_at = Instant::now();
+            }
+        }
+        file.flush().await.map_err(|err| io_error(&part, err))?;
+    }
+
+    on_event(DownloadEvent::Progress {
+        completed_segments: 1,
+        total_segments: 1,
+        downloaded_bytes,
+        total_bytes: Some(downloaded_bytes),
+    });
+
+    fs::rename(&part, &path)
+        .await
+        .map_err(|err| io_error(&path, err))?;
+    Ok(path)
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> SupplierError {
+    SupplierError::storage(format!("download file {path:?}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use aes::cipher::{BlockEncryptMut, KeyIvInit, block_padding::Pkcs7};
+    use tokio::{
+        io::{AsyncBufReadExt, BufReader},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    const KEY: [u8; 16] = *b"0123456789abcdef";
+
+    fn encrypt(iv: u128, data: &[u8]) -> Vec<u8> {
+        cbc::Encryptor::<aes::Aes128>::new(&KEY.into(), &iv.to_be_bytes().into())
+            .encrypt_padded_vec_mut::<Pkcs7>(data)
+    }
+
+    fn write_parts(parts: &Path, uri: &str, segments: usize) {
+        std::fs::create_dir_all(parts).unwrap();
+        let manifest = PartsManifest {
+            uri: uri.to_owned(),
+            segments,
+        };
+        std::fs::write(
+            parts.join(PARTS_MANIFEST),
+            serde_json::to_vec(&manifest).unwrap(),
+        )
+        .unwrap();
+        std::fs::write(part_path(parts, 0), b"first ").unwrap();
+    }
+
+    // serves fixed bodies by path and records requested paths
+    async fn serve(files: HashMap<String, Vec<u8>>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let requested = Arc::new(Mutex::new(vec![]));
+
+        let requested_ref = requested.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                while !request.ends_with("\r\n\r\n") {
+                    reader.read_line(&mut request).await.unwrap();
+                }
+
+                let path = request.split(' ').nth(1).unwrap_or_default().to_owned();
+                requested_ref.lock().unwrap().push(path.clone());
+
+                let res = match files.get(&path) {
+                    Some(body) => {
+                        let mut res = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            body.len()
+                        )
+                        .into_bytes();
+                        res.extend_from_slice(body);
+                        res
+                    }
+                    None => {
+                        b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                            .to_vec()
+                    }
+                };
+                reader.get_mut().write_all(&res).await.unwrap();
+            }
+        });
+
+        (base, requested)
+    }
+
+    #[tokio::test]
+    async fn should_download_encrypted_hls_and_resume_missing_segments() {
+        let master = "#EXTM3U
+#EXT-X-STREAM-INF:BANDWIDTH=5000000,RESOLUTION=1920x1080
+1080.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=1000000,RESOLUTION=1280x720
+720.m3u8
+";
+        let media = "#EXTM3U
+#EXT-X-MEDIA-SEQUENCE:3
+#EXT-X-KEY:METHOD=AES-128,URI=\"key.bin\"
+#EXTINF:4.0,
+seg-0.ts
+#EXTINF:4.0,
+seg-1.ts
+#EXT-X-KEY:METHOD=AES-128,URI=\"key.bin\",IV=0x10
+#EXTINF:4.0,
+seg-2.ts
+#EXT-X-ENDLIST
+";
+        let (base, requested) = serve(HashMap::from([
+            ("/master.m3u8".to_owned(), master.into()),
+            ("/720.m3u8".to_owned(), media.into()),
+            ("/key.bin".to_owned(), KEY.to_vec()),
+            ("/seg-0.ts".to_owned(), encrypt(3, b"first ")),
+            ("/seg-1.ts".to_owned(), encrypt(4, b"second ")),
+            ("/seg-2.ts".to_owned(), encrypt(0x10, b"third")),
+        ]))
+        .await;
+
+        let output = std::env::temp_dir()
+            .join(format!("strumok_download_{}", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+        // first segment is left from interrupted download
+        let parts = PathBuf::from(format!("{output}.ts.parts"));
+        write_parts(&parts, &format!("{base}/720.m3u8"), 3);
+
+        let events = Mutex::new(vec![]);
+        let source = ContentMediaItemSource::Video {
+            link: format!("{base}/master.m3u8"),
+            description: String::new(),
+            headers: None,
+            hls_proxy: false,
+        };
+        let options = DownloadOptions {
+            max_height: Some(720),
+            concurrency: 2,
+        };
+        let path = download(source, &output, &options, |event| {
+            events.lock().unwrap().push(event)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(path, format!("{output}.ts"));
+        assert_eq!(std::fs::read(&path).unwrap(), b"first second third");
+        assert!(!parts.exists());
+        std::fs::remove_file(&path).unwrap();
+
+        let requested = requested.lock().unwrap();
+        assert!(requested.contains(&"/720.m3u8".to_owned()));
+        assert!(!requested.contains(&"/1080.m3u8".to_owned()));
+        assert!(!requested.contains(&"/seg-0.ts".to_owned()));
+
+        let events = events.into_inner().unwrap();
+        assert!(matches!(
+            events[0],
+            DownloadEvent::Progress {
+                completed_segments: 1,
+                total_segments: 3,
+                ..
+            }
+        ));
+        assert!(matches!(
+            events.last(),
+            Some(DownloadEvent::Completed { size: 18, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_discard_parts_of_other_playlist() {
+        let media = "#EXTM3U
+#EXTINF:4.0,
+seg-0.ts
+#EXTINF:4.0,
+seg-1.ts
+#EXT-X-ENDLIST
+";
+        let (base, requested) = serve(HashMap::from([
+            ("/media.m3u8".to_owned(), media.into()),
+            ("/seg-0.ts".to_owned(), b"fresh ".to_vec()),
+            ("/seg-1.ts".to_owned(), b"tail".to_vec()),
+        ]))
+        .await;
+
+        let output = std::env::temp_dir()
+            .join(format!("strumok_download_stale_{}", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+        // parts are left from same link which had more segments before
+        let parts = PathBuf::from(format!("{output}.ts.parts"));
+        write_parts(&parts, &format!("{base}/media.m3u8"), 5);
+
+        let source = ContentMediaItemSource::Video {
+            link: format!("{base}/media.m3u8"),
+            description: String::new(),
+            headers: None,
+            hls_proxy: false,
+        };
+        let path = download(source, &output, &DownloadOptions::default(), |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"fresh tail");
+        assert!(requested.lock().unwrap().contains(&"/seg-0.ts".to_owned()));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
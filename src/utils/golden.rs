@@ -0,0 +1,130 @@
+//! Offline parsing tests against pages saved in `fixtures/<supplier>/`.
+//!
+//! `REFRESH_FIXTURES=1 cargo test golden` downloads pages again and rewrites snapshots,
+//! `UPDATE_SNAPSHOTS=1 cargo test golden` only rewrites snapshots,
+//! changes are reviewed with `git diff fixtures/`.
+//! API clients are tested against HAR cassettes refreshed the same way.
+
+use std::{fmt::Debug, future::Future, path::PathBuf};
+
+use super::har::{self, StubResponse};
+
+pub struct Fixture {
+    file: &'static str,
+    method: &'static str,
+    url: String,
+}
+
+pub fn get(file: &'static str, url: impl AsRef<str>) -> Fixture {
+    fixture(file, "GET", url.as_ref())
+}
+
+pub fn post(file: &'static str, url: impl AsRef<str>) -> Fixture {
+    fixture(file, "POST", url.as_ref())
+}
+
+fn fixture(file: &'static str, method: &'static str, url: &str) -> Fixture {
+    // same normalization as request url (trailing slash, percent encoding)
+    let url = reqwest::Url::parse(url)
+        .unwrap_or_else(|err| panic!("{file}: invalid url {url}: {err}"))
+        .to_string();
+
+    Fixture { file, method, url }
+}
+
+/// Serve requests made by future from fixtures, or refresh fixtures from live site
+pub async fn run<F: Future>(supplier: &str, fixtures: &[Fixture], fut: F) -> F::Output {
+    if !is_refresh() {
+        let responses = fixtures
+            .iter()
+            .map(|fixture| StubResponse {
+                method: fixture.method.to_owned(),
+                url: fixture.url.clone(),
+                content_type: content_type(fixture.file).to_owned(),
+                body: std::fs::read_to_string(fixture_path(supplier, fixture.file))
+                    .unwrap_or_else(|err| panic!("{supplier}/{}: {err}", fixture.file)),
+            })
+            .collect();
+
+        return har::stub(responses, fut).await;
+    }
+
+    let (output, responses) = har::capture(fut).await;
+
+    for fixture in fixtures {
+        let response = responses
+            .iter()
+            .rev()
+            .find(|r| r.method == fixture.method && r.url == fixture.url)
+            .unwrap_or_else(|| {
+                panic!(
+                    "{supplier}/{}: {} {} was not requested",
+                    fixture.file, fixture.method, fixture.url
+                )
+            });
+
+        write(fixture_path(supplier, fixture.file), &response.body);
+    }
+
+    output
+}
+
+/// Replay requests made by future from `fixtures/<supplier>/<name>.har`, or record it again from live site
+pub async fn cassette<F: Future>(supplier: &str, name: &str, fut: F) -> F::Output {
+    let path = fixture_path(supplier, &format!("{name}.har"));
+
+    let output = if is_refresh() {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        har::record(&path, fut).await
+    } else {
+        har::replay(&path, fut).await
+    };
+
+    output.unwrap_or_else(|err| panic!("{supplier}/{name}.har: {err}"))
+}
+
+pub fn assert_snapshot<T: Debug>(supplier: &str, name: &str, value: &T) {
+    let actual = format!("{value:#?}\n");
+    let path = fixture_path(supplier, &format!("{name}.snap"));
+
+    if is_refresh() || std::env::var_os("UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        write(path, &actual);
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap();
+    if expected != actual {
+        let new_path = path.with_extension("snap.new");
+        write(new_path.clone(), &actual);
+        panic!(
+            "{supplier}/{name} snapshot changed, compare {path:?} with {new_path:?} and run with UPDATE_SNAPSHOTS=1 to accept"
+        );
+    }
+}
+
+fn is_refresh() -> bool {
+    std::env::var_os("REFRESH_FIXTURES").is_some()
+}
+
+fn fixture_path(supplier: &str, file: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(supplier)
+        .join(file)
+}
+
+fn content_type(file: &str) -> &'static str {
+    match file.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("json") => "application/json",
+        _ => "text/html; charset=utf-8",
+    }
+}
+
+fn write(path: PathBuf, content: &str) {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).unwrap();
+    }
+    std::fs::write(&path, content).unwrap_or_else(|err| panic!("{path:?}: {err}"));
+}
@@ -0,0 +1,846 @@
+//! HAR recording and replay of outgoing requests.
+//!
+//! The hook sits in `request_policy::execute`, so only requests sent with `utils::send`,
+//! `fetch_text`, `fetch_json` and `scrap_page` are recorded or replayed. Requests sent
+//! with `RequestBuilder::send` directly (animeon, anitaku) go to network as before.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock, RwLock},
+    time::Instant,
+};
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use log::debug;
+use reqwest::{Client, Request, Response, ResponseBuilderExt, Url, header};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{SupplierError, SupplierResult};
+
+// never written to disk, recordings are meant to be attached to bug reports
+const REDACTED_HEADERS: [&str; 4] = [
+    "authorization",
+    "cookie",
+    "set-cookie",
+    "proxy-authorization",
+];
+
+// query string and body params with credentials, matched case insensitively
+const REDACTED_PARAMS: [&str; 7] = [
+    "access_token",
+    "api_key",
+    "apikey",
+    "password",
+    "token",
+    "login_password",
+    "user_hash",
+];
+
+// body is stored decoded, so transfer headers would lie about it on replay
+const SKIPPED_REPLAY_HEADERS: [&str; 3] =
+    ["content-encoding", "content-length", "transfer-encoding"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CassetteMode {
+    Record,
+    Replay,
+}
+
+/// HAR file that requests are recorded to or replayed from
+pub struct Cassette {
+    mode: CassetteMode,
+    // in-memory recording when not set
+    path: Option<PathBuf>,
+    log: Mutex<HarLog>,
+    // next entry to serve for each request key, last one is repeated when exhausted
+    cursors: Mutex<HashMap<String, usize>>,
+}
+
+impl Cassette {
+    fn recorder(path: Option<PathBuf>) -> Self {
+        Self {
+            mode: CassetteMode::Record,
+            path,
+            log: Mutex::new(HarLog::default()),
+            cursors: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn player(path: PathBuf) -> SupplierResult<Self> {
+        let content = std::fs::read(&path).map_err(|err| {
+            SupplierError::invalid_params(format!("fail to read har {path:?}: {err}"))
+        })?;
+        let har: Har = serde_json::from_slice(&content)?;
+
+        Ok(Self::player_with_log(Some(path), har.log))
+    }
+
+    fn player_with_log(path: Option<PathBuf>, log: HarLog) -> Self {
+        Self {
+            mode: CassetteMode::Replay,
+            path,
+            log: Mutex::new(log),
+            cursors: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn find(&self, request: &HarRequest) -> Option<HarEntry> {
+        let key = request.key();
+        let log = self.log.lock().unwrap();
+        let mut matching: Vec<_> = log
+            .entries
+            .iter()
+            .filter(|e| e.request.key() == key)
+            .collect();
+
+        // form bodies built from maps have no stable field order, fall back to method and url
+        if matching.is_empty() {
+            matching = log
+                .entries
+                .iter()
+                .filter(|e| {
+                    e.request.method == request.method
+                        && redact_url(&e.request.url) == redact_url(&request.url)
+                })
+                .collect();
+        }
+
+        let mut cursors = self.cursors.lock().unwrap();
+        let cursor = cursors.entry(key).or_default();
+        let entry = matching.get(*cursor).or_else(|| matching.last())?;
+        *cursor += 1;
+
+        Some((*entry).clone())
+    }
+
+    fn push(&self, entry: HarEntry) {
+        self.log.lock().unwrap().entries.push(entry);
+    }
+
+    /// Write recorded entries to file once recording is over
+    fn flush(&self) -> SupplierResult<()> {
+        let Some(path) = self
+            .path
+            .as_ref()
+            .filter(|_| self.mode == CassetteMode::Record)
+        else {
+            return Ok(());
+        };
+
+        let content = serde_json::to_vec_pretty(&Har {
+            log: self.log.lock().unwrap().clone(),
+        })?;
+
+        std::fs::write(path, content)
+            .map_err(|err| SupplierError::storage(format!("fail to write har {path:?}: {err}")))
+    }
+}
+
+tokio::task_local! {
+    static CASSETTE: Arc<Cassette>;
+}
+
+fn global_cassette() -> &'static RwLock<Option<Arc<Cassette>>> {
+    static GLOBAL: OnceLock<RwLock<Option<Arc<Cassette>>>> = OnceLock::new();
+    GLOBAL.get_or_init(|| RwLock::new(None))
+}
+
+fn current() -> Option<Arc<Cassette>> {
+    CASSETTE
+        .try_with(Arc::clone)
+        .ok()
+        .or_else(|| global_cassette().read().unwrap().clone())
+}
+
+/// Record every request of the app until `stop` is called, which writes them to HAR file
+pub fn start_recording(path: &str) -> SupplierResult<()> {
+    let path = PathBuf::from(path);
+    if let Some(dir) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(|err| {
+            SupplierError::invalid_params(format!("fail to create dir {dir:?}: {err}"))
+        })?;
+    }
+
+    *global_cassette().write().unwrap() = Some(Arc::new(Cassette::recorder(Some(path))));
+    Ok(())
+}
+
+/// Serve every request of the app from HAR file, requests missing in it fail
+pub fn start_replay(path: &str) -> SupplierResult<()> {
+    let cassette = Cassette::player(PathBuf::from(path))?;
+    *global_cassette().write().unwrap() = Some(Arc::new(cassette));
+    Ok(())
+}
+
+pub fn stop() -> SupplierResult<()> {
+    let cassette = global_cassette().write().unwrap().take();
+    match cassette {
+        Some(cassette) => cassette.flush(),
+        None => Ok(()),
+    }
+}
+
+/// Record requests made by future, used to capture fixtures for tests
+pub async fn record<F: Future>(path: impl AsRef<Path>, fut: F) -> SupplierResult<F::Output> {
+    let cassette = Arc::new(Cassette::recorder(Some(path.as_ref().to_path_buf())));
+    let output = CASSETTE.scope(cassette.clone(), fut).await;
+    cassette.flush()?;
+    Ok(output)
+}
+
+/// Response body captured for request, without HAR file around it
+#[derive(Debug, Clone)]
+pub struct StubResponse {
+    pub method: String,
+    pub url: String,
+    pub content_type: String,
+    pub body: String,
+}
+
+/// Record requests made by future in memory
+pub async fn capture<F: Future>(fut: F) -> (F::Output, Vec<StubResponse>) {
+    let cassette = Arc::new(Cassette::recorder(None));
+    let output = CASSETTE.scope(cassette.clone(), fut).await;
+
+    let responses = cassette
+        .log
+        .lock()
+        .unwrap()
+        .entries
+        .iter()
+        .map(|entry| StubResponse {
+            method: entry.request.method.clone(),
+            url: entry.request.url.clone(),
+            content_type: entry.response.content.mime_type.clone(),
+            body: entry.response.content.text.clone().unwrap_or_default(),
+        })
+        .collect();
+
+    (output, responses)
+}
+
+/// Serve requests made by future from given responses instead of network
+pub async fn stub<F: Future>(responses: Vec<StubResponse>, fut: F) -> F::Output {
+    let entries = responses
+        .into_iter()
+        .map(|stub| HarEntry {
+            started_date_time: String::new(),
+            time: 0.0,
+            request: HarRequest {
+                method: stub.method,
+                url: stub.url,
+                http_version: String::new(),
+                headers: vec![],
+                query_string: vec![],
+                cookies: vec![],
+                headers_size: -1,
+                body_size: 0,
+                post_data: None,
+            },
+            response: HarResponse {
+                status: 200,
+                status_text: "OK".into(),
+                http_version: String::new(),
+                headers: vec![],
+                cookies: vec![],
+                content: HarContent {
+                    size: stub.body.len() as i64,
+                    mime_type: stub.content_type,
+                    text: Some(stub.body),
+                    encoding: None,
+                },
+                redirect_url: String::new(),
+                headers_size: -1,
+                body_size: -1,
+            },
+            cache: serde_json::Value::Object(Default::default()),
+            timings: HarTimings::default(),
+        })
+        .collect();
+
+    let log = HarLog {
+        entries,
+        ..Default::default()
+    };
+    let cassette = Cassette::player_with_log(None, log);
+    CASSETTE.scope(Arc::new(cassette), fut).await
+}
+
+/// Serve requests made by future from HAR file instead of network
+pub async fn replay<F: Future>(path: impl AsRef<Path>, fut: F) -> SupplierResult<F::Output> {
+    let cassette = Cassette::player(path.as_ref().to_path_buf())?;
+    Ok(CASSETTE.scope(Arc::new(cassette), fut).await)
+}
+
+/// Send request through active cassette, goes straight to network when nothing is recorded or replayed
+pub async fn execute(client: &Client, request: Request) -> SupplierResult<Response> {
+    let Some(cassette) = current() else {
+        return Ok(client.execute(request).await?);
+    };
+
+    let har_request = HarRequest::from_request(&request);
+
+    match cassette.mode {
+        CassetteMode::Replay => {
+            let key = har_request.key();
+            let entry = cassette.find(&har_request).ok_or_else(|| {
+                SupplierError::not_found(format!("no recorded response for {key}"))
+            })?;
+
+            debug!("[har] replay {key}");
+            entry.response.into_response(request.url().clone())
+        }
+        CassetteMode::Record => {
+            let started_at = chrono::Utc::now();
+            let start = Instant::now();
+
+            let res = client.execute(request).await?;
+            let url = res.url().clone();
+            let status = res.status();
+            let version = res.version();
+            let headers = res.headers().clone();
+            let body = res.bytes().await?;
+
+            let har_response = HarResponse::new(status, version, &headers, &body);
+            let entry = HarEntry {
+                started_date_time: started_at.to_rfc3339(),
+                time: start.elapsed().as_secs_f64() * 1000.0,
+                request: har_request,
+                response: har_response,
+                cache: serde_json::Value::Object(Default::default()),
+                timings: HarTimings::default(),
+            };
+            cassette.push(entry);
+
+            let mut builder = http::Response::builder()
+                .status(status)
+                .version(version)
+                .url(url);
+            for (name, value) in headers.iter() {
+                if !SKIPPED_REPLAY_HEADERS.contains(&name.as_str()) {
+                    builder = builder.header(name, value);
+                }
+            }
+
+            Ok(builder
+                .body(body)
+                .map_err(|err| SupplierError::parse(err.to_string()))?
+                .into())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Har {
+    log: HarLog,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HarLog {
+    version: String,
+    creator: HarCreator,
+    #[serde(default)]
+    entries: Vec<HarEntry>,
+}
+
+impl Default for HarLog {
+    fn default() -> Self {
+        Self {
+            version: "1.2".into(),
+            creator: HarCreator {
+                name: env!("CARGO_PKG_NAME").into(),
+                version: env!("CARGO_PKG_VERSION").into(),
+            },
+            entries: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HarCreator {
+    name: String,
+    version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarEntry {
+    started_date_time: String,
+    time: f64,
+    request: HarRequest,
+    response: HarResponse,
+    #[serde(default)]
+    cache: serde_json::Value,
+    #[serde(default)]
+    timings: HarTimings,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HarTimings {
+    send: f64,
+    wait: f64,
+    receive: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HarHeader {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    http_version: String,
+    #[serde(default)]
+    headers: Vec<HarHeader>,
+    #[serde(default)]
+    query_string: Vec<HarHeader>,
+    #[serde(default)]
+    cookies: Vec<serde_json::Value>,
+    #[serde(default)]
+    headers_size: i64,
+    #[serde(default)]
+    body_size: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    post_data: Option<HarPostData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarPostData {
+    #[serde(default)]
+    mime_type: String,
+    #[serde(default)]
+    text: String,
+}
+
+impl HarRequest {
+    fn from_request(request: &Request) -> Self {
+        let post_data = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map(|body| HarPostData {
+                mime_type: request
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_owned(),
+                text: redact_body(&String::from_utf8_lossy(body)),
+            });
+
+        let url = request.url();
+
+        Self {
+            method: request.method().to_string(),
+            url: redact_url(url.as_str()).into_owned(),
+            http_version: format!("{:?}", request.version()),
+            headers: har_headers(request.headers()),
+            query_string: url
+                .query_pairs()
+                .map(|(name, value)| HarHeader {
+                    value: if is_redacted_param(&name) {
+                        "[redacted]".to_owned()
+                    } else {
+                        value.into_owned()
+                    },
+                    name: name.into_owned(),
+                })
+                .collect(),
+            cookies: vec![],
+            headers_size: -1,
+            body_size: post_data.as_ref().map_or(0, |p| p.text.len() as i64),
+            post_data,
+        }
+    }
+
+    // entries written by other tools or stubbed in tests may keep secrets,
+    // so both sides are redacted before comparing
+    fn key(&self) -> String {
+        let url = redact_url(&self.url);
+        match &self.post_data {
+            Some(post_data) if !post_data.text.is_empty() => {
+                format!("{} {url} {}", self.method, redact_body(&post_data.text))
+            }
+            _ => format!("{} {url}", self.method),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarResponse {
+    status: u16,
+    #[serde(default)]
+    status_text: String,
+    #[serde(default)]
+    http_version: String,
+    #[serde(default)]
+    headers: Vec<HarHeader>,
+    #[serde(default)]
+    cookies: Vec<serde_json::Value>,
+    content: HarContent,
+    #[serde(default, rename = "redirectURL")]
+    redirect_url: String,
+    #[serde(default)]
+    headers_size: i64,
+    #[serde(default)]
+    body_size: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarContent {
+    #[serde(default)]
+    size: i64,
+    #[serde(default)]
+    mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    encoding: Option<String>,
+}
+
+impl HarResponse {
+    fn new(
+        status: reqwest::StatusCode,
+        version: reqwest::Version,
+        headers: &header::HeaderMap,
+        body: &[u8],
+    ) -> Self {
+        let (text, encoding) = match std::str::from_utf8(body) {
+            Ok(text) => (text.to_owned(), None),
+            Err(_) => (BASE64_STANDARD.encode(body), Some("base64".to_owned())),
+        };
+
+        Self {
+            status: status.as_u16(),
+            status_text: status.canonical_reason().unwrap_or_default().to_owned(),
+            http_version: format!("{version:?}"),
+            headers: har_headers(headers),
+            cookies: vec![],
+            content: HarContent {
+                size: body.len() as i64,
+                mime_type: headers
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default()
+                    .to_owned(),
+                text: Some(text),
+                encoding,
+            },
+            redirect_url: headers
+                .get(header::LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_owned(),
+            headers_size: -1,
+            body_size: body.len() as i64,
+        }
+    }
+
+    fn into_response(self, url: Url) -> SupplierResult<Response> {
+        let text = self.content.text.unwrap_or_default();
+        let body = match self.content.encoding.as_deref() {
+            Some("base64") => BASE64_STANDARD
+                .decode(text)
+                .map_err(|err| SupplierError::parse(format!("broken har body: {err}")))?,
+            _ => text.into_bytes(),
+        };
+
+        let mut builder = http::Response::builder().status(self.status).url(url);
+        for HarHeader { name, value } in &self.headers {
+            let name = name.to_ascii_lowercase();
+            if !SKIPPED_REPLAY_HEADERS.contains(&name.as_str()) {
+                builder = builder.header(name, value);
+            }
+        }
+
+        if !self.content.mime_type.is_empty()
+            && !builder
+                .headers_ref()
+                .is_some_and(|h| h.contains_key(header::CONTENT_TYPE))
+        {
+            builder = builder.header(header::CONTENT_TYPE, self.content.mime_type);
+        }
+
+        Ok(builder
+            .body(body)
+            .map_err(|err| SupplierError::parse(format!("broken har response: {err}")))?
+            .into())
+    }
+}
+
+fn har_headers(headers: &header::HeaderMap) -> Vec<HarHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if REDACTED_HEADERS.contains(&name.as_str()) {
+                "[redacted]".to_owned()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+
+            HarHeader {
+                name: name.to_string(),
+                value,
+            }
+        })
+        .collect()
+}
+
+fn is_redacted_param(name: &str) -> bool {
+    REDACTED_PARAMS.iter().any(|p| p.eq_ignore_ascii_case(name))
+}
+
+/// Redact `name=value` pairs of query string or form body, other pairs are kept byte for byte
+fn redact_params(params: &str) -> Cow<'_, str> {
+    let has_secret = params.split('&').any(|pair| {
+        let name = pair.split_once('=').map_or(pair, |(name, _)| name);
+        is_redacted_param(&urlencoding::decode(name).unwrap_or_default())
+    });
+    if !has_secret {
+        return Cow::Borrowed(params);
+    }
+
+    let redacted: Vec<_> = params
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _))
+                if is_redacted_param(&urlencoding::decode(name).unwrap_or_default()) =>
+            {
+                format!("{name}=[redacted]")
+            }
+            _ => pair.to_owned(),
+        })
+        .collect();
+
+    Cow::Owned(redacted.join("&"))
+}
+
+fn redact_url(url: &str) -> Cow<'_, str> {
+    let Some((base, query)) = url.split_once('?') else {
+        return Cow::Borrowed(url);
+    };
+    let (query, fragment) = match query.split_once('#') {
+        Some((query, fragment)) => (query, Some(fragment)),
+        None => (query, None),
+    };
+
+    match redact_params(query) {
+        Cow::Borrowed(_) => Cow::Borrowed(url),
+        Cow::Owned(query) => match fragment {
+            Some(fragment) => Cow::Owned(format!("{base}?{query}#{fragment}")),
+            None => Cow::Owned(format!("{base}?{query}")),
+        },
+    }
+}
+
+fn redact_body(body: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Object(mut object)) => {
+            let mut redacted = false;
+            for (name, value) in object.iter_mut() {
+                if is_redacted_param(name) {
+                    *value = serde_json::Value::String("[redacted]".into());
+                    redacted = true;
+                }
+            }
+
+            if redacted {
+                serde_json::Value::Object(object).to_string()
+            } else {
+                body.to_owned()
+            }
+        }
+        Ok(_) => body.to_owned(),
+        Err(_) => redact_params(body).into_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils;
+
+    fn write_har(name: &str, entries: serde_json::Value) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("strumok_{name}_{}.har", std::process::id()));
+        let har = serde_json::json!({
+            "log": {
+                "version": "1.2",
+                "creator": { "name": "test", "version": "1" },
+                "entries": entries,
+            }
+        });
+        std::fs::write(&path, har.to_string()).unwrap();
+        path
+    }
+
+    fn entry(method: &str, url: &str, post: Option<&str>, body: &str) -> serde_json::Value {
+        let mut request = serde_json::json!({ "method": method, "url": url });
+        if let Some(text) = post {
+            request["postData"] = serde_json::json!({ "mimeType": "application/x-www-form-urlencoded", "text": text });
+        }
+
+        serde_json::json!({
+            "startedDateTime": "2026-01-01T00:00:00Z",
+            "time": 1.0,
+            "request": request,
+            "response": {
+                "status": 200,
+                "headers": [{ "name": "Content-Type", "value": "text/html" }],
+                "content": { "mimeType": "text/html", "text": body },
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn should_replay_recorded_responses() {
+        let path = write_har(
+            "replay",
+            serde_json::json!([
+                entry("GET", "https://har.test/page", None, "first"),
+                entry("GET", "https://har.test/page", None, "second"),
+                entry(
+                    "POST",
+                    "https://har.test/search",
+                    Some("story=a"),
+                    "found a"
+                ),
+            ]),
+        );
+
+        let bodies = replay(&path, async {
+            let client = utils::create_client();
+            let mut bodies = vec![];
+            for _ in 0..3 {
+                bodies.push(
+                    utils::fetch_text(client.get("https://har.test/page"))
+                        .await
+                        .unwrap(),
+                );
+            }
+            bodies.push(
+                utils::fetch_text(client.post("https://har.test/search").body("story=a"))
+                    .await
+                    .unwrap(),
+            );
+            bodies
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(bodies, ["first", "second", "second", "found a"]);
+    }
+
+    #[tokio::test]
+    async fn should_replay_entries_with_secrets() {
+        let path = write_har(
+            "secrets",
+            serde_json::json!([entry(
+                "GET",
+                "https://har.test/playlist?news_id=1&user_hash=abc",
+                None,
+                "playlist"
+            )]),
+        );
+
+        let body = replay(&path, async {
+            utils::fetch_text(
+                utils::create_client().get("https://har.test/playlist?news_id=1&user_hash=abc"),
+            )
+            .await
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(body.unwrap(), "playlist");
+    }
+
+    #[tokio::test]
+    async fn should_fail_on_missing_entry() {
+        let path = write_har("missing", serde_json::json!([]));
+
+        let result = replay(&path, async {
+            utils::fetch_text(utils::create_client().get("https://har.test/unknown")).await
+        })
+        .await
+        .unwrap();
+
+        assert!(matches!(result, Err(SupplierError::NotFound { .. })));
+    }
+
+    #[test]
+    fn should_redact_secrets() {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, "Bearer secret".parse().unwrap());
+        headers.insert(header::REFERER, "https://har.test".parse().unwrap());
+
+        let har_headers = har_headers(&headers);
+        assert!(har_headers.iter().all(|h| !h.value.contains("secret")));
+        assert!(har_headers.iter().any(|h| h.value == "https://har.test"));
+
+        let request = utils::create_client()
+            .post("https://har.test/search?api_key=secret&query=%D0%B0")
+            .body("story=a&user_hash=secret")
+            .build()
+            .unwrap();
+        let har_request = HarRequest::from_request(&request);
+        assert_eq!(
+            har_request.url,
+            "https://har.test/search?api_key=[redacted]&query=%D0%B0"
+        );
+        assert_eq!(
+            har_request.post_data.unwrap().text,
+            "story=a&user_hash=[redacted]"
+        );
+        assert_eq!(
+            redact_body(r#"{"token":"secret","page":1}"#),
+            r#"{"page":1,"token":"[redacted]"}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn should_write_recording_once_flushed() {
+        let path = std::env::temp_dir().join(format!("strumok_stop_{}.har", std::process::id()));
+        let cassette = Cassette::recorder(Some(path.clone()));
+        let request = HarRequest::from_request(
+            &utils::create_client()
+                .get("https://har.test/page")
+                .build()
+                .unwrap(),
+        );
+
+        cassette.push(HarEntry {
+            started_date_time: String::new(),
+            time: 0.0,
+            request,
+            response: HarResponse::new(
+                reqwest::StatusCode::OK,
+                reqwest::Version::HTTP_11,
+                &header::HeaderMap::new(),
+                b"page",
+            ),
+            cache: serde_json::Value::Object(Default::default()),
+            timings: HarTimings::default(),
+        });
+        assert!(!path.exists());
+
+        cassette.flush().unwrap();
+        let bodies = replay(&path, async {
+            utils::fetch_text(utils::create_client().get("https://har.test/page")).await
+        })
+        .await
+        .unwrap();
+        assert_eq!(bodies.unwrap(), "page");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,840 @@
+//! HLS playlists: parsing of master and media playlists and expansion of
+//! master links returned by suppliers into per-quality, per-audio and
+//! subtitle sources.
+
+use std::collections::HashMap;
+
+use log::warn;
+use reqwest::Url;
+
+use super::hls_proxy;
+use crate::{
+    errors::{SupplierError, SupplierResult},
+    models::ContentMediaItemSource,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub uri: Url,
+    pub bandwidth: Option<u64>,
+    pub resolution: Option<(u32, u32)>,
+    pub codecs: Option<String>,
+    pub frame_rate: Option<f32>,
+    /// group id of `EXT-X-MEDIA` audio renditions
+    pub audio: Option<String>,
+    pub subtitles: Option<String>,
+    // attribute list of `EXT-X-STREAM-INF` as is
+    attributes: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenditionType {
+    Audio,
+    Video,
+    Subtitles,
+    ClosedCaptions,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rendition {
+    pub rendition_type: RenditionType,
+    pub group_id: String,
+    pub name: String,
+    pub language: Option<String>,
+    /// not set for renditions muxed into variant stream
+    pub uri: Option<Url>,
+    pub default: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MasterPlaylist {
+    pub variants: Vec<Variant>,
+    pub renditions: Vec<Rendition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub uri: Url,
+    pub duration: f64,
+    /// media sequence number, default IV of AES-128 segments
+    pub sequence: u64,
+    /// `EXT-X-KEY` in effect, not set for clear segments
+    pub key: Option<SegmentKey>,
+    pub byte_range: Option<ByteRange>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentKey {
+    /// `AES-128`, `SAMPLE-AES`...
+    pub method: String,
+    pub uri: Option<Url>,
+    pub iv: Option<[u8; 16]>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub offset: u64,
+    pub length: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaPlaylist {
+    pub segments: Vec<Segment>,
+    /// `EXT-X-MAP` initialization section of fMP4 streams
+    pub init: Option<Segment>,
+}
+
+/// Playlist may be served with any content type and extension, so body start is checked too
+pub fn is_playlist(content_type: &str, url: &Url, body_start: &[u8]) -> bool {
+    content_type.to_lowercase().contains("mpegurl")
+        || url.path().ends_with(".m3u8")
+        || body_start.trim_ascii_start().starts_with(b"#EXTM3U")
+}
+
+/// Master playlist with its variants and renditions, `None` for media playlist
+pub fn parse_master(playlist: &str, base: &Url) -> Option<MasterPlaylist> {
+    let mut variants = vec![];
+    let mut renditions = vec![];
+    let mut pending: Option<String> = None;
+
+    for line in playlist.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(attrs) = line.strip_prefix("#EXT-X-STREAM-INF:") {
+            pending = Some(attrs.to_owned());
+        } else if let Some(attrs) = line.strip_prefix("#EXT-X-MEDIA:") {
+            renditions.extend(parse_rendition(attrs, base));
+        } else if line.starts_with('#') {
+            continue;
+        } else if let Some(attrs) = pending.take()
+            && let Ok(uri) = base.join(line)
+        {
+            variants.push(parse_variant(attrs, uri));
+        }
+    }
+
+    if variants.is_empty() {
+        return None;
+    }
+
+    Some(MasterPlaylist {
+        variants,
+        renditions,
+    })
+}
+
+pub fn parse_media(playlist: &str, base: &Url) -> MediaPlaylist {
+    let mut segments = vec![];
+    let mut init = None;
+    let mut duration = 0.0;
+    let mut sequence = 0;
+    let mut key: Option<SegmentKey> = None;
+    let mut byte_range: Option<ByteRange> = None;
+    // sub-range without offset continues previous one
+    let mut next_offset = 0;
+
+    for line in playlist.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if let Some(inf) = line.strip_prefix("#EXTINF:") {
+            duration = inf
+                .split(',')
+                .next()
+                .and_then(|d| d.trim().parse().ok())
+                .unwrap_or_default();
+        } else if let Some(value) = line.strip_prefix("#EXT-X-MEDIA-SEQUENCE:") {
+            sequence = value.trim().parse().unwrap_or_default();
+        } else if let Some(attrs) = line.strip_prefix("#EXT-X-KEY:") {
+            key = parse_key(attrs, base);
+        } else if let Some(value) = line.strip_prefix("#EXT-X-BYTERANGE:") {
+            byte_range = parse_byte_range(value, next_offset);
+        } else if let Some(attrs) = line.strip_prefix("#EXT-X-MAP:") {
+            let attrs = parse_attributes(attrs);
+            init = attrs
+                .get("URI")
+                .and_then(|u| base.join(u).ok())
+                .map(|uri| Segment {
+                    uri,
+                    duration: 0.0,
+                    sequence: 0,
+                    key: key.clone(),
+                    byte_range: attrs.get("BYTERANGE").and_then(|r| parse_byte_range(r, 0)),
+                });
+        } else if !line.starts_with('#')
+            && let Ok(uri) = base.join(line)
+        {
+            if let Some(range) = byte_range {
+                next_offset = range.offset + range.length;
+            }
+            segments.push(Segment {
+                uri,
+                duration,
+                sequence,
+                key: key.clone(),
+                byte_range: byte_range.take(),
+            });
+            duration = 0.0;
+            sequence += 1;
+        }
+    }
+
+    MediaPlaylist { segments, init }
+}
+
+fn parse_key(attributes: &str, base: &Url) -> Option<SegmentKey> {
+    let attrs = parse_attributes(attributes);
+    let method = attrs.get("METHOD")?;
+    if method == "NONE" {
+        return None;
+    }
+
+    Some(SegmentKey {
+        method: method.clone(),
+        uri: attrs.get("URI").and_then(|u| base.join(u).ok()),
+        iv: attrs.get("IV").and_then(|iv| {
+            let hex = iv.trim_start_matches("0x").trim_start_matches("0X");
+            u128::from_str_radix(hex, 16).ok().map(u128::to_be_bytes)
+        }),
+    })
+}
+
+// `<length>[@<offset>]`
+fn parse_byte_range(value: &str, next_offset: u64) -> Option<ByteRange> {
+    let (length, offset) = match value.trim().split_once('@') {
+        Some((length, offset)) => (length, offset.parse().ok()?),
+        None => (value.trim(), next_offset),
+    };
+
+    Some(ByteRange {
+        offset,
+        length: length.parse().ok()?,
+    })
+}
+
+fn parse_variant(attributes: String, uri: Url) -> Variant {
+    let attrs = parse_attributes(&attributes);
+    Variant {
+        uri,
+        bandwidth: attrs.get("BANDWIDTH").and_then(|b| b.parse().ok()),
+        resolution: attrs.get("RESOLUTION").and_then(|r| {
+            let (w, h) = r.split_once('x')?;
+            Some((w.parse().ok()?, h.parse().ok()?))
+        }),
+        codecs: attrs.get("CODECS").cloned(),
+        frame_rate: attrs.get("FRAME-RATE").and_then(|f| f.parse().ok()),
+        audio: attrs.get("AUDIO").cloned(),
+        subtitles: attrs.get("SUBTITLES").cloned(),
+        attributes,
+    }
+}
+
+fn parse_rendition(attributes: &str, base: &Url) -> Option<Rendition> {
+    let attrs = parse_attributes(attributes);
+    let rendition_type = match attrs.get("TYPE")?.as_str() {
+        "AUDIO" => RenditionType::Audio,
+        "VIDEO" => RenditionType::Video,
+        "SUBTITLES" => RenditionType::Subtitles,
+        "CLOSED-CAPTIONS" => RenditionType::ClosedCaptions,
+        _ => return None,
+    };
+
+    Some(Rendition {
+        rendition_type,
+        group_id: attrs.get("GROUP-ID")?.clone(),
+        name: attrs.get("NAME").cloned().unwrap_or_default(),
+        language: attrs.get("LANGUAGE").cloned(),
+        uri: attrs.get("URI").and_then(|u| base.join(u).ok()),
+        default: attrs.get("DEFAULT").is_some_and(|d| d == "YES"),
+    })
+}
+
+/// `KEY=value,KEY="quoted, value"` attribute list, quotes are stripped
+pub fn parse_attributes(list: &str) -> HashMap<String, String> {
+    let mut attrs = HashMap::new();
+    let mut rest = list.trim();
+
+    while let Some((key, tail)) = rest.split_once('=') {
+        let (value, tail) = match tail.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some((value, tail)) => (value, tail),
+                None => (quoted, ""),
+            },
+            None => tail.split_once(',').map_or((tail, ""), |(v, t)| (v, t)),
+        };
+
+        attrs.insert(key.trim().to_owned(), value.trim().to_owned());
+        rest = tail.trim_start_matches(',').trim_start();
+    }
+
+    attrs
+}
+
+impl Variant {
+    /// `1080p · 5.2 Mbps · avc1/mp4a`
+    pub fn describe(&self) -> String {
+        let mut parts = vec![];
+        if let Some(quality) = self.quality() {
+            parts.push(quality);
+        }
+        if let Some(bandwidth) = self.bandwidth {
+            parts.push(format!("{:.1} Mbps", bandwidth as f64 / 1_000_000.0));
+        }
+        if let Some(codecs) = &self.codecs {
+            parts.push(short_codecs([codecs.as_str()]));
+        }
+        parts.join(" · ")
+    }
+
+    fn quality(&self) -> Option<String> {
+        self.resolution.map(|(_, height)| format!("{height}p"))
+    }
+}
+
+impl MasterPlaylist {
+    /// `1080p/720p/480p · avc1/mp4a`
+    pub fn describe(&self) -> String {
+        let mut qualities: Vec<_> = self.variants.iter().filter_map(Variant::quality).collect();
+        qualities.dedup();
+
+        let codecs = short_codecs(self.variants.iter().filter_map(|v| v.codecs.as_deref()));
+        [qualities.join("/"), codecs]
+            .into_iter()
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+
+    /// Variants from highest bandwidth to lowest
+    pub fn sorted_variants(&self) -> Vec<&Variant> {
+        let mut variants: Vec<_> = self.variants.iter().collect();
+        variants.sort_by_key(|v| std::cmp::Reverse(v.bandwidth.unwrap_or_default()));
+        variants.dedup_by(|a, b| a.uri == b.uri);
+        variants
+    }
+
+    pub fn renditions_of(
+        &self,
+        rendition_type: RenditionType,
+        group_id: Option<&str>,
+    ) -> Vec<&Rendition> {
+        self.renditions
+            .iter()
+            .filter(|r| r.rendition_type == rendition_type)
+            .filter(|r| group_id.is_none_or(|g| r.group_id == g))
+            .collect()
+    }
+
+    /// Master with single variant and its renditions, `audio` becomes the only audio rendition.
+    /// All uris are absolute
+    pub fn single_variant(&self, variant: &Variant, audio: Option<&Rendition>) -> String {
+        let mut out = String::from("#EXTM3U\n");
+
+        let audio_renditions = match audio {
+            Some(audio) => vec![audio],
+            None => self.renditions_of(RenditionType::Audio, variant.audio.as_deref()),
+        };
+        let subtitles = self.renditions_of(RenditionType::Subtitles, variant.subtitles.as_deref());
+        for rendition in audio_renditions.into_iter().chain(subtitles) {
+            out.push_str(&rendition_tag(
+                rendition,
+                audio.is_some() || rendition.default,
+            ));
+        }
+
+        out.push_str(&format!(
+            "#EXT-X-STREAM-INF:{}\n{}\n",
+            variant.attributes, variant.uri
+        ));
+        out
+    }
+}
+
+fn rendition_tag(rendition: &Rendition, default: bool) -> String {
+    let rendition_type = match rendition.rendition_type {
+        RenditionType::Audio => "AUDIO",
+        RenditionType::Video => "VIDEO",
+        RenditionType::Subtitles => "SUBTITLES",
+        RenditionType::ClosedCaptions => "CLOSED-CAPTIONS",
+    };
+    let yes_no = if default { "YES" } else { "NO" };
+
+    let mut tag = format!(
+        "#EXT-X-MEDIA:TYPE={rendition_type},GROUP-ID=\"{}\",NAME=\"{}\",DEFAULT={yes_no},AUTOSELECT={yes_no}",
+        rendition.group_id, rendition.name
+    );
+    if let Some(language) = &rendition.language {
+        tag.push_str(&format!(",LANGUAGE=\"{language}\""));
+    }
+    if let Some(uri) = &rendition.uri {
+        tag.push_str(&format!(",URI=\"{uri}\""));
+    }
+    tag.push('\n');
+    tag
+}
+
+// avc1.640028,mp4a.40.2 -> avc1/mp4a
+fn short_codecs<'a>(codecs: impl IntoIterator<Item = &'a str>) -> String {
+    let mut short: Vec<&str> = vec![];
+    for codec in codecs.into_iter().flat_map(|c| c.split(',')) {
+        let name = codec.trim().split('.').next().unwrap_or_default();
+        if !name.is_empty() && !short.contains(&name) {
+            short.push(name);
+        }
+    }
+    short.join("/")
+}
+
+/// Add quality and codecs info of master playlists to video sources descriptions.
+/// With `split` master is replaced with source per variant, per alternative audio and per subtitle rendition
+pub async fn expand_sources(
+    sources: Vec<ContentMediaItemSource>,
+    split: bool,
+) -> Vec<ContentMediaItemSource> {
+    let expanded = futures::future::join_all(
+        sources
+            .into_iter()
+            .map(|source| async move { expand_source(source, split).await }),
+    )
+    .await;
+
+    expanded.into_iter().flatten().collect()
+}
+
+async fn expand_source(source: ContentMediaItemSource, split: bool) -> Vec<ContentMediaItemSource> {
+    let ContentMediaItemSource::Video {
+        link,
+        description,
+        headers,
+        hls_proxy,
+    } = source
+    else {
+        return vec![source];
+    };
+
+    let master = match fetch_master(&link, headers.as_ref()).await {
+        Ok(master) => master,
+        Err(err) => {
+            warn!("[hls] fail to load {link}: {err}");
+            None
+        }
+    };
+
+    let Some(master) = master else {
+        return vec![ContentMediaItemSource::Video {
+            link,
+            description,
+            headers,
+            hls_proxy,
+        }];
+    };
+
+    if split {
+        let sources = split_master(&master, &description, headers.as_ref(), hls_proxy).await;
+        if sources
+            .iter()
+            .any(|s| matches!(s, ContentMediaItemSource::Video { .. }))
+        {
+            return sources;
+        }
+    }
+
+    let summary = master.describe();
+    vec![ContentMediaItemSource::Video {
+        link,
+        description: if summary.is_empty() {
+            description
+        } else {
+            format!("{description} ({summary})")
+        },
+        headers,
+        hls_proxy,
+    }]
+}
+
+async fn split_master(
+    master: &MasterPlaylist,
+    description: &str,
+    headers: Option<&HashMap<String, String>>,
+    hls_proxy: bool,
+) -> Vec<ContentMediaItemSource> {
+    let mut sources = vec![];
+    let variants = master.sorted_variants();
+
+    // demuxed audio can only be played through master, which is served by local proxy
+    let playable = |variant: &Variant, audio: Option<&Rendition>, description: String| {
+        let has_audio_renditions = master
+            .renditions_of(RenditionType::Audio, variant.audio.as_deref())
+            .iter()
+            .any(|r| r.uri.is_some());
+
+        if audio.is_none() && !has_audio_renditions {
+            return Some(ContentMediaItemSource::Video {
+                link: variant.uri.to_string(),
+                description,
+                headers: headers.cloned(),
+                hls_proxy,
+            });
+        }
+
+        // proxy sends headers itself
+        let link =
+            hls_proxy::serve_playlist(master.single_variant(variant, audio), headers).ok()?;
+        Some(ContentMediaItemSource::Video {
+            link,
+            description,
+            headers: None,
+            hls_proxy: false,
+        })
+    };
+
+    for variant in &variants {
+        sources.extend(playable(
+            variant,
+            None,
+            format!("{description} {}", variant.describe()),
+        ));
+    }
+
+    // alternative audio tracks with best variant
+    if let Some(best) = variants.first() {
+        let audio = master.renditions_of(RenditionType::Audio, best.audio.as_deref());
+        if audio.len() > 1 {
+            for rendition in audio.into_iter().filter(|r| r.uri.is_some()) {
+                let label = match &rendition.language {
+                    Some(language) => format!("{} [{language}]", rendition.name),
+                    None => rendition.name.clone(),
+                };
+                sources.extend(playable(
+                    best,
+                    Some(rendition),
+                    format!("{description} audio: {label}"),
+                ));
+            }
+        }
+    }
+
+    for rendition in master.renditions_of(RenditionType::Subtitles, None) {
+        let Some(uri) = &rendition.uri else {
+            continue;
+        };
+        sources.push(ContentMediaItemSource::Subtitle {
+            link: subtitle_link(uri, headers).await,
+            description: format!("{description} {}", rendition.name),
+            headers: headers.cloned(),
+        });
+    }
+
+    sources
+}
+
+// subtitle playlists usually wrap single WebVTT file, players can load it directly
+async fn subtitle_link(uri: &Url, headers: Option<&HashMap<String, String>>) -> String {
+    let res = super::fetch_text(with_headers(uri.as_str(), headers)).await;
+    match res.map(|playlist| parse_media(&playlist, uri)) {
+        Ok(media) if media.segments.len() == 1 => media.segments[0].uri.to_string(),
+        _ => uri.to_string(),
+    }
+}
+
+/// Master playlist behind link, `None` when link is media playlist or not a playlist at all
+pub async fn fetch_master(
+    link: &str,
+    headers: Option<&HashMap<String, String>>,
+) -> SupplierResult<Option<MasterPlaylist>> {
+    let playlist = fetch_playlist(link, headers).await?;
+    Ok(playlist.and_then(|(playlist, url)| parse_master(&playlist, &url)))
+}
+
+/// Playlist behind link with its url after redirects, `None` when link is media file
+pub async fn fetch_playlist(
+    link: &str,
+    headers: Option<&HashMap<String, String>>,
+) -> SupplierResult<Option<(String, Url)>> {
+    let url =
+        Url::parse(link).map_err(|err| SupplierError::invalid_params(format!("{link}: {err}")))?;
+    if is_media_file(&url) {
+        return Ok(None);
+    }
+
+    let mut res = super::send(with_headers(link, headers))
+        .await?
+        .error_for_status()?;
+
+    let content_type = res
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_owned();
+
+    // only start of body is read for links that turn out to be media files
+    let mut body = res.chunk().await?.map(|c| c.to_vec()).unwrap_or_default();
+    if !is_playlist(&content_type, res.url(), &body) {
+        return Ok(None);
+    }
+    while let Some(chunk) = res.chunk().await? {
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(Some((
+        String::from_utf8_lossy(&body).into_owned(),
+        res.url().clone(),
+    )))
+}
+
+pub fn is_media_file(url: &Url) -> bool {
+    [".mp4", ".mkv", ".webm"]
+        .iter()
+        .any(|ext| url.path().ends_with(ext))
+}
+
+pub fn with_headers(
+    link: &str,
+    headers: Option<&HashMap<String, String>>,
+) -> reqwest::RequestBuilder {
+    let mut request = super::create_client().get(link);
+    for (name, value) in headers.into_iter().flatten() {
+        request = request.header(name, value);
+    }
+    request
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MASTER: &str = r#"#EXTM3U
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="aud",NAME="English",LANGUAGE="en",DEFAULT=YES,URI="audio/en.m3u8"
+#EXT-X-MEDIA:TYPE=AUDIO,GROUP-ID="aud",NAME="Japanese",LANGUAGE="ja",URI="audio/ja.m3u8"
+#EXT-X-MEDIA:TYPE=SUBTITLES,GROUP-ID="subs",NAME="English, CC",LANGUAGE="en",URI="subs/en.m3u8"
+#EXT-X-STREAM-INF:BANDWIDTH=1280000,RESOLUTION=1280x720,CODECS="avc1.4d401f,mp4a.40.2",AUDIO="aud",SUBTITLES="subs"
+720/index.m3u8
+#EXT-X-STREAM-INF:BANDWIDTH=5200000,RESOLUTION=1920x1080,CODECS="avc1.640028,mp4a.40.2",FRAME-RATE=23.976,AUDIO="aud",SUBTITLES="subs"
+https://cdn.example/1080/index.m3u8
+"#;
+
+    fn base() -> Url {
+        Url::parse("https://host.example/hls/master.m3u8").unwrap()
+    }
+
+    #[test]
+    fn should_parse_master_playlist() {
+        let master = parse_master(MASTER, &base()).unwrap();
+
+        assert_eq!(master.variants.len(), 2);
+        let best = master.sorted_variants()[0];
+        assert_eq!(best.uri.as_str(), "https://cdn.example/1080/index.m3u8");
+        assert_eq!(best.resolution, Some((1920, 1080)));
+        assert_eq!(best.frame_rate, Some(23.976));
+        assert_eq!(best.describe(), "1080p · 5.2 Mbps · avc1/mp4a");
+        assert_eq!(master.describe(), "720p/1080p · avc1/mp4a");
+
+        let subs = master.renditions_of(RenditionType::Subtitles, Some("subs"));
+        assert_eq!(subs[0].name, "English, CC");
+        assert_eq!(
+            subs[0].uri.as_ref().unwrap().as_str(),
+            "https://host.example/hls/subs/en.m3u8"
+        );
+        assert!(master.renditions_of(RenditionType::Audio, None)[0].default);
+    }
+
+    #[test]
+    fn should_build_single_variant_master() {
+        let master = parse_master(MASTER, &base()).unwrap();
+        let variant = &master.variants[0];
+        let japanese = master.renditions_of(RenditionType::Audio, None)[1];
+
+        let playlist = master.single_variant(variant, Some(japanese));
+        let reparsed = parse_master(&playlist, &base()).unwrap();
+
+        assert_eq!(reparsed.variants, vec![variant.clone()]);
+        let audio = reparsed.renditions_of(RenditionType::Audio, None);
+        assert_eq!(audio.len(), 1);
+        assert_eq!(audio[0].name, "Japanese");
+        assert!(audio[0].default);
+        assert_eq!(
+            reparsed.renditions_of(RenditionType::Subtitles, None).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn should_parse_media_playlist() {
+        let playlist =
+            "#EXTM3U\n#EXT-X-TARGETDURATION:10\n#EXTINF:9.5,\nseg-0.vtt\n#EXT-X-ENDLIST\n";
+        assert!(parse_master(playlist, &base()).is_none());
+        assert_eq!(
+            parse_media(playlist, &base()).segments,
+            vec![Segment {
+                uri: Url::parse("https://host.example/hls/seg-0.vtt").unwrap(),
+                duration: 9.5,
+                sequence: 0,
+                key: None,
+                byte_range: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn should_parse_keys_and_ranges_of_media_playlist() {
+        let playlist = r#"#EXTM3U
+#EXT-X-MEDIA-SEQUENCE:7
+#EXT-X-MAP:URI="init.mp4",BYTERANGE="720@0"
+#EXT-X-KEY:METHOD=AES-128,URI="/keys/1.bin"
+#EXTINF:4.0,
+#EXT-X-BYTERANGE:1000@720
+media.mp4
+#EXT-X-KEY:METHOD=AES-128,URI="/keys/2.bin",IV=0x0000000000000000000000000000000A
+#EXTINF:4.0,
+#EXT-X-BYTERANGE:500
+media.mp4
+#EXT-X-KEY:METHOD=NONE
+#EXTINF:2.0,
+media.mp4
+#EXT-X-ENDLIST
+"#;
+        let media = parse_media(playlist, &base());
+
+        let init = media.init.unwrap();
+        assert_eq!(init.uri.as_str(), "https://host.example/hls/init.mp4");
+        assert_eq!(init.key, None);
+        assert_eq!(
+            init.byte_range,
+            Some(ByteRange {
+                offset: 0,
+                length: 720
+            })
+        );
+
+        let [first, second, third] = media.segments.as_slice() else {
+            panic!("expected 3 segments, got {:?}", media.segments);
+        };
+        assert_eq!((first.sequence, second.sequence, third.sequence), (7, 8, 9));
+
+        let key = first.key.as_ref().unwrap();
+        assert_eq!(key.method, "AES-128");
+        assert_eq!(
+            key.uri.as_ref().unwrap().as_str(),
+            "https://host.example/keys/1.bin"
+        );
+        assert_eq!(key.iv, None);
+        assert_eq!(second.key.as_ref().unwrap().iv.unwrap()[15], 10);
+        assert_eq!(third.key, None);
+
+        assert_eq!(
+            second.byte_range,
+            Some(ByteRange {
+                offset: 1720,
+                length: 500
+            })
+        );
+        assert_eq!(third.byte_range, None);
+    }
+
+    // upstream serving MASTER with its renditions, audio playlists need Referer of source
+    async fn serve_upstream() -> String {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                while !request.ends_with("\r\n\r\n") {
+                    if reader.read_line(&mut request).await.unwrap() == 0 {
+                        break;
+                    }
+                }
+
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let has_referer = request.contains("referer: https://site.example");
+                let body = match path {
+                    "/hls/master.m3u8" => MASTER,
+                    "/hls/subs/en.m3u8" => "#EXTM3U\n#EXTINF:60.0,\nen.vtt\n#EXT-X-ENDLIST\n",
+                    "/hls/audio/ja.m3u8" if has_referer => {
+                        "#EXTM3U\n#EXTINF:4.0,\nja-1.aac\n#EXT-X-ENDLIST\n"
+                    }
+                    _ => "",
+                };
+                let status = if body.is_empty() {
+                    "404 Not Found"
+                } else {
+                    "200 OK"
+                };
+                let res = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: application/vnd.apple.mpegurl\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = reader.get_mut().write_all(res.as_bytes()).await;
+            }
+        });
+        base
+    }
+
+    #[tokio::test]
+    async fn should_split_master_into_proxied_variants_audio_and_subtitles() {
+        let upstream = serve_upstream().await;
+        let _server = hls_proxy::tests::SERVER_LOCK.lock().await;
+        let proxy_base = hls_proxy::start(0, false).await.unwrap();
+        let headers = HashMap::from([("Referer".to_owned(), "https://site.example".to_owned())]);
+
+        let sources = expand_sources(
+            vec![ContentMediaItemSource::Video {
+                link: format!("{upstream}/hls/master.m3u8"),
+                description: "Test".into(),
+                headers: Some(headers),
+                hls_proxy: false,
+            }],
+            true,
+        )
+        .await;
+
+        // variants and audio tracks have demuxed audio, so all of them are served by proxy
+        let videos: Vec<_> = sources
+            .iter()
+            .filter_map(|s| match s {
+                ContentMediaItemSource::Video {
+                    link,
+                    description,
+                    headers,
+                    ..
+                } => Some((link, description, headers)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(videos.len(), 4, "{sources:#?}");
+        assert!(
+            videos
+                .iter()
+                .all(|(link, _, headers)| link.starts_with(&proxy_base) && headers.is_none())
+        );
+
+        // subtitle playlist with single file is unwrapped
+        assert!(sources.iter().any(|s| matches!(
+            s,
+            ContentMediaItemSource::Subtitle { link, .. } if *link == format!("{upstream}/hls/subs/en.vtt")
+        )));
+
+        // japanese track plays through proxy, which sends source headers upstream
+        let (japanese, ..) = videos
+            .iter()
+            .find(|(_, description, _)| description.contains("Japanese"))
+            .unwrap();
+        let master = reqwest::get(japanese.as_str())
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        let audio_link = master
+            .lines()
+            .find(|l| l.starts_with("#EXT-X-MEDIA:TYPE=AUDIO"))
+            .and_then(|l| l.split("URI=\"").nth(1))
+            .and_then(|uri| uri.split('"').next())
+            .unwrap();
+        assert!(audio_link.starts_with(&proxy_base), "{master}");
+
+        let res = reqwest::get(audio_link).await.unwrap();
+        assert_eq!(res.status(), reqwest::StatusCode::OK);
+        let audio = res.text().await.unwrap();
+        assert!(audio.contains("/ja-1.aac?u="), "{audio}");
+    }
+}
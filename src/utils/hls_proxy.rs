@@ -0,0 +1,689 @@
+//! Local HTTP proxy for sources players can't load directly.
+//!
+//! Upstream requests get headers (Referer, Origin, Cookie...) of the source,
+//! playlists are rewritten so variants, segments, keys and init maps go
+//! through proxy too. Links carry random session id bound to upstream hosts
+//! of its links and playlists, so proxy can't be used for arbitrary urls even
+//! when it listens on LAN for cast targets. Sessions unused for `SESSION_TTL`
+//! are dropped.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, HashSet},
+    hash::{BuildHasher, RandomState},
+    net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
+    sync::{Mutex, OnceLock, RwLock},
+    time::{Duration, Instant},
+};
+
+use log::{debug, info, warn};
+use reqwest::{StatusCode, Url, header};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    task::AbortHandle,
+};
+
+use crate::{
+    errors::{SupplierError, SupplierResult},
+    models::ContentMediaItemSource,
+};
+
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+// long enough to pause an episode, links of dropped session answer 403
+const SESSION_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+struct Server {
+    base_url: String,
+    port: u16,
+    allow_lan: bool,
+    accept_loop: AbortHandle,
+}
+
+fn server() -> &'static Mutex<Option<Server>> {
+    static SERVER: OnceLock<Mutex<Option<Server>>> = OnceLock::new();
+    SERVER.get_or_init(Default::default)
+}
+
+// playlist id -> (session, playlist with absolute uris), built by crate itself
+fn playlists() -> &'static RwLock<HashMap<String, (String, String)>> {
+    static PLAYLISTS: OnceLock<RwLock<HashMap<String, (String, String)>>> = OnceLock::new();
+    PLAYLISTS.get_or_init(Default::default)
+}
+
+struct Session {
+    // sent with every upstream request
+    headers: HashMap<String, String>,
+    // upstream hosts links may point to, proxied playlists add hosts of their uris
+    hosts: HashSet<String>,
+    expires_at: Instant,
+}
+
+// session id -> session
+fn sessions() -> &'static RwLock<HashMap<String, Session>> {
+    static SESSIONS: OnceLock<RwLock<HashMap<String, Session>>> = OnceLock::new();
+    SESSIONS.get_or_init(Default::default)
+}
+
+fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        super::create_client_builder()
+            .read_timeout(Duration::from_secs(30))
+            .build()
+            .unwrap()
+    })
+}
+
+/// Start proxy or return running one with same settings, `port` 0 picks free port.
+/// With `allow_lan` proxy listens on all interfaces and links use LAN address, so cast targets can reach them
+pub async fn start(port: u16, allow_lan: bool) -> SupplierResult<String> {
+    if let Some(server) = server().lock().unwrap().as_ref()
+        && server.allow_lan == allow_lan
+        && (port == 0 || server.port == port)
+        && !server.accept_loop.is_finished()
+    {
+        return Ok(server.base_url.clone());
+    }
+    stop();
+
+    let bind_ip = if allow_lan {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V4(Ipv4Addr::LOCALHOST)
+    };
+    let listener = TcpListener::bind(SocketAddr::new(bind_ip, port))
+        .await
+        .map_err(|err| SupplierError::Network {
+            message: format!("hls proxy can't listen on {port}: {err}"),
+        })?;
+    let port = listener.local_addr().map(|a| a.port()).unwrap_or(port);
+
+    let host = if allow_lan {
+        lan_ip().unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST))
+    } else {
+        IpAddr::V4(Ipv4Addr::LOCALHOST)
+    };
+    let base_url = format!("http://{}", SocketAddr::new(host, port));
+
+    let accept_loop = tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(async move {
+                        if let Err(err) = handle(stream).await {
+                            debug!("[hls_proxy] connection closed: {err}");
+                        }
+                    });
+                }
+                Err(err) => warn!("[hls_proxy] accept failed: {err}"),
+            }
+        }
+    })
+    .abort_handle();
+
+    info!("[hls_proxy] listening on {base_url}");
+    *server().lock().unwrap() = Some(Server {
+        base_url: base_url.clone(),
+        port,
+        allow_lan,
+        accept_loop,
+    });
+    Ok(base_url)
+}
+
+/// Stop accepting connections, links issued earlier stop working
+pub fn stop() {
+    if let Some(server) = server().lock().unwrap().take() {
+        server.accept_loop.abort();
+        info!("[hls_proxy] stopped");
+    }
+    sessions().write().unwrap().clear();
+    playlists().write().unwrap().clear();
+}
+
+/// Local link of upstream url, requests to it are sent with given headers
+pub fn proxy_url(link: &str, headers: Option<&HashMap<String, String>>) -> SupplierResult<String> {
+    let base_url = running_base_url()?;
+
+    let upstream = Url::parse(link)
+        .map_err(|err| SupplierError::invalid_params(format!("invalid link {link}: {err}")))?;
+
+    let session = open_session(headers, Some(upstream_host(&upstream)));
+    Ok(local_url(&base_url, &session, &upstream))
+}
+
+/// Local link of playlist built in memory (e.g. master with single variant), its uris have to be absolute
+pub fn serve_playlist(
+    playlist: String,
+    headers: Option<&HashMap<String, String>>,
+) -> SupplierResult<String> {
+    let base_url = running_base_url()?;
+
+    // hosts of its uris are allowed once playlist is served
+    let session = open_session(headers, None);
+    let id = format!("{:016x}", random_state().hash_one(&playlist));
+    playlists()
+        .write()
+        .unwrap()
+        .insert(id.clone(), (session.clone(), playlist));
+
+    Ok(format!("{base_url}/{session}/{id}.m3u8?p={id}"))
+}
+
+/// Same source with link pointing to proxy, headers are applied by proxy so they are dropped
+pub fn proxy_source(source: ContentMediaItemSource) -> SupplierResult<ContentMediaItemSource> {
+    Ok(match source {
+        ContentMediaItemSource::Video {
+            link,
+            description,
+            headers,
+            ..
+        } => ContentMediaItemSource::Video {
+            link: proxy_url(&link, headers.as_ref())?,
+            description,
+            headers: None,
+            hls_proxy: false,
+        },
+        ContentMediaItemSource::Subtitle {
+            link,
+            description,
+            headers,
+        } => ContentMediaItemSource::Subtitle {
+            link: proxy_url(&link, headers.as_ref())?,
+            description,
+            headers: None,
+        },
+        manga => manga,
+    })
+}
+
+fn running_base_url() -> SupplierResult<String> {
+    server()
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|s| s.base_url.clone())
+        .ok_or_else(|| SupplierError::invalid_params("hls proxy is not running"))
+}
+
+// secret key keeps ids unguessable
+fn random_state() -> &'static RandomState {
+    static KEY: OnceLock<RandomState> = OnceLock::new();
+    KEY.get_or_init(RandomState::new)
+}
+
+// session of headers allowed to reach host, expired sessions and their playlists are dropped
+fn open_session(headers: Option<&HashMap<String, String>>, host: Option<String>) -> String {
+    let headers = headers.cloned().unwrap_or_default();
+    let id = session_id(&headers);
+    let now = Instant::now();
+
+    {
+        let mut sessions = sessions().write().unwrap();
+        sessions.retain(|_, s| s.expires_at > now);
+
+        let session = sessions.entry(id.clone()).or_insert_with(|| Session {
+            headers,
+            hosts: HashSet::new(),
+            expires_at: now + SESSION_TTL,
+        });
+        session.hosts.extend(host);
+        session.expires_at = now + SESSION_TTL;
+
+        playlists()
+            .write()
+            .unwrap()
+            .retain(|_, (owner, _)| sessions.contains_key(owner));
+    }
+
+    id
+}
+
+// headers of session allowed to reach upstream, playlists are reachable by id alone
+fn use_session(id: &str, upstream: Option<&Url>) -> Option<HashMap<String, String>> {
+    let mut sessions = sessions().write().unwrap();
+    let session = sessions.get_mut(id)?;
+    if upstream.is_some_and(|u| !session.hosts.contains(&upstream_host(u))) {
+        return None;
+    }
+
+    session.expires_at = Instant::now() + SESSION_TTL;
+    Some(session.headers.clone())
+}
+
+fn allow_hosts(id: &str, hosts: HashSet<String>) {
+    if let Some(session) = sessions().write().unwrap().get_mut(id) {
+        session.hosts.extend(hosts);
+    }
+}
+
+fn upstream_host(url: &Url) -> String {
+    format!(
+        "{}:{}",
+        url.host_str().unwrap_or_default(),
+        url.port_or_known_default().unwrap_or_default()
+    )
+}
+
+// same headers give same id
+fn session_id(headers: &HashMap<String, String>) -> String {
+    let sorted: BTreeMap<_, _> = headers.iter().map(|(k, v)| (k.to_lowercase(), v)).collect();
+    format!("{:016x}", random_state().hash_one(sorted))
+}
+
+fn local_url(base_url: &str, session: &str, upstream: &Url) -> String {
+    // keep file name, players sniff format from extension
+    let name = upstream
+        .path_segments()
+        .and_then(|mut s| s.next_back())
+        .filter(|s| !s.is_empty())
+        .unwrap_or("media");
+
+    format!(
+        "{base_url}/{session}/{name}?u={}",
+        urlencoding::encode(upstream.as_str())
+    )
+}
+
+async fn handle(stream: TcpStream) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    let mut head = Vec::new();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(());
+        }
+        if line == "\r\n" || line == "\n" {
+            break;
+        }
+        head.push(line.trim_end().to_owned());
+        if head.iter().map(String::len).sum::<usize>() > MAX_REQUEST_HEAD {
+            return respond_status(reader.get_mut(), StatusCode::PAYLOAD_TOO_LARGE).await;
+        }
+    }
+
+    let mut stream = reader.into_inner();
+    let Some((method, target)) = head.first().and_then(|l| {
+        let mut parts = l.split_whitespace();
+        Some((parts.next()?.to_owned(), parts.next()?.to_owned()))
+    }) else {
+        return respond_status(&mut stream, StatusCode::BAD_REQUEST).await;
+    };
+
+    if method != "GET" && method != "HEAD" {
+        return respond_status(&mut stream, StatusCode::METHOD_NOT_ALLOWED).await;
+    }
+
+    let Some((session, target)) = parse_target(&target) else {
+        return respond_status(&mut stream, StatusCode::NOT_FOUND).await;
+    };
+    let upstream = match &target {
+        Target::Upstream(upstream) => Some(upstream),
+        Target::Playlist(_) => None,
+    };
+    let Some(headers) = use_session(&session, upstream) else {
+        return respond_status(&mut stream, StatusCode::FORBIDDEN).await;
+    };
+    let head_only = method == "HEAD";
+
+    let upstream = match target {
+        Target::Upstream(upstream) => upstream,
+        Target::Playlist(id) => {
+            let stored = playlists().read().unwrap().get(&id).cloned();
+            return match stored {
+                Some((owner, playlist)) if owner == session => {
+                    respond_playlist(
+                        &mut stream,
+                        StatusCode::OK,
+                        &playlist,
+                        None,
+                        &session,
+                        head_only,
+                    )
+                    .await
+                }
+                _ => respond_status(&mut stream, StatusCode::NOT_FOUND).await,
+            };
+        }
+    };
+
+    let range = head.iter().skip(1).find_map(|l| {
+        let (name, value) = l.split_once(':')?;
+        name.eq_ignore_ascii_case("range")
+            .then(|| value.trim().to_owned())
+    });
+
+    let mut request = client().get(upstream.clone());
+    for (name, value) in &headers {
+        request = request.header(name, value);
+    }
+    if let Some(range) = range {
+        request = request.header(header::RANGE, range);
+    }
+
+    let mut res = match request.send().await {
+        Ok(res) => res,
+        Err(err) => {
+            warn!("[hls_proxy] {upstream}: {err}");
+            return respond_status(&mut stream, StatusCode::BAD_GATEWAY).await;
+        }
+    };
+
+    let status = res.status();
+    let content_type = res
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_owned();
+
+    // playlists may be served with any content type and extension, so first chunk is checked too
+    let first = match res.chunk().await {
+        Ok(first) => first.unwrap_or_default(),
+        Err(err) => {
+            warn!("[hls_proxy] {upstream}: {err}");
+            return respond_status(&mut stream, StatusCode::BAD_GATEWAY).await;
+        }
+    };
+
+    if status.is_success() && super::hls::is_playlist(&content_type, res.url(), &first) {
+        let mut body = first.to_vec();
+        while let Some(chunk) = res.chunk().await.ok().flatten() {
+            body.extend_from_slice(&chunk);
+        }
+
+        let body = String::from_utf8_lossy(&body);
+        return respond_playlist(
+            &mut stream,
+            status,
+            &body,
+            Some(res.url()),
+            &session,
+            head_only,
+        )
+        .await;
+    }
+
+    let passthrough: Vec<_> = [
+        &header::CONTENT_TYPE,
+        &header::CONTENT_LENGTH,
+        &header::CONTENT_RANGE,
+        &header::ACCEPT_RANGES,
+    ]
+    .into_iter()
+    .filter_map(|name| {
+        let value = res.headers().get(name)?.to_str().ok()?.to_owned();
+        Some((name.as_str(), value))
+    })
+    .collect();
+
+    stream
+        .write_all(response_head(status, &passthrough).as_bytes())
+        .await?;
+    if !head_only {
+        stream.write_all(&first).await?;
+        while let Some(chunk) = res.chunk().await.ok().flatten() {
+            stream.write_all(&chunk).await?;
+        }
+    }
+    stream.flush().await
+}
+
+async fn respond_playlist(
+    stream: &mut TcpStream,
+    status: StatusCode,
+    playlist: &str,
+    base: Option<&Url>,
+    session: &str,
+    head_only: bool,
+) -> std::io::Result<()> {
+    let Ok(base_url) = running_base_url() else {
+        return respond_status(stream, StatusCode::SERVICE_UNAVAILABLE).await;
+    };
+    // playlists built in memory have absolute uris only
+    let fallback_base = Url::parse("http://localhost/").unwrap();
+    let hosts = RefCell::new(HashSet::new());
+    let playlist = rewrite_playlist(playlist, base.unwrap_or(&fallback_base), |url| {
+        hosts.borrow_mut().insert(upstream_host(url));
+        local_url(&base_url, session, url)
+    });
+    allow_hosts(session, hosts.into_inner());
+
+    let head = response_head(
+        status,
+        &[
+            ("Content-Type", "application/vnd.apple.mpegurl".to_owned()),
+            ("Content-Length", playlist.len().to_string()),
+        ],
+    );
+    stream.write_all(head.as_bytes()).await?;
+    if !head_only {
+        stream.write_all(playlist.as_bytes()).await?;
+    }
+    stream.flush().await
+}
+
+#[derive(Debug, PartialEq)]
+enum Target {
+    Upstream(Url),
+    Playlist(String),
+}
+
+fn parse_target(target: &str) -> Option<(String, Target)> {
+    let (path, query) = target.split_once('?')?;
+    let session = path.trim_start_matches('/').split('/').next()?.to_owned();
+
+    if let Some(id) = query.split('&').find_map(|pair| pair.strip_prefix("p=")) {
+        return Some((session, Target::Playlist(id.to_owned())));
+    }
+
+    let upstream = query.split('&').find_map(|pair| pair.strip_prefix("u="))?;
+    let upstream = Url::parse(&urlencoding::decode(upstream).ok()?).ok()?;
+    if !matches!(upstream.scheme(), "http" | "https") {
+        return None;
+    }
+
+    Some((session, Target::Upstream(upstream)))
+}
+
+/// Point every uri of playlist (variants, segments, `URI="..."` of keys, maps and renditions) to `to_local`
+pub fn rewrite_playlist(playlist: &str, base: &Url, to_local: impl Fn(&Url) -> String) -> String {
+    let resolve = |uri: &str| match base.join(uri) {
+        Ok(url) => to_local(&url),
+        Err(_) => uri.to_owned(),
+    };
+
+    let mut out = String::with_capacity(playlist.len() * 2);
+    for line in playlist.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            out.push_str(line);
+        } else if trimmed.starts_with('#') {
+            out.push_str(&rewrite_uri_attr(trimmed, &resolve));
+        } else {
+            out.push_str(&resolve(trimmed));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn rewrite_uri_attr(tag: &str, resolve: &impl Fn(&str) -> String) -> String {
+    const ATTR: &str = "URI=\"";
+
+    let mut out = String::with_capacity(tag.len());
+    let mut rest = tag;
+    while let Some(start) = rest.find(ATTR) {
+        let value_start = start + ATTR.len();
+        let Some(len) = rest[value_start..].find('"') else {
+            break;
+        };
+        out.push_str(&rest[..value_start]);
+        out.push_str(&resolve(&rest[value_start..value_start + len]));
+        rest = &rest[value_start + len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn response_head(status: StatusCode, headers: &[(&str, String)]) -> String {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or_default()
+    );
+    for (name, value) in headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("Access-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n");
+    head
+}
+
+async fn respond_status(stream: &mut TcpStream, status: StatusCode) -> std::io::Result<()> {
+    let head = response_head(status, &[("Content-Length", "0".to_owned())]);
+    stream.write_all(head.as_bytes()).await?;
+    stream.flush().await
+}
+
+// address of interface used for outgoing traffic, no packets are sent
+fn lan_ip() -> Option<IpAddr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    socket.local_addr().ok().map(|a| a.ip())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    // server lives on runtime of test that started it, so tests using it run one by one
+    pub(crate) static SERVER_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+    #[test]
+    fn should_rewrite_playlist_uris() {
+        let playlist = r#"#EXTM3U
+#EXT-X-KEY:METHOD=AES-128,URI="key.bin",IV=0x1
+#EXT-X-MAP:URI="/init.mp4"
+#EXTINF:4.0,
+seg-1.ts
+#EXTINF:4.0,
+https://cdn.example/seg-2.ts
+"#;
+        let base = Url::parse("https://host.example/hls/index.m3u8").unwrap();
+        let res = rewrite_playlist(playlist, &base, |url| format!("local:{url}"));
+
+        assert_eq!(
+            res,
+            r#"#EXTM3U
+#EXT-X-KEY:METHOD=AES-128,URI="local:https://host.example/hls/key.bin",IV=0x1
+#EXT-X-MAP:URI="local:https://host.example/init.mp4"
+#EXTINF:4.0,
+local:https://host.example/hls/seg-1.ts
+#EXTINF:4.0,
+local:https://cdn.example/seg-2.ts
+"#
+        );
+    }
+
+    #[test]
+    fn should_parse_local_url_back() {
+        let upstream = Url::parse("https://host.example/hls/index.m3u8?token=a&b=1").unwrap();
+        let local = local_url("http://127.0.0.1:1", "abc", &upstream);
+        assert!(local.starts_with("http://127.0.0.1:1/abc/index.m3u8?u="));
+
+        let target = local.trim_start_matches("http://127.0.0.1:1");
+        assert_eq!(
+            parse_target(target),
+            Some(("abc".to_owned(), Target::Upstream(upstream)))
+        );
+        assert_eq!(parse_target("/abc/x?u=file%3A%2F%2F%2Fetc%2Fpasswd"), None);
+    }
+
+    #[tokio::test]
+    async fn should_proxy_playlist_with_source_headers_only_for_known_session() {
+        // upstream answers with playlist only when Referer of source is sent
+        let upstream = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let upstream_url = format!("http://{}/hls/index.m3u8", upstream.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = upstream.accept().await.unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            while !request.ends_with("\r\n\r\n") {
+                reader.read_line(&mut request).await.unwrap();
+            }
+            let body = if request.contains("referer: https://site.example") {
+                "#EXTM3U\n#EXTINF:4.0,\nseg-1.ts\n"
+            } else {
+                ""
+            };
+            let res = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(res.as_bytes()).await.unwrap();
+        });
+
+        let _server = SERVER_LOCK.lock().await;
+        let base_url = start(0, false).await.unwrap();
+        let headers = HashMap::from([("Referer".to_owned(), "https://site.example".to_owned())]);
+        let link = proxy_url(&upstream_url, Some(&headers)).unwrap();
+
+        let res = reqwest::get(&link).await.unwrap();
+        assert_eq!(
+            res.headers()[header::CONTENT_TYPE],
+            "application/vnd.apple.mpegurl"
+        );
+        let playlist = res.text().await.unwrap();
+        let segment = playlist.lines().last().unwrap();
+        assert!(segment.starts_with(&base_url), "{playlist}");
+        assert!(segment.contains("/seg-1.ts?u="), "{playlist}");
+
+        // same headers share session, unknown session is rejected
+        assert_eq!(link, proxy_url(&upstream_url, Some(&headers)).unwrap());
+        let res = reqwest::get(format!(
+            "{base_url}/unknown/index.m3u8?u=http%3A%2F%2Fa.example%2F"
+        ))
+        .await
+        .unwrap();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // known session can't be pointed at hosts outside of its links and playlists
+        let session = link
+            .trim_start_matches(&base_url)
+            .split('/')
+            .nth(1)
+            .unwrap();
+        let res = reqwest::get(format!(
+            "{base_url}/{session}/index.m3u8?u=http%3A%2F%2Fother.example%2F"
+        ))
+        .await
+        .unwrap();
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn should_drop_expired_sessions_with_playlists() {
+        let stale = Session {
+            headers: HashMap::new(),
+            hosts: HashSet::new(),
+            expires_at: Instant::now(),
+        };
+        sessions()
+            .write()
+            .unwrap()
+            .insert("stale-session".into(), stale);
+        playlists().write().unwrap().insert(
+            "stale-playlist".into(),
+            ("stale-session".into(), "#EXTM3U".into()),
+        );
+
+        let headers = HashMap::from([("Referer".to_owned(), "https://ttl.example".to_owned())]);
+        open_session(Some(&headers), None);
+
+        assert!(!sessions().read().unwrap().contains_key("stale-session"));
+        assert!(!playlists().read().unwrap().contains_key("stale-playlist"));
+    }
+}
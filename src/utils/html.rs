@@ -1,12 +1,13 @@
 #![allow(unused)]
 
-use std::{borrow::Cow, str, sync::OnceLock};
+use std::{borrow::Cow, cell::RefCell, str, sync::OnceLock};
 
 use chrono::format::Item;
 use log::warn;
 use regex::Regex;
 use scraper::{ElementRef, Selector, selector::ToCss};
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::models::{
     ContentDetails, ContentInfo, ContentMediaItem, ContentMediaItemSource, MediaType,
 };
@@ -108,6 +109,15 @@ pub trait DOMProcessor<T>: Sync + Send {
         MapValue::new(|opt| opt.unwrap_or_default(), Box::new(self))
     }
 
+    /// fail page processing with layout changed error when value is empty
+    fn required(self) -> Required<T>
+    where
+        Self: Sized + 'static,
+        T: Presence,
+    {
+        Required::new(Box::new(self))
+    }
+
     fn boxed(self) -> Box<dyn DOMProcessor<T>>
     where
         Self: Sized + 'static,
@@ -116,6 +126,12 @@ pub trait DOMProcessor<T>: Sync + Send {
     }
 }
 
+impl<T> DOMProcessor<T> for Box<dyn DOMProcessor<T>> {
+    fn process(&self, el: &ElementRef) -> T {
+        (**self).process(el)
+    }
+}
+
 pub trait ItrDOMProcessor<T>: DOMProcessor<Vec<T>> {
     fn filter<Item, Predicate>(self, predicate: Predicate) -> FilterProcessor<Item>
     where
@@ -145,10 +161,10 @@ pub struct ContentInfoProcessor {
 impl DOMProcessor<ContentInfo> for ContentInfoProcessor {
     fn process(&self, el: &ElementRef) -> ContentInfo {
         ContentInfo {
-            id: self.id.process(el),
-            title: self.title.process(el),
-            secondary_title: self.secondary_title.process(el),
-            image: self.image.process(el),
+            id: in_field("id", || self.id.process(el)),
+            title: in_field("title", || self.title.process(el)),
+            secondary_title: in_field("secondary_title", || self.secondary_title.process(el)),
+            image: in_field("image", || self.image.process(el)),
         }
     }
 }
@@ -168,14 +184,14 @@ impl DOMProcessor<ContentDetails> for ContentDetailsProcessor {
     fn process(&self, el: &ElementRef) -> ContentDetails {
         ContentDetails {
             media_type: self.media_type,
-            title: self.title.process(el),
-            original_title: self.original_title.process(el),
-            image: self.image.process(el),
-            description: self.description.process(el),
-            additional_info: self.additional_info.process(el),
+            title: in_field("title", || self.title.process(el)),
+            original_title: in_field("original_title", || self.original_title.process(el)),
+            image: in_field("image", || self.image.process(el)),
+            description: in_field("description", || self.description.process(el)),
+            additional_info: in_field("additional_info", || self.additional_info.process(el)),
             media_items: None,
-            similar: self.similar.process(el),
-            params: self.params.process(el),
+            similar: in_field("similar", || self.similar.process(el)),
+            params: in_field("params", || self.params.process(el)),
         }
     }
 }
@@ -191,11 +207,11 @@ pub struct ContentMediaItemProcessor {
 impl DOMProcessor<ContentMediaItem> for ContentMediaItemProcessor {
     fn process(&self, el: &ElementRef) -> ContentMediaItem {
         ContentMediaItem {
-            title: self.title.process(el),
-            section: self.section.process(el),
-            image: self.image.process(el),
-            sources: self.sources.process(el),
-            params: self.params.process(el),
+            title: in_field("title", || self.title.process(el)),
+            section: in_field("section", || self.section.process(el)),
+            image: in_field("image", || self.image.process(el)),
+            sources: in_field("sources", || self.sources.process(el)),
+            params: in_field("params", || self.params.process(el)),
         }
     }
 }
@@ -252,15 +268,16 @@ pub fn optional_text_value(selectors: &str) -> Box<dyn DOMProcessor<Option<Strin
 }
 
 pub struct AttrValue {
-    pub attr: &'static str,
+    pub attr: Cow<'static, str>,
 }
 
 impl DOMProcessor<Option<String>> for AttrValue {
     fn process(&self, el: &ElementRef) -> Option<String> {
-        let res = el.attr(self.attr).map(|s| s.to_string());
+        let res = el.attr(&self.attr).map(|s| s.to_string());
 
         if res.is_none() {
             warn!("attr {:?} not found in {:?}", self.attr, el);
+            record_miss(|| format!("@{}", self.attr));
         }
 
         res
@@ -268,8 +285,8 @@ impl DOMProcessor<Option<String>> for AttrValue {
 }
 
 impl AttrValue {
-    pub fn new(attr: &'static str) -> AttrValue {
-        AttrValue { attr }
+    pub fn new(attr: impl Into<Cow<'static, str>>) -> AttrValue {
+        AttrValue { attr: attr.into() }
     }
 }
 
@@ -389,15 +406,33 @@ pub struct ItemsProcessor<Item> {
 
 impl<Item> DOMProcessor<Vec<Item>> for ItemsProcessor<Item> {
     fn process(&self, el: &ElementRef) -> Vec<Item> {
-        if let Some(selector) = &self.scope {
+        let items: Vec<_> = if let Some(selector) = &self.scope {
             el.select(selector)
-                .map(|e| self.item_processor.process(&e))
+                .enumerate()
+                .map(|(idx, e)| {
+                    in_path(
+                        || format!("{}[{idx}]", selector.to_css_string()),
+                        || self.item_processor.process(&e),
+                    )
+                })
                 .collect()
         } else {
             el.child_elements()
-                .map(|e| self.item_processor.process(&e))
+                .enumerate()
+                .map(|(idx, e)| in_path(|| format!("*[{idx}]"), || self.item_processor.process(&e)))
                 .collect()
+        };
+
+        if items.is_empty() {
+            record_miss(|| {
+                self.scope
+                    .as_ref()
+                    .map(|s| s.to_css_string())
+                    .unwrap_or_else(|| "*".into())
+            });
         }
+
+        items
     }
 }
 
@@ -552,10 +587,12 @@ pub struct ScopeProcessor<Item> {
 
 impl<Item> DOMProcessor<Option<Item>> for ScopeProcessor<Item> {
     fn process(&self, el: &ElementRef) -> Option<Item> {
-        let res = el
-            .select(&self.scope)
-            .map(|e| self.item_processor.process(&e))
-            .next();
+        let res = el.select(&self.scope).next().map(|e| {
+            in_path(
+                || self.scope.to_css_string(),
+                || self.item_processor.process(&e),
+            )
+        });
 
         if res.is_none() {
             warn!(
@@ -563,6 +600,7 @@ impl<Item> DOMProcessor<Option<Item>> for ScopeProcessor<Item> {
                 self.scope.to_css_string(),
                 el
             );
+            record_miss(|| self.scope.to_css_string());
         }
 
         res
@@ -634,7 +672,7 @@ pub fn default_value() -> Box<DefaultValue> {
     Box::new(DefaultValue::new())
 }
 
-pub fn self_hosted_image<'a>(
+pub fn self_hosted_image(
     url: &'static str,
     selectors: &str,
     attr: &'static str,
@@ -657,12 +695,264 @@ pub fn self_hosted_image_optional(
         .boxed()
 }
 
+/// Absolute url of site resource, relative links point to working mirror of `base_url`
 pub fn self_hosted_url(base_url: &str, src: &str) -> String {
     if src.starts_with("http") {
         return src.to_string();
     }
+    let base_url = super::mirrors::current(base_url);
     if !src.starts_with("/") {
         return format!("{base_url}/{src}");
     }
     format!("{base_url}{src}")
 }
+
+// diagnostics
+
+/// Selector which matched nothing and where it was evaluated in processor tree
+#[derive(Debug, Clone)]
+struct Miss {
+    path: String,
+    selector: String,
+}
+
+#[derive(Default)]
+struct Trace {
+    path: Vec<String>,
+    misses: Vec<Miss>,
+    broken: Option<Miss>,
+}
+
+thread_local! {
+    static TRACE: RefCell<Option<Trace>> = const { RefCell::new(None) };
+}
+
+fn with_trace<R>(f: impl FnOnce(&mut Trace) -> R) -> Option<R> {
+    TRACE.with_borrow_mut(|trace| trace.as_mut().map(f))
+}
+
+fn in_path<R>(segment: impl FnOnce() -> String, f: impl FnOnce() -> R) -> R {
+    let entered = with_trace(|trace| trace.path.push(segment())).is_some();
+    let res = f();
+    if entered {
+        with_trace(|trace| trace.path.pop());
+    }
+    res
+}
+
+fn in_field<R>(name: &'static str, f: impl FnOnce() -> R) -> R {
+    in_path(|| name.to_owned(), f)
+}
+
+fn record_miss(selector: impl FnOnce() -> String) {
+    with_trace(|trace| {
+        let miss = Miss {
+            path: trace.path.join(" / "),
+            selector: selector(),
+        };
+        trace.misses.push(miss);
+    });
+}
+
+/// Values that can be checked by `Required`
+pub trait Presence {
+    fn is_present(&self) -> bool;
+}
+
+impl Presence for String {
+    fn is_present(&self) -> bool {
+        !self.trim().is_empty()
+    }
+}
+
+impl<T> Presence for Option<T> {
+    fn is_present(&self) -> bool {
+        self.is_some()
+    }
+}
+
+impl<T> Presence for Vec<T> {
+    fn is_present(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+pub struct Required<T> {
+    pub sub_processor: Box<dyn DOMProcessor<T>>,
+}
+
+impl<T: Presence> DOMProcessor<T> for Required<T> {
+    fn process(&self, el: &ElementRef) -> T {
+        let mark = with_trace(|trace| trace.misses.len());
+        let value = self.sub_processor.process(el);
+
+        if let Some(mark) = mark
+            && !value.is_present()
+        {
+            with_trace(|trace| {
+                // first miss is the outermost selector that broke
+                let miss = trace.misses.get(mark).cloned().unwrap_or_else(|| Miss {
+                    path: trace.path.join(" / "),
+                    selector: String::new(),
+                });
+                trace.broken.get_or_insert(miss);
+            });
+        }
+
+        value
+    }
+}
+
+impl<T> Required<T> {
+    pub fn new(sub_processor: Box<dyn DOMProcessor<T>>) -> Required<T> {
+        Required { sub_processor }
+    }
+}
+
+pub fn required<T: Presence + 'static>(
+    sub_processor: Box<dyn DOMProcessor<T>>,
+) -> Box<dyn DOMProcessor<T>> {
+    Required::new(sub_processor).boxed()
+}
+
+/// Scope that must be present on page while its content may be empty, e.g. container of search results
+pub fn required_scope<T: Default + 'static>(
+    scope: &str,
+    sub_processor: Box<dyn DOMProcessor<T>>,
+) -> Box<dyn DOMProcessor<T>> {
+    ScopeProcessor::new(scope, sub_processor)
+        .required()
+        .unwrap_or_default()
+        .boxed()
+}
+
+/// Process element and report first required field that is missing as layout change
+pub fn process_checked<T>(processor: &dyn DOMProcessor<T>, el: &ElementRef) -> SupplierResult<T> {
+    struct Restore(Option<Trace>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let prev = self.0.take();
+            TRACE.with_borrow_mut(|trace| *trace = prev);
+        }
+    }
+
+    let restore = Restore(TRACE.with_borrow_mut(|trace| trace.replace(Trace::default())));
+    let res = processor.process(el);
+    let broken = with_trace(|trace| trace.broken.take()).flatten();
+    drop(restore);
+
+    match broken {
+        Some(miss) => Err(SupplierError::LayoutChanged {
+            path: miss.path,
+            selector: miss.selector,
+        }),
+        None => Ok(res),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details_processor() -> Required<Option<ContentDetails>> {
+        ScopeProcessor::new(
+            ".details",
+            ContentDetailsProcessor {
+                media_type: MediaType::Video,
+                title: required(text_value("h1.title")),
+                original_title: optional_text_value(".original"),
+                image: attr_value("img.poster", "src"),
+                description: text_value(".description"),
+                additional_info: default_value(),
+                similar: items_processor(
+                    ".similar a",
+                    ContentInfoProcessor {
+                        id: AttrValue::new("href")
+                            .unwrap_or_default()
+                            .required()
+                            .boxed(),
+                        title: text_value("span"),
+                        secondary_title: default_value(),
+                        image: default_value(),
+                    }
+                    .boxed(),
+                ),
+                params: default_value(),
+            }
+            .boxed(),
+        )
+        .required()
+    }
+
+    fn process(html: &str) -> SupplierResult<Option<ContentDetails>> {
+        let document = scraper::Html::parse_document(html);
+        process_checked(&details_processor(), &document.root_element())
+    }
+
+    #[test]
+    fn should_ignore_missing_optional_fields() {
+        let details = process(r#"<div class="details"><h1 class="title">Title</h1></div>"#)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(details.title, "Title");
+        assert_eq!(details.image, "");
+    }
+
+    #[test]
+    fn should_report_selector_of_missing_required_field() {
+        let err = process(r#"<div class="details"><h1 class="name">Title</h1></div>"#).unwrap_err();
+
+        assert!(matches!(
+            err,
+            SupplierError::LayoutChanged { ref path, ref selector }
+                if path == ".details / title" && selector == "h1.title"
+        ));
+    }
+
+    #[test]
+    fn should_report_path_of_missing_attr_in_list_item() {
+        let err = process(
+            r#"<div class="details">
+                <h1 class="title">Title</h1>
+                <div class="similar"><a href="/1"><span>1</span></a><a><span>2</span></a></div>
+            </div>"#,
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            SupplierError::LayoutChanged { ref path, ref selector }
+                if path == ".details / similar / .similar a[1] / id" && selector == "@href"
+        ));
+    }
+
+    #[test]
+    fn should_report_selector_of_missing_details_scope() {
+        let err = process(r#"<div class="page"><h1 class="title">Title</h1></div>"#).unwrap_err();
+
+        assert!(matches!(
+            err,
+            SupplierError::LayoutChanged { ref path, ref selector }
+                if path.is_empty() && selector == ".details"
+        ));
+    }
+
+    #[test]
+    fn should_allow_empty_list_in_required_scope() {
+        let processor = required_scope(".list", items_processor("a", text_value("span")));
+        let empty = scraper::Html::parse_document(r#"<div class="list"></div>"#);
+        let missing = scraper::Html::parse_document(r#"<div class="grid"></div>"#);
+
+        assert!(
+            process_checked(processor.as_ref(), &empty.root_element())
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            process_checked(processor.as_ref(), &missing.root_element()),
+            Err(SupplierError::LayoutChanged { ref selector, .. }) if selector == ".list"
+        ));
+    }
+}
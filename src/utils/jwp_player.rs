@@ -50,6 +50,7 @@ impl JWPConfig {
         prefix: &str,
         headers: Option<HashMap<String, String>>,
         hls_proxy: bool,
+        subtitle_langs: &[String],
     ) -> Vec<ContentMediaItemSource> {
         let mut result = vec![];
 
@@ -66,7 +67,7 @@ impl JWPConfig {
                 link: String::from(&source.file),
                 headers: headers.clone(),
                 description,
-                hls_proxy: hls_proxy,
+                hls_proxy,
             });
         });
 
@@ -81,7 +82,7 @@ impl JWPConfig {
                 let mut description = format!("{prefix} {num}.");
 
                 if let Some(label) = &track.label {
-                    if !lang::is_allowed(&label) {
+                    if !lang::is_allowed(subtitle_langs, label) {
                         return;
                     }
 
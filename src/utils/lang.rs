@@ -3,24 +3,32 @@ use std::{
     sync::OnceLock,
 };
 
-pub fn is_allowed(b: &str) -> bool {
-    let langs_alias_once: OnceLock<HashMap<&str, HashSet<&str>>> = OnceLock::new();
-    let langs_alias = langs_alias_once.get_or_init(|| {
+/// Languages subtitles and dubs can be filtered by
+pub const LANGUAGES: [&str; 8] = ["en", "uk", "ja", "es", "fr", "de", "pt", "it"];
+
+pub fn is_allowed(langs: &[String], b: &str) -> bool {
+    static LANGS_ALIAS: OnceLock<HashMap<&str, HashSet<&str>>> = OnceLock::new();
+    let langs_alias = LANGS_ALIAS.get_or_init(|| {
         HashMap::from([
             ("en", HashSet::from(["eng", "english"])),
-            ("uk", HashSet::from(["ukk", "ukranian"])),
+            ("uk", HashSet::from(["ukk", "ukranian", "ukr", "ukrainian"])),
             ("ja", HashSet::from(["jpn", "japanese"])),
+            ("es", HashSet::from(["spa", "spanish"])),
+            ("fr", HashSet::from(["fre", "french"])),
+            ("de", HashSet::from(["ger", "german"])),
+            ("pt", HashSet::from(["por", "portuguese"])),
+            ("it", HashSet::from(["ita", "italian"])),
         ])
     });
 
     let lowwer_b = b.to_lowercase();
 
-    for l in ["en", "uk", "ja"] {
+    for l in langs {
         if *l == lowwer_b {
             return true;
         }
 
-        let maybe_alias = langs_alias.get(l);
+        let maybe_alias = langs_alias.get(l.as_str());
 
         if let Some(alias) = maybe_alias {
             for a in alias {
@@ -0,0 +1,338 @@
+//! Mirror domains of suppliers.
+//!
+//! Suppliers, ids and cache keys keep using canonical base url, requests to it
+//! are sent to the working mirror and move to the next one when mirror is gone
+//! (dns, connection errors or redirect to parking page).
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{OnceLock, RwLock},
+};
+
+use log::warn;
+use reqwest::{Request, Response, Url};
+
+use crate::errors::{SupplierError, SupplierResult};
+
+#[derive(Default)]
+struct Site {
+    // registered by suppliers, canonical url first
+    defaults: Vec<Url>,
+    // pushed from host app, always wins
+    overrides: Option<Vec<Url>>,
+    // working mirror, remembered for the process lifetime
+    active: Option<Url>,
+}
+
+impl Site {
+    fn mirrors(&self) -> &[Url] {
+        self.overrides.as_deref().unwrap_or(&self.defaults)
+    }
+
+    fn active(&self) -> Option<&Url> {
+        self.active.as_ref().or_else(|| self.mirrors().first())
+    }
+}
+
+// keyed by canonical host
+fn sites() -> &'static RwLock<HashMap<String, Site>> {
+    static SITES: OnceLock<RwLock<HashMap<String, Site>>> = OnceLock::new();
+    SITES.get_or_init(Default::default)
+}
+
+/// Supplier defaults, mirrors pushed by host app take precedence
+pub fn register_mirrors(canonical: &str, mirrors: &[String]) -> SupplierResult<()> {
+    let canonical_url = parse_base(canonical)?;
+    let mut defaults = vec![canonical_url.clone()];
+    for mirror in mirrors {
+        let mirror = parse_base(mirror)?;
+        if !defaults.contains(&mirror) {
+            defaults.push(mirror);
+        }
+    }
+
+    let mut sites = sites().write().unwrap();
+    sites.entry(host_key(&canonical_url)).or_default().defaults = defaults;
+    Ok(())
+}
+
+/// Replace mirrors of site, empty list restores supplier defaults
+pub fn set_mirrors(canonical: &str, mirrors: &[String]) -> SupplierResult<()> {
+    let canonical_url = parse_base(canonical)?;
+    let mirrors = mirrors
+        .iter()
+        .map(|m| parse_base(m))
+        .collect::<SupplierResult<Vec<_>>>()?;
+
+    let mut sites = sites().write().unwrap();
+    let site = sites.entry(host_key(&canonical_url)).or_default();
+    if site.defaults.is_empty() {
+        site.defaults = vec![canonical_url];
+    }
+
+    site.active = site
+        .active
+        .take()
+        .filter(|active| mirrors.is_empty() || mirrors.contains(active));
+    site.overrides = Some(mirrors).filter(|m| !m.is_empty());
+    Ok(())
+}
+
+/// Mirrors of site in failover order
+pub fn mirrors(canonical: &str) -> Vec<String> {
+    with_site(canonical, |site| {
+        site.mirrors().iter().map(base_to_string).collect()
+    })
+    .unwrap_or_else(|| vec![canonical.to_owned()])
+}
+
+/// Base url of working mirror, canonical url for sites without mirrors
+pub fn current(canonical: &str) -> String {
+    with_site(canonical, |site| site.active().map(base_to_string))
+        .flatten()
+        .unwrap_or_else(|| canonical.to_owned())
+}
+
+/// Strip base url of any mirror of site (or any other host) from link
+pub fn strip_base<'a>(canonical: &str, link: &'a str) -> &'a str {
+    if let Some(rest) = link.strip_prefix(canonical) {
+        return rest;
+    }
+
+    match link.split_once("://") {
+        Some((_, rest)) => rest.find('/').map(|idx| &rest[idx..]).unwrap_or_default(),
+        None => link,
+    }
+}
+
+/// Send request to working mirror of its site, moving to next mirror when current one is gone
+pub async fn execute<F, Fut>(request: Request, send: F) -> SupplierResult<Response>
+where
+    F: Fn(Request) -> Fut,
+    Fut: Future<Output = SupplierResult<Response>>,
+{
+    let key = host_key(request.url());
+    let Some((mirrors, active)) = with_site_key(&key, |site| {
+        (site.mirrors().to_vec(), site.active().cloned())
+    }) else {
+        return send(request).await;
+    };
+
+    // start from working mirror, then try the rest in declared order
+    let start = active
+        .and_then(|active| mirrors.iter().position(|m| *m == active))
+        .unwrap_or_default();
+    let order = mirrors[start..].iter().chain(&mirrors[..start]);
+
+    let mut last_err = None;
+    for mirror in order {
+        let Some(mut attempt) = request.try_clone() else {
+            // streamed bodies can't be resent, use working mirror only
+            let mut request = request;
+            rebase(request.url_mut(), mirror);
+            return send(request).await;
+        };
+        rebase(attempt.url_mut(), mirror);
+
+        let err = match send(attempt).await {
+            Ok(res) if is_parked(&res, &mirrors) => SupplierError::Network {
+                message: format!("{} redirected to {}", base_to_string(mirror), res.url()),
+            },
+            Ok(res) => {
+                remember(&key, mirror);
+                return Ok(res);
+            }
+            Err(err @ (SupplierError::Network { .. } | SupplierError::Timeout)) => err,
+            Err(err) => return Err(err),
+        };
+
+        warn!(
+            "[mirrors] {} is not available: {err}",
+            base_to_string(mirror)
+        );
+        last_err = Some(err);
+    }
+
+    Err(last_err.unwrap_or_else(|| SupplierError::invalid_params("no mirrors")))
+}
+
+fn remember(key: &str, mirror: &Url) {
+    let mut sites = sites().write().unwrap();
+    if let Some(site) = sites.get_mut(key)
+        && site.active.as_ref() != Some(mirror)
+    {
+        site.active = Some(mirror.clone());
+    }
+}
+
+// dead domains are often redirected to registrar or ad parking on foreign host,
+// `www` and other subdomains of a mirror are the same site
+fn is_parked(res: &Response, mirrors: &[Url]) -> bool {
+    let host = site_host(res.url());
+    !mirrors.iter().any(|m| {
+        let mirror = site_host(m);
+        host == mirror || host.ends_with(&format!(".{mirror}"))
+    })
+}
+
+fn site_host(url: &Url) -> &str {
+    let host = url.host_str().unwrap_or_default();
+    host.strip_prefix("www.").unwrap_or(host)
+}
+
+fn rebase(url: &mut Url, mirror: &Url) {
+    // both are http(s) urls, so changing scheme, host and port can't fail
+    let _ = url.set_scheme(mirror.scheme());
+    let _ = url.set_host(mirror.host_str());
+    let _ = url.set_port(mirror.port());
+}
+
+fn with_site<T>(canonical: &str, f: impl FnOnce(&Site) -> T) -> Option<T> {
+    let url = Url::parse(canonical).ok()?;
+    with_site_key(&host_key(&url), f)
+}
+
+fn with_site_key<T>(key: &str, f: impl FnOnce(&Site) -> T) -> Option<T> {
+    let sites = sites().read().unwrap();
+    sites.get(key).filter(|s| !s.mirrors().is_empty()).map(f)
+}
+
+fn host_key(url: &Url) -> String {
+    match url.port() {
+        Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+        None => url.host_str().unwrap_or_default().to_owned(),
+    }
+}
+
+fn base_to_string(url: &Url) -> String {
+    url.as_str().trim_end_matches('/').to_owned()
+}
+
+/// Validates site root used as supplier url or mirror
+pub fn parse_base(base: &str) -> SupplierResult<Url> {
+    let url = Url::parse(base)
+        .map_err(|err| SupplierError::invalid_params(format!("invalid mirror {base}: {err}")))?;
+
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() || url.path() != "/" {
+        return Err(SupplierError::invalid_params(format!(
+            "mirror has to be http(s) site root: {base}"
+        )));
+    }
+
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::ResponseBuilderExt;
+
+    #[test]
+    fn should_strip_any_mirror_base() {
+        let canonical = "https://uakino.best";
+        assert_eq!(
+            strip_base(canonical, "https://uakino.best/a/1-b.html"),
+            "/a/1-b.html"
+        );
+        assert_eq!(
+            strip_base(canonical, "https://uakino.me/a/1-b.html"),
+            "/a/1-b.html"
+        );
+        assert_eq!(strip_base(canonical, "/a/1-b.html"), "/a/1-b.html");
+        assert_eq!(strip_base(canonical, "https://uakino.me"), "");
+    }
+
+    #[test]
+    fn should_prefer_pushed_mirrors() {
+        let canonical = "https://mirrors-test.example";
+        register_mirrors(canonical, &["https://mirror-a.example".into()]).unwrap();
+        assert_eq!(
+            mirrors(canonical),
+            vec![canonical, "https://mirror-a.example"]
+        );
+        assert_eq!(current(canonical), canonical);
+
+        set_mirrors(canonical, &["https://mirror-b.example/".into()]).unwrap();
+        assert_eq!(mirrors(canonical), vec!["https://mirror-b.example"]);
+        assert_eq!(current(canonical), "https://mirror-b.example");
+
+        set_mirrors(canonical, &[]).unwrap();
+        assert_eq!(current(canonical), canonical);
+
+        assert!(set_mirrors(canonical, &["https://mirror-c.example/path".into()]).is_err());
+        assert!(set_mirrors(canonical, &["ftp://mirror-c.example".into()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn should_fail_over_to_next_mirror() {
+        let canonical = "https://failover-test.example";
+        register_mirrors(canonical, &["https://failover-mirror.example".into()]).unwrap();
+
+        let request = Request::new(
+            reqwest::Method::GET,
+            Url::parse("https://failover-test.example/page/1").unwrap(),
+        );
+
+        let sent = std::sync::Mutex::new(vec![]);
+        let res = execute(request, |request| {
+            sent.lock().unwrap().push(request.url().to_string());
+            async move {
+                if request.url().host_str() == Some("failover-test.example") {
+                    return Err(SupplierError::Network {
+                        message: "dns error".into(),
+                    });
+                }
+                Ok(Response::from(
+                    http::Response::builder()
+                        .status(200)
+                        .url(request.url().clone())
+                        .body(String::new())
+                        .unwrap(),
+                ))
+            }
+        })
+        .await;
+
+        assert!(res.is_ok());
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![
+                "https://failover-test.example/page/1",
+                "https://failover-mirror.example/page/1"
+            ]
+        );
+        assert_eq!(current(canonical), "https://failover-mirror.example");
+    }
+
+    #[tokio::test]
+    async fn should_follow_redirect_to_www() {
+        let canonical = "https://redirect-test.example";
+        register_mirrors(canonical, &["https://redirect-mirror.example".into()]).unwrap();
+
+        let request = Request::new(
+            reqwest::Method::GET,
+            Url::parse("https://redirect-test.example/page/1").unwrap(),
+        );
+
+        let res = execute(request, |request| async move {
+            let mut url = request.url().clone();
+            url.set_host(Some(&format!("www.{}", url.host_str().unwrap())))
+                .unwrap();
+            Ok(Response::from(
+                http::Response::builder()
+                    .status(200)
+                    .url(url)
+                    .body(String::new())
+                    .unwrap(),
+            ))
+        })
+        .await;
+
+        assert_eq!(
+            res.unwrap().url().as_str(),
+            "https://www.redirect-test.example/page/1"
+        );
+        assert_eq!(current(canonical), canonical);
+    }
+}
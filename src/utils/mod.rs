@@ -1,16 +1,33 @@
 #![allow(dead_code)]
 
 pub mod anilist;
+pub mod cache;
+pub mod cancel;
+pub mod challenge;
+pub mod cookies;
+pub mod credentials;
 pub mod crypto;
 pub mod crypto_js;
 pub mod datalife;
 pub mod dns_over_https;
+pub mod downloader;
 pub mod enc_dec_app;
+#[cfg(test)]
+pub mod golden;
+pub mod har;
+pub mod hls;
+pub mod hls_proxy;
 pub mod html;
 pub mod jwp_player;
 pub mod lang;
+pub mod mirrors;
 pub mod nextjs;
+pub mod params;
 pub mod playerjs;
+pub mod proxy;
+pub mod request_policy;
+pub mod settings;
+pub mod sources_sink;
 pub mod text;
 pub mod unpack;
 
@@ -19,13 +36,16 @@ use std::{
     time::Duration,
 };
 
+use cache::CacheEntry;
 use dns_over_https::DoHResolver;
-use log::info;
+use log::{info, warn};
 use reqwest::{
-    ClientBuilder,
+    ClientBuilder, RequestBuilder, StatusCode,
     header::{self, HeaderMap},
 };
-use serde::Deserialize;
+use serde::{Deserialize, de::DeserializeOwned};
+
+use crate::errors::SupplierResult;
 
 #[derive(Debug, Deserialize)]
 pub struct GenericResponse {
@@ -72,8 +92,9 @@ pub fn create_client_builder() -> reqwest::ClientBuilder {
         .read_timeout(Duration::from_secs(15))
         .user_agent(get_user_agent())
         .danger_accept_invalid_certs(true)
-        .cookie_store(true)
-        .dns_resolver(Arc::new(DoHResolver::default()))
+        .cookie_provider(cookies::jar())
+        .dns_resolver(Arc::new(DoHResolver))
+        .proxy(proxy::client_proxy())
 }
 
 pub fn get_default_headers() -> HeaderMap {
@@ -93,11 +114,91 @@ pub fn get_default_headers() -> HeaderMap {
     headers
 }
 
+/// Send request with host request policy applied, for callers that need raw response.
+/// Only requests sent through here (or `fetch_*`) are recorded to and replayed from HAR files
+pub async fn send(request_builder: RequestBuilder) -> SupplierResult<reqwest::Response> {
+    let (client, request) = request_builder.build_split();
+    request_policy::execute(&client, request?).await
+}
+
+/// Load response body with host request policy applied, goes through response cache when called inside cache scope
+pub async fn fetch_text(request_builder: RequestBuilder) -> SupplierResult<String> {
+    let (client, request) = request_builder.build_split();
+    let mut request = request?;
+
+    let Some(ttl) = cache::current_ttl() else {
+        return challenge::read_text(request_policy::execute(&client, request).await?).await;
+    };
+
+    let key = cache::cache_key(
+        request.method().as_str(),
+        request.url().as_str(),
+        request.headers(),
+        request.body().and_then(|b| b.as_bytes()),
+    );
+
+    let cached = cache::get(&key).await;
+
+    if let Some(entry) = &cached {
+        if entry.is_fresh(ttl) {
+            return Ok(entry.body.clone());
+        }
+
+        let headers = request.headers_mut();
+        if let Some(etag) = entry.etag.as_ref().and_then(|v| v.parse().ok()) {
+            headers.insert(header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = entry.last_modified.as_ref().and_then(|v| v.parse().ok()) {
+            headers.insert(header::IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let result: SupplierResult<String> = async {
+        let res = request_policy::execute(&client, request).await?;
+
+        if res.status() == StatusCode::NOT_MODIFIED
+            && let Some(mut entry) = cached.clone()
+        {
+            entry.touch();
+            let body = entry.body.clone();
+            cache::put(&key, entry).await;
+            return Ok(body);
+        }
+
+        let header_value = |name| {
+            res.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_owned())
+        };
+        let etag = header_value(header::ETAG);
+        let last_modified = header_value(header::LAST_MODIFIED);
+
+        let body = challenge::read_text(res).await?;
+        cache::put(&key, CacheEntry::new(body.clone(), etag, last_modified)).await;
+        Ok(body)
+    }
+    .await;
+
+    match (result, cached) {
+        (Err(err), Some(entry)) if err.is_transient() && entry.is_usable_stale() => {
+            warn!("[cache] serving stale response: {err}");
+            Ok(entry.body)
+        }
+        (result, _) => result,
+    }
+}
+
+pub async fn fetch_json<T: DeserializeOwned>(request_builder: RequestBuilder) -> SupplierResult<T> {
+    let text = fetch_text(request_builder).await?;
+    Ok(serde_json::from_str(&text)?)
+}
+
 pub async fn scrap_page<T>(
     request_builder: reqwest::RequestBuilder,
     processor: &dyn html::DOMProcessor<T>,
-) -> Result<T, anyhow::Error> {
-    let html = request_builder.send().await?.text().await?;
+) -> SupplierResult<T> {
+    let html = fetch_text(request_builder).await?;
 
     // println!("{html}");
 
@@ -105,17 +206,17 @@ pub async fn scrap_page<T>(
     let root = document.root_element();
 
     let start = std::time::Instant::now();
-    let res = processor.process(&root);
+    let res = html::process_checked(processor, &root);
     info!("page processing took {:?}", start.elapsed());
 
-    Ok(res)
+    res
 }
 
 pub async fn scrap_fragment<T>(
     request_builder: reqwest::RequestBuilder,
     processor: &dyn html::DOMProcessor<T>,
-) -> Result<T, anyhow::Error> {
-    let html = request_builder.send().await?.text().await?;
+) -> SupplierResult<T> {
+    let html = fetch_text(request_builder).await?;
 
     // debug!("{html}");
 
@@ -123,8 +224,8 @@ pub async fn scrap_fragment<T>(
     let root = document.root_element();
 
     let start = std::time::Instant::now();
-    let res = processor.process(&root);
+    let res = html::process_checked(processor, &root);
     info!("fragment processing took {:?}", start.elapsed());
 
-    Ok(res)
+    res
 }
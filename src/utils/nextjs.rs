@@ -1,5 +1,5 @@
 use reqwest::header;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{Serialize, de::DeserializeOwned};
 
 use crate::utils;
 
@@ -9,18 +9,17 @@ pub async fn server_action<P: Serialize, R: DeserializeOwned>(
     res_id: u8,
     params: &P,
 ) -> anyhow::Result<R> {
-    let res = utils::create_json_client()
-        .post(url)
-        .header("Referer", url)
-        .header(header::CONTENT_TYPE, "text/x-component")
-        .header("Next-Action", action)
-        .json(params)
-        .send()
-        .await?
-        .text()
-        .await?;
-
-    
+    let res = utils::send(
+        utils::create_json_client()
+            .post(url)
+            .header("Referer", url)
+            .header(header::CONTENT_TYPE, "text/x-component")
+            .header("Next-Action", action)
+            .json(params),
+    )
+    .await?
+    .text()
+    .await?;
 
     let split_pattern = format!("{res_id}:");
     let action_result = res
@@ -32,8 +31,6 @@ pub async fn server_action<P: Serialize, R: DeserializeOwned>(
         .last()
         .unwrap_or_default();
 
-    
-
     let result: R = serde_json::from_str(action_result)?;
 
     Ok(result)
@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+use crate::errors::{SupplierError, SupplierResult};
+
+/// Params persisted by host app (watch history) in a format older than current one
+pub enum LegacyParams<'a> {
+    /// plain `Vec<String>` written before params were versioned
+    Raw(&'a [String]),
+    /// envelope with older schema version
+    Versioned { version: u32, data: Value },
+}
+
+/// Typed payload of `ContentDetails.params` / `ContentMediaItem.params`.
+/// Bump `VERSION` when layout changes and convert previous layouts in `migrate`
+pub trait MediaParams: Serialize + DeserializeOwned {
+    const KIND: &'static str;
+    const VERSION: u32;
+
+    fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+        match legacy {
+            LegacyParams::Raw(_) => Err(SupplierError::invalid_params(format!(
+                "{}: unversioned params are not supported",
+                Self::KIND
+            ))),
+            LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(format!(
+                "{}: params version {version} is not supported",
+                Self::KIND
+            ))),
+        }
+    }
+
+    fn validate(&self) -> SupplierResult<()> {
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Envelope<T> {
+    kind: String,
+    v: u32,
+    data: T,
+}
+
+pub fn encode<T: MediaParams>(params: &T) -> Vec<String> {
+    let envelope = Envelope {
+        kind: T::KIND.to_owned(),
+        v: T::VERSION,
+        data: params,
+    };
+
+    vec![serde_json::to_string(&envelope).expect("params are always serializable")]
+}
+
+pub fn decode<T: MediaParams>(params: &[String]) -> SupplierResult<T> {
+    let maybe_envelope = match params {
+        [single] if single.starts_with('{') => serde_json::from_str::<Envelope<Value>>(single).ok(),
+        _ => None,
+    };
+
+    let decoded = match maybe_envelope {
+        Some(envelope) if envelope.kind != T::KIND => {
+            return Err(SupplierError::invalid_params(format!(
+                "expected {} params, got {}",
+                T::KIND,
+                envelope.kind
+            )));
+        }
+        Some(envelope) if envelope.v == T::VERSION => serde_json::from_value(envelope.data)
+            .map_err(|err| {
+                SupplierError::invalid_params(format!("{}: malformed params: {err}", T::KIND))
+            })?,
+        Some(envelope) => T::migrate(LegacyParams::Versioned {
+            version: envelope.v,
+            data: envelope.data,
+        })?,
+        None => T::migrate(LegacyParams::Raw(params))?,
+    };
+
+    decoded.validate()?;
+    Ok(decoded)
+}
+
+/// Positional param from legacy layout with readable error instead of index panic.
+/// Details params scraped straight from page (iframe links, data ids) stay positional and are read with it
+pub fn required<'a>(params: &'a [String], idx: usize, name: &str) -> SupplierResult<&'a str> {
+    params
+        .get(idx)
+        .map(String::as_str)
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| SupplierError::invalid_params(format!("{name} expected in params")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct ChapterParams {
+        chapter_id: String,
+    }
+
+    impl MediaParams for ChapterParams {
+        const KIND: &'static str = "test_chapter";
+        const VERSION: u32 = 2;
+
+        fn migrate(legacy: LegacyParams) -> SupplierResult<Self> {
+            match legacy {
+                LegacyParams::Raw(params) => Ok(Self {
+                    chapter_id: required(params, 0, "chapter id")?.to_owned(),
+                }),
+                LegacyParams::Versioned { version: 1, data } => Ok(Self {
+                    chapter_id: data["id"].as_str().unwrap_or_default().to_owned(),
+                }),
+                LegacyParams::Versioned { version, .. } => Err(SupplierError::invalid_params(
+                    format!("unknown version {version}"),
+                )),
+            }
+        }
+
+        fn validate(&self) -> SupplierResult<()> {
+            if self.chapter_id.is_empty() {
+                return Err(SupplierError::invalid_params("empty chapter id"));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn should_roundtrip_envelope() {
+        let params = ChapterParams {
+            chapter_id: "abc".into(),
+        };
+        let encoded = encode(&params);
+        assert_eq!(decode::<ChapterParams>(&encoded).unwrap(), params);
+    }
+
+    #[test]
+    fn should_migrate_old_params() {
+        let raw = vec!["abc".to_owned()];
+        assert_eq!(decode::<ChapterParams>(&raw).unwrap().chapter_id, "abc");
+
+        let v1 = vec![r#"{"kind":"test_chapter","v":1,"data":{"id":"abc"}}"#.to_owned()];
+        assert_eq!(decode::<ChapterParams>(&v1).unwrap().chapter_id, "abc");
+    }
+
+    #[test]
+    fn should_reject_invalid_params() {
+        assert!(matches!(
+            decode::<ChapterParams>(&[]),
+            Err(SupplierError::InvalidParams { .. })
+        ));
+
+        let other_kind = vec![r#"{"kind":"tmdb_sources","v":2,"data":{}}"#.to_owned()];
+        assert!(matches!(
+            decode::<ChapterParams>(&other_kind),
+            Err(SupplierError::InvalidParams { .. })
+        ));
+
+        let empty_id = encode(&ChapterParams {
+            chapter_id: String::new(),
+        });
+        assert!(matches!(
+            decode::<ChapterParams>(&empty_id),
+            Err(SupplierError::InvalidParams { .. })
+        ));
+    }
+}
@@ -6,6 +6,7 @@ use regex::Regex;
 use reqwest::RequestBuilder;
 use serde::Deserialize;
 
+use crate::errors::{SupplierError, SupplierResult};
 use crate::models::{ContentMediaItem, ContentMediaItemSource};
 
 #[derive(Deserialize, Debug)]
@@ -20,8 +21,8 @@ pub struct PlayerJSFile {
 pub async fn load_and_parse_playerjs(
     request_builder: RequestBuilder,
     startegy: fn(&Vec<PlayerJSFile>) -> Vec<ContentMediaItem>,
-) -> anyhow::Result<Vec<ContentMediaItem>> {
-    let html = request_builder.send().await?.text().await?;
+) -> SupplierResult<Vec<ContentMediaItem>> {
+    let html = super::fetch_text(request_builder).await?;
 
     let maybe_file = extract_playerjs_playlist(&html);
 
@@ -55,8 +56,8 @@ pub async fn load_and_parse_playerjs(
 pub async fn load_and_parse_playerjs_sources(
     request_builder: RequestBuilder,
     description: &str,
-) -> anyhow::Result<Vec<ContentMediaItemSource>> {
-    let html = request_builder.send().await?.text().await?;
+) -> SupplierResult<Vec<ContentMediaItemSource>> {
+    let html = super::fetch_text(request_builder).await?;
 
     let maybe_file = extract_playerjs_playlist(&html);
 
@@ -76,10 +77,13 @@ pub async fn load_and_parse_playerjs_sources(
         populate_video_sources(&mut result, description, file);
     } else {
         let file = file.trim_end_matches("=");
-        let decoded_b64 = BASE64_STANDARD_NO_PAD.decode(file)?;
+        let decoded_b64 = BASE64_STANDARD_NO_PAD
+            .decode(file)
+            .map_err(|err| SupplierError::parse(format!("playerjs file: {err}")))?;
         let decoded_b64 = decoded_b64.into_iter().rev().collect();
 
-        let decoded_file = String::from_utf8(decoded_b64)?;
+        let decoded_file = String::from_utf8(decoded_b64)
+            .map_err(|err| SupplierError::parse(format!("playerjs file: {err}")))?;
         populate_video_sources(&mut result, description, &decoded_file);
     }
     Ok(result)
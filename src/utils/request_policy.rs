@@ -0,0 +1,333 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, RandomState},
+    sync::{Mutex, OnceLock, RwLock},
+    time::{Duration, Instant},
+};
+
+use chrono::DateTime;
+use log::{debug, warn};
+use reqwest::{Client, Method, Request, Response, StatusCode, header};
+
+use crate::errors::{SupplierError, SupplierResult};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestPolicy {
+    /// sustained request rate per host, 0 disables rate limiting
+    pub requests_per_second: f64,
+    /// requests that can be sent at once before limiting kicks in
+    pub burst: u32,
+    /// retries for idempotent requests on 429, 5xx and timeouts
+    pub max_retries: u32,
+    pub base_delay_ms: u64,
+    /// longest wait before retry, response with longer `Retry-After` is returned as is
+    pub max_delay_ms: u64,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 10.0,
+            burst: 10,
+            max_retries: 2,
+            base_delay_ms: 500,
+            max_delay_ms: 10_000,
+        }
+    }
+}
+
+impl RequestPolicy {
+    pub fn validate(&self) -> SupplierResult<()> {
+        if !self.requests_per_second.is_finite() || self.requests_per_second < 0.0 {
+            return Err(SupplierError::invalid_params(
+                "requests_per_second must be a positive number",
+            ));
+        }
+
+        if self.base_delay_ms > self.max_delay_ms {
+            return Err(SupplierError::invalid_params(
+                "base_delay_ms must not exceed max_delay_ms",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base_delay_ms
+            .saturating_mul(1 << attempt.min(16))
+            .min(self.max_delay_ms);
+
+        // full jitter keeps parallel fan-outs from retrying in lockstep
+        Duration::from_millis(random_u64() % (exp + 1))
+    }
+
+    /// Server asked delay is honoured as is, `None` when it is longer than the policy allows to wait
+    fn retry_delay(&self, retry_after: Option<Duration>, attempt: u32) -> Option<Duration> {
+        match retry_after {
+            Some(delay) if delay > Duration::from_millis(self.max_delay_ms) => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+struct Policies {
+    // set from host app configuration, always wins
+    overrides: HashMap<String, RequestPolicy>,
+    // registered by suppliers for the hosts they use
+    defaults: HashMap<String, RequestPolicy>,
+}
+
+fn policies() -> &'static RwLock<Policies> {
+    static POLICIES: OnceLock<RwLock<Policies>> = OnceLock::new();
+    POLICIES.get_or_init(|| {
+        RwLock::new(Policies {
+            overrides: HashMap::new(),
+            defaults: HashMap::new(),
+        })
+    })
+}
+
+fn buckets() -> &'static Mutex<HashMap<String, Bucket>> {
+    static BUCKETS: OnceLock<Mutex<HashMap<String, Bucket>>> = OnceLock::new();
+    BUCKETS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Supplier default for host, configuration from host app takes precedence
+pub fn register_host_policy(host: &str, policy: RequestPolicy) {
+    policies()
+        .write()
+        .unwrap()
+        .defaults
+        .insert(host.to_owned(), policy);
+}
+
+pub fn set_host_policy(host: &str, policy: RequestPolicy) {
+    policies()
+        .write()
+        .unwrap()
+        .overrides
+        .insert(host.to_owned(), policy);
+}
+
+pub fn reset_host_policy(host: &str) {
+    policies().write().unwrap().overrides.remove(host);
+}
+
+pub fn host_policy(host: &str) -> RequestPolicy {
+    let policies = policies().read().unwrap();
+    policies
+        .overrides
+        .get(host)
+        .or_else(|| policies.defaults.get(host))
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Send request to working mirror with host rate limit and retries applied
+pub async fn execute(client: &Client, request: Request) -> SupplierResult<Response> {
+    super::mirrors::execute(request, |request| execute_with_policy(client, request)).await
+}
+
+async fn execute_with_policy(client: &Client, request: Request) -> SupplierResult<Response> {
+    let host = request.url().host_str().unwrap_or_default().to_owned();
+    let policy = host_policy(&host);
+    let retryable = is_idempotent(request.method());
+
+    let mut request = request;
+    let mut attempt = 0;
+    loop {
+        // streamed bodies can't be cloned, such requests are sent once
+        let next = if retryable && attempt < policy.max_retries {
+            request.try_clone()
+        } else {
+            None
+        };
+
+        acquire(&host, &policy).await;
+        let result = super::har::execute(client, request).await;
+
+        let Some(next) = next else {
+            return result;
+        };
+
+        let delay = match result {
+            // challenge page won't go away until user passes it
+            Ok(res)
+                if is_retryable_status(res.status())
+                    && super::challenge::detect_by_headers(res.status(), res.headers())
+                        .is_none() =>
+            {
+                let Some(delay) = policy.retry_delay(retry_after(&res), attempt) else {
+                    warn!(
+                        "[request_policy] {} asks to retry later than {}ms, giving up",
+                        res.url(),
+                        policy.max_delay_ms
+                    );
+                    return Ok(res);
+                };
+                warn!(
+                    "[request_policy] {} answered {}, retry in {delay:?}",
+                    res.url(),
+                    res.status()
+                );
+                delay
+            }
+            Ok(res) => return Ok(res),
+            Err(err @ (SupplierError::Timeout | SupplierError::Network { .. })) => {
+                let delay = policy.backoff(attempt);
+                warn!("[request_policy] {host}: {err}, retry in {delay:?}");
+                delay
+            }
+            Err(err) => return Err(err),
+        };
+
+        tokio::time::sleep(delay).await;
+        request = next;
+        attempt += 1;
+    }
+}
+
+async fn acquire(host: &str, policy: &RequestPolicy) {
+    if policy.requests_per_second <= 0.0 {
+        return;
+    }
+
+    let wait = {
+        let mut buckets = buckets().lock().unwrap();
+        let capacity = policy.burst.max(1) as f64;
+        let now = Instant::now();
+
+        let bucket = buckets.entry(host.to_owned()).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * policy.requests_per_second).min(capacity);
+        bucket.updated_at = now;
+
+        // token is reserved right away, so concurrent callers queue up behind each other
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64(
+                -bucket.tokens / policy.requests_per_second,
+            ))
+        }
+    };
+
+    if let Some(wait) = wait {
+        debug!("[request_policy] {host} throttled for {wait:?}");
+        tokio::time::sleep(wait).await;
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+    )
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+fn retry_after(res: &Response) -> Option<Duration> {
+    let value = res.headers().get(header::RETRY_AFTER)?.to_str().ok()?;
+    parse_retry_after(value)
+}
+
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(secs) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    let secs = date.timestamp() - chrono::Utc::now().timestamp();
+    Some(Duration::from_secs(secs.max(0) as u64))
+}
+
+fn random_u64() -> u64 {
+    RandomState::new().hash_one(Instant::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn should_keep_backoff_under_max_delay() {
+        let policy = RequestPolicy::default();
+        for attempt in 0..32 {
+            assert!(policy.backoff(attempt) <= Duration::from_millis(policy.max_delay_ms));
+        }
+    }
+
+    #[test]
+    fn should_give_up_when_retry_after_exceeds_max_delay() {
+        let policy = RequestPolicy::default();
+        assert_eq!(
+            policy.retry_delay(Some(Duration::from_secs(3)), 0),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(policy.retry_delay(Some(Duration::from_secs(60)), 0), None);
+        assert!(policy.retry_delay(None, 0).is_some());
+    }
+
+    #[test]
+    fn should_prefer_configured_policy() {
+        let supplier_default = RequestPolicy {
+            requests_per_second: 1.0,
+            ..Default::default()
+        };
+        let configured = RequestPolicy {
+            requests_per_second: 2.0,
+            ..Default::default()
+        };
+
+        register_host_policy("policy.test", supplier_default.clone());
+        assert_eq!(host_policy("policy.test"), supplier_default);
+
+        set_host_policy("policy.test", configured.clone());
+        register_host_policy("policy.test", supplier_default.clone());
+        assert_eq!(host_policy("policy.test"), configured);
+
+        reset_host_policy("policy.test");
+        assert_eq!(host_policy("policy.test"), supplier_default);
+    }
+
+    #[tokio::test]
+    async fn should_throttle_after_burst() {
+        let policy = RequestPolicy {
+            requests_per_second: 20.0,
+            burst: 2,
+            ..Default::default()
+        };
+
+        let start = Instant::now();
+        for _ in 0..4 {
+            acquire("throttle.test", &policy).await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+}
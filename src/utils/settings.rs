@@ -0,0 +1,162 @@
+//! User preferences of suppliers, described by [`SupplierSetting`] schema and set by host app.
+
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, RwLock},
+};
+
+use crate::errors::{SupplierError, SupplierResult};
+use crate::models::{SettingType, SupplierSetting};
+
+// setting key -> values
+type SupplierValues = HashMap<String, Vec<String>>;
+
+// keyed by supplier name
+fn store() -> &'static RwLock<HashMap<String, SupplierValues>> {
+    static STORE: OnceLock<RwLock<HashMap<String, SupplierValues>>> = OnceLock::new();
+    STORE.get_or_init(Default::default)
+}
+
+pub fn bool(key: &str, default: bool) -> SupplierSetting {
+    setting(
+        key,
+        SettingType::Bool,
+        &[],
+        &[if default { "true" } else { "false" }],
+    )
+}
+
+pub fn choice(key: &str, allowed: &[&str], default: &str) -> SupplierSetting {
+    setting(key, SettingType::Choice, allowed, &[default])
+}
+
+pub fn multi_choice(key: &str, allowed: &[&str], default: &[&str]) -> SupplierSetting {
+    setting(key, SettingType::MultiChoice, allowed, default)
+}
+
+pub fn text(key: &str, default: &str) -> SupplierSetting {
+    setting(key, SettingType::Text, &[], &[default])
+}
+
+fn setting(
+    key: &str,
+    setting_type: SettingType,
+    allowed: &[&str],
+    default: &[&str],
+) -> SupplierSetting {
+    SupplierSetting {
+        key: key.to_owned(),
+        setting_type,
+        allowed_values: allowed.iter().map(|&s| s.into()).collect(),
+        default_values: default.iter().map(|&s| s.into()).collect(),
+    }
+}
+
+/// Values set by user or defaults of setting
+pub fn values(supplier: &str, setting: &SupplierSetting) -> Vec<String> {
+    let store = store().read().unwrap();
+    store
+        .get(supplier)
+        .and_then(|values| values.get(&setting.key))
+        .cloned()
+        .unwrap_or_else(|| setting.default_values.clone())
+}
+
+/// Single value of `Bool`, `Choice` and `Text` settings
+pub fn value(supplier: &str, setting: &SupplierSetting) -> String {
+    values(supplier, setting)
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+}
+
+pub fn is_enabled(supplier: &str, setting: &SupplierSetting) -> bool {
+    value(supplier, setting) == "true"
+}
+
+/// Validate values against setting schema and store them, empty values restore default
+pub fn set(supplier: &str, setting: &SupplierSetting, values: Vec<String>) -> SupplierResult<()> {
+    let mut store = store().write().unwrap();
+    if values.is_empty() {
+        if let Some(supplier_values) = store.get_mut(supplier) {
+            supplier_values.remove(&setting.key);
+        }
+        return Ok(());
+    }
+
+    let values = validate(setting, values)?;
+    store
+        .entry(supplier.to_owned())
+        .or_default()
+        .insert(setting.key.clone(), values);
+    Ok(())
+}
+
+/// Restore defaults of all supplier settings
+pub fn reset(supplier: &str) {
+    store().write().unwrap().remove(supplier);
+}
+
+fn validate(setting: &SupplierSetting, mut values: Vec<String>) -> SupplierResult<Vec<String>> {
+    let key = &setting.key;
+    let single = !matches!(setting.setting_type, SettingType::MultiChoice);
+    if single && values.len() > 1 {
+        return Err(SupplierError::invalid_params(format!(
+            "setting {key} accepts single value"
+        )));
+    }
+
+    match setting.setting_type {
+        SettingType::Bool if values[0] != "true" && values[0] != "false" => Err(
+            SupplierError::invalid_params(format!("setting {key} accepts true or false")),
+        ),
+        SettingType::Choice | SettingType::MultiChoice => {
+            if let Some(value) = values.iter().find(|v| !setting.allowed_values.contains(v)) {
+                return Err(SupplierError::invalid_params(format!(
+                    "{value} is not allowed for setting {key}"
+                )));
+            }
+            // keep schema order, so values are stable for cache keys and urls
+            values.sort_by_key(|v| setting.allowed_values.iter().position(|a| a == v));
+            values.dedup();
+            Ok(values)
+        }
+        _ => Ok(values),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_store_validated_values() {
+        let supplier = "SettingsTest";
+        let ratings = multi_choice("ratings", &["safe", "suggestive", "erotica"], &["safe"]);
+        let adult = bool("adult", false);
+
+        assert_eq!(values(supplier, &ratings), vec!["safe"]);
+        assert!(!is_enabled(supplier, &adult));
+
+        set(
+            supplier,
+            &ratings,
+            vec!["erotica".into(), "safe".into(), "erotica".into()],
+        )
+        .unwrap();
+        assert_eq!(values(supplier, &ratings), vec!["safe", "erotica"]);
+
+        set(supplier, &adult, vec!["true".into()]).unwrap();
+        assert!(is_enabled(supplier, &adult));
+
+        assert!(set(supplier, &ratings, vec!["pornographic".into()]).is_err());
+        assert!(set(supplier, &adult, vec!["yes".into()]).is_err());
+        assert!(set(supplier, &adult, vec!["true".into(), "false".into()]).is_err());
+
+        set(supplier, &ratings, vec![]).unwrap();
+        assert_eq!(values(supplier, &ratings), vec!["safe"]);
+
+        reset(supplier);
+        assert!(!is_enabled(supplier, &adult));
+    }
+}
@@ -0,0 +1,213 @@
+use std::{
+    fmt::Display,
+    future::Future,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time,
+};
+
+use futures::{StreamExt, stream::FuturesUnordered};
+use log::{info, warn};
+
+use crate::{
+    errors::{SupplierError, SupplierResult},
+    models::{ContentMediaItemSource, SourcesEvent},
+};
+
+type EventCallback = Box<dyn Fn(SourcesEvent) + Send + Sync>;
+
+/// Receives sources as soon as they are resolved.
+/// Default sink just collects them, keeping order in which batches were started
+#[derive(Default)]
+pub struct SourcesSink {
+    on_event: Option<EventCallback>,
+    next_position: AtomicUsize,
+    collected: Mutex<Vec<(usize, Vec<ContentMediaItemSource>)>>,
+    failed: Mutex<Vec<String>>,
+}
+
+impl SourcesSink {
+    pub fn new(on_event: impl Fn(SourcesEvent) + Send + Sync + 'static) -> Self {
+        Self {
+            on_event: Some(Box::new(on_event)),
+            ..Default::default()
+        }
+    }
+
+    pub fn add(&self, origin: &str, sources: Vec<ContentMediaItemSource>) {
+        let position = self.next_position.fetch_add(1, Ordering::Relaxed);
+        self.emit(position, origin, sources);
+    }
+
+    pub fn fail(&self, origin: &str, err: impl Display) {
+        warn!("[sources] '{origin}' failed: {err}");
+        self.failed.lock().unwrap().push(origin.to_owned());
+    }
+
+    /// Run all tasks concurrently, each batch is pushed as soon as its task finishes
+    pub async fn fan_out<F>(&self, tasks: impl IntoIterator<Item = (String, F)>)
+    where
+        F: Future<Output = SupplierResult<Vec<ContentMediaItemSource>>>,
+    {
+        let tasks: Vec<_> = tasks.into_iter().collect();
+        let base = self.next_position.fetch_add(tasks.len(), Ordering::Relaxed);
+
+        let mut running: FuturesUnordered<_> = tasks
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (origin, task))| async move {
+                let start_ts = time::Instant::now();
+                let res = task.await;
+                info!("[sources] '{origin}' finished in {:?}", start_ts.elapsed());
+                (base + idx, origin, res)
+            })
+            .collect();
+
+        while let Some((position, origin, res)) = running.next().await {
+            match res {
+                Ok(sources) => self.emit(position, &origin, sources),
+                Err(err) => self.fail(&origin, err),
+            }
+        }
+    }
+
+    pub fn failed(&self) -> Vec<String> {
+        self.failed.lock().unwrap().clone()
+    }
+
+    pub fn complete(&self) {
+        if let Some(on_event) = &self.on_event {
+            on_event(SourcesEvent::Completed {
+                failed: self.failed(),
+            });
+        }
+    }
+
+    /// Finish stream with error when call failed before all tasks were run
+    pub fn abort(&self, error: SupplierError) {
+        if let Some(on_event) = &self.on_event {
+            on_event(SourcesEvent::Error { error });
+        }
+    }
+
+    pub fn into_sources(self) -> Vec<ContentMediaItemSource> {
+        let mut collected = self.collected.into_inner().unwrap();
+        collected.sort_by_key(|(position, _)| *position);
+        collected
+            .into_iter()
+            .flat_map(|(_, sources)| sources)
+            .collect()
+    }
+
+    fn emit(&self, position: usize, origin: &str, sources: Vec<ContentMediaItemSource>) {
+        if sources.is_empty() {
+            return;
+        }
+
+        match &self.on_event {
+            Some(on_event) => on_event(SourcesEvent::Sources {
+                origin: origin.to_owned(),
+                sources,
+            }),
+            None => self.collected.lock().unwrap().push((position, sources)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use super::*;
+
+    fn video(link: &str) -> ContentMediaItemSource {
+        ContentMediaItemSource::Video {
+            link: link.into(),
+            description: link.into(),
+            headers: None,
+            hls_proxy: false,
+        }
+    }
+
+    async fn delayed(
+        ms: u64,
+        res: SupplierResult<Vec<ContentMediaItemSource>>,
+    ) -> SupplierResult<Vec<ContentMediaItemSource>> {
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+        res
+    }
+
+    #[tokio::test]
+    async fn should_stream_batches_in_completion_order() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let events_ref = events.clone();
+        let sink = SourcesSink::new(move |event| events_ref.lock().unwrap().push(event));
+
+        sink.fan_out([
+            ("slow".to_owned(), delayed(100, Ok(vec![video("slow")]))),
+            ("fast".to_owned(), delayed(10, Ok(vec![video("fast")]))),
+            (
+                "broken".to_owned(),
+                delayed(20, Err(SupplierError::parse("layout changed"))),
+            ),
+        ])
+        .await;
+        sink.complete();
+
+        let origins: Vec<_> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|event| match event {
+                SourcesEvent::Sources { origin, .. } => origin.clone(),
+                SourcesEvent::Completed { failed } => format!("completed {failed:?}"),
+                SourcesEvent::Error { error } => format!("error {error}"),
+            })
+            .collect();
+
+        assert_eq!(origins, ["fast", "slow", r#"completed ["broken"]"#]);
+    }
+
+    #[tokio::test]
+    async fn should_finish_stream_with_error() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let events_ref = events.clone();
+        let sink = SourcesSink::new(move |event| events_ref.lock().unwrap().push(event));
+
+        sink.add("first", vec![video("first")]);
+        sink.abort(SupplierError::Cancelled);
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            events.last(),
+            Some(SourcesEvent::Error {
+                error: SupplierError::Cancelled
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_collect_batches_in_start_order() {
+        let sink = SourcesSink::default();
+
+        sink.fan_out([
+            ("slow".to_owned(), delayed(50, Ok(vec![video("slow")]))),
+            ("fast".to_owned(), delayed(10, Ok(vec![video("fast")]))),
+        ])
+        .await;
+
+        let links: Vec<_> = sink
+            .into_sources()
+            .into_iter()
+            .map(|source| match source {
+                ContentMediaItemSource::Video { link, .. } => link,
+                _ => unreachable!(),
+            })
+            .collect();
+
+        assert_eq!(links, ["slow", "fast"]);
+    }
+}
@@ -58,6 +58,31 @@ pub fn to_title_case(input: &str) -> String {
         .join(" ")
 }
 
+/// Lowercase alphanumeric words only, release year is dropped, used to match same title across sites
+pub fn normalize_title(title: &str) -> String {
+    let without_year = year_regex().replace_all(title, " ");
+
+    without_year
+        .chars()
+        .map(|ch| if ch.is_alphanumeric() { ch } else { ' ' })
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn extract_year(text: &str) -> Option<u16> {
+    year_regex()
+        .find(text)
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+fn year_regex() -> &'static Regex {
+    static YEAR_RE: OnceLock<Regex> = OnceLock::new();
+    YEAR_RE.get_or_init(|| Regex::new(r#"\b(19|20)\d{2}\b"#).unwrap())
+}
+
 pub fn extract_css_background_url(css_style: &str) -> Option<String> {
     static BACKGROUND_URL_RE: OnceLock<Regex> = OnceLock::new();
     BACKGROUND_URL_RE
@@ -111,6 +136,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_normalize_title() {
+        assert_eq!(normalize_title("Frieren: Beyond Journey's End (2023)"), "frieren beyond journey s end");
+        assert_eq!(normalize_title("  Клинок,  що знищує демонів "), "клинок що знищує демонів");
+        assert_eq!(extract_year("Frieren (2023)"), Some(2023));
+        assert_eq!(extract_year("Episode 12"), None);
+    }
+
     #[test]
     fn should_return_none_for_invalid_css() {
         let css = "color: red;";